@@ -11,9 +11,14 @@ import {
 } from "@wormhole-foundation/wormhole-query-sdk";
 import axios from "axios";
 import { Logger } from "winston";
-import { getWormholeBridgeData } from "../tests/helpers/config";
+import {
+  deriveConfigKey,
+  getWormholeBridgeData,
+} from "../tests/helpers/config";
 import { deriveGuardianSetKey } from "../tests/helpers/guardianSet";
 import { deriveLatestRootKey } from "../tests/helpers/latestRoot";
+import { deriveRelayerAllowlistKey } from "../tests/helpers/relayerAllowlist";
+import { deriveVerificationTypeConfigKey } from "../tests/helpers/verificationTypeConfig";
 import { cleanUpRoots } from "./cleanup";
 import { getEnv } from "./env";
 
@@ -26,8 +31,7 @@ const {
   CLEANUP,
   ETH_RPC_URL,
   ETH_CHAIN_ID,
-  ETH_WORLD_ID_IDENTITY_MANAGER,
-  LATEST_ROOT_SIGNATURE,
+  VERIFICATION_TYPE,
   coreBridgeAddress,
   mockGuardianSetIndex,
   provider,
@@ -39,21 +43,48 @@ async function sleep(timeout: number) {
   return new Promise((resolve) => setTimeout(resolve, timeout));
 }
 
+type QuerySource = {
+  chainId: number;
+  contract: string;
+  selector: string;
+};
+
+// The source is read from the verification type's config, since it may change after initialization.
+async function getQuerySource(): Promise<QuerySource> {
+  const { source } = await program.account.verificationTypeConfig.fetch(
+    deriveVerificationTypeConfigKey(program.programId, VERIFICATION_TYPE)
+  );
+  if (!source.query) {
+    throw new Error(
+      `Verification type ${VERIFICATION_TYPE} does not accept roots from a query`
+    );
+  }
+  if (source.query.chainId !== ETH_CHAIN_ID) {
+    throw new Error(
+      `Verification type ${VERIFICATION_TYPE} queries chain ${source.query.chainId}, but ETH_RPC_URL serves chain ${ETH_CHAIN_ID}`
+    );
+  }
+  return {
+    chainId: source.query.chainId,
+    contract: `0x${Buffer.from(source.query.contract).toString("hex")}`,
+    selector: `0x${Buffer.from(source.query.selector).toString("hex")}`,
+  };
+}
+
 type RootHashAndBlockNumber = {
   hash: string;
   blockNumber: bigint;
 };
 
-async function getLatestEthereumRoot(): Promise<RootHashAndBlockNumber> {
+async function getLatestEthereumRoot(
+  source: QuerySource
+): Promise<RootHashAndBlockNumber> {
   const response = await axios.post(ETH_RPC_URL, [
     {
       jsonrpc: "2.0",
       id: 0,
       method: "eth_call",
-      params: [
-        { to: ETH_WORLD_ID_IDENTITY_MANAGER, data: LATEST_ROOT_SIGNATURE },
-        "latest",
-      ],
+      params: [{ to: source.contract, data: source.selector }, "latest"],
     },
     {
       jsonrpc: "2.0",
@@ -78,7 +109,7 @@ async function getLatestEthereumRoot(): Promise<RootHashAndBlockNumber> {
 
 async function getLatestSolanaRoot(): Promise<RootHashAndBlockNumber> {
   const latestRoot = await program.account.latestRoot.fetch(
-    deriveLatestRootKey(program.programId, 0)
+    deriveLatestRootKey(program.programId, VERIFICATION_TYPE)
   );
   const hash = Buffer.from(latestRoot.root).toString("hex");
   const blockNumber = BigInt(latestRoot.readBlockNumber.toString());
@@ -97,19 +128,20 @@ async function getGuardianSetIndex(): Promise<number> {
 }
 
 async function queryEthLatestRoot(
+  source: QuerySource,
   blockNumber: bigint
 ): Promise<QueryProxyQueryResponse> {
   const query = new QueryRequest(42, [
     new PerChainQueryRequest(
-      ETH_CHAIN_ID,
+      source.chainId,
       new EthCallQueryRequest(`0x${blockNumber.toString(16)}`, [
-        { to: ETH_WORLD_ID_IDENTITY_MANAGER, data: LATEST_ROOT_SIGNATURE },
+        { to: source.contract, data: source.selector },
       ])
     ),
   ]);
   if (MOCK) {
     const mock = new QueryProxyMock({
-      [ETH_CHAIN_ID]: ETH_RPC_URL,
+      [source.chainId]: ETH_RPC_URL,
     });
     return await mock.mock(query);
   }
@@ -124,7 +156,8 @@ async function queryEthLatestRoot(
 }
 
 async function syncRoot(logger: Logger) {
-  const ethRoot = await getLatestEthereumRoot();
+  const source = await getQuerySource();
+  const ethRoot = await getLatestEthereumRoot(source);
   const solRoot = await getLatestSolanaRoot();
   logger.info(`Eth root: ${ethRoot.blockNumber.toString()} ${ethRoot.hash}`);
   logger.info(`Sol root: ${solRoot.blockNumber.toString()} ${solRoot.hash}`);
@@ -133,13 +166,17 @@ async function syncRoot(logger: Logger) {
     ethRoot.blockNumber > solRoot.blockNumber
   ) {
     logger.debug("Eth root is newer, querying...");
-    const queryResponse = await queryEthLatestRoot(ethRoot.blockNumber);
+    const queryResponse = await queryEthLatestRoot(source, ethRoot.blockNumber);
     const mockEthCallQueryResponse = QueryResponse.from(queryResponse.bytes)
       .responses[0].response as EthCallQueryResponse;
     const newRootHash = mockEthCallQueryResponse.results[0].substring(2);
     if (newRootHash === ethRoot.hash) {
       logger.debug("Query successful! Updating...");
       const guardianSetIndex = await getGuardianSetIndex();
+      // The payer must be on the relayer allowlist while it is enabled.
+      const config = await program.account.config.fetch(
+        deriveConfigKey(program.programId)
+      );
       const signatureSet = web3.Keypair.generate();
       const signatureData = signaturesToSolanaArray(queryResponse.signatures);
       await program.methods
@@ -151,6 +188,7 @@ async function syncRoot(logger: Logger) {
         .updateRootWithQuery(
           Buffer.from(queryResponse.bytes, "hex"),
           [...Buffer.from(newRootHash, "hex")],
+          [VERIFICATION_TYPE],
           guardianSetIndex
         )
         .accountsPartial({
@@ -159,11 +197,14 @@ async function syncRoot(logger: Logger) {
             guardianSetIndex
           ),
           guardianSignatures: signatureSet.publicKey,
+          relayerAllowlist: config.relayerAllowlistEnabled
+            ? deriveRelayerAllowlistKey(program.programId)
+            : null,
         })
         .preInstructions(
           NETWORK === "mainnet"
             ? [
-                anchor.web3.ComputeBudgetProgram.setComputeUnitLimit({
+                web3.ComputeBudgetProgram.setComputeUnitLimit({
                   units: 420_000,
                 }),
               ]
@@ -4,7 +4,6 @@ import { Program } from "@coral-xyz/anchor";
 import { BN } from "bn.js";
 import { Logger } from "winston";
 import { SolanaWorldIdProgram } from "../target/types/solana_world_id_program";
-import { deriveConfigKey } from "../tests/helpers/config";
 import { deriveLatestRootKey } from "../tests/helpers/latestRoot";
 
 export async function cleanUpRoots(
@@ -12,10 +11,12 @@ export async function cleanUpRoots(
   logger: Logger
 ) {
   logger.info(`Cleaning up roots...`);
-  const config = await program.account.config.fetch(
-    deriveConfigKey(program.programId)
+  const rootExpiries = new Map(
+    (await program.account.verificationTypeConfig.all()).map((c) => [
+      c.account.verificationType[0],
+      c.account.rootExpiry,
+    ])
   );
-  const latestRoot = deriveLatestRootKey(program.programId, 0);
   const slot = await program.provider.connection.getSlot();
   const blockTime = new BN(
     await program.provider.connection.getBlockTime(slot)
@@ -23,11 +24,19 @@ export async function cleanUpRoots(
   const roots = await program.account.root.all();
   logger.debug(`Found ${roots.length} root(s)`);
   for (const root of roots) {
+    const verificationType = root.account.verificationType[0];
+    const rootExpiry = rootExpiries.get(verificationType);
+    const rootHex = Buffer.from(root.account.root).toString("hex");
+    if (rootExpiry === undefined) {
+      logger.warn(
+        `Skipping root ${rootHex} account ${root.publicKey.toString()} of unknown verification type ${verificationType}`
+      );
+      continue;
+    }
     // programs/solana-world-id-program/src/state/root.rs
     const readTimeInSeconds = root.account.readBlockTime.div(new BN(1_000_000));
-    const expiry = readTimeInSeconds.add(config.rootExpiry);
+    const expiry = readTimeInSeconds.add(rootExpiry);
     const isActive = expiry.gte(blockTime);
-    const rootHex = Buffer.from(root.account.root).toString("hex");
     if (isActive) {
       logger.debug(
         `Skipping active root ${rootHex} account ${root.publicKey.toString()}, expires in ${expiry.sub(
@@ -38,7 +47,13 @@ export async function cleanUpRoots(
       try {
         const tx = await program.methods
           .cleanUpRoot()
-          .accounts({ root: root.publicKey, latestRoot: latestRoot })
+          .accounts({
+            root: root.publicKey,
+            latestRoot: deriveLatestRootKey(
+              program.programId,
+              verificationType
+            ),
+          })
           .rpc();
         logger.info(
           `Cleaned up root ${rootHex} account ${root.publicKey.toString()} in tx ${tx}`
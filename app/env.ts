@@ -63,6 +63,9 @@ export function getEnv(needsQueryApiKeyOrMock: boolean = false) {
   // web3.eth.abi.encodeFunctionSignature("latestRoot()");
   const LATEST_ROOT_SIGNATURE = "0xd7b0fef1";
   envLogger.info(`Identity Manager: ${ETH_WORLD_ID_IDENTITY_MANAGER}`);
+  // Verification type 0 is the World ID Identity Manager on Ethereum.
+  const VERIFICATION_TYPE = parseInt(process.env.VERIFICATION_TYPE || "0");
+  envLogger.info(`Verification:     ${VERIFICATION_TYPE}`);
 
   const SOLANA_RPC_URL =
     process.env.SOLANA_RPC_URL ||
@@ -109,6 +112,7 @@ export function getEnv(needsQueryApiKeyOrMock: boolean = false) {
     ETH_CHAIN_ID,
     ETH_WORLD_ID_IDENTITY_MANAGER,
     LATEST_ROOT_SIGNATURE,
+    VERIFICATION_TYPE,
     SOLANA_RPC_URL,
     coreBridgeAddress,
     mockGuardianSetIndex,
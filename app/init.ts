@@ -4,7 +4,12 @@ import { web3 } from "@coral-xyz/anchor";
 import { BN } from "bn.js";
 import { getEnv } from "./env";
 
-const { program } = getEnv();
+const {
+  ETH_CHAIN_ID,
+  ETH_WORLD_ID_IDENTITY_MANAGER,
+  LATEST_ROOT_SIGNATURE,
+  program,
+} = getEnv();
 
 const programData = web3.PublicKey.findProgramAddressSync(
   [program.programId.toBuffer()],
@@ -17,6 +22,17 @@ const fiveMinutes = new BN(5 * 60);
     .initialize({
       rootExpiry: twentyFourHours,
       allowedUpdateStaleness: fiveMinutes,
+      // Source of verification type 0. Defaults to the network feature of the build.
+      ethChainId: ETH_CHAIN_ID,
+      ethWorldIdIdentityManager: [
+        ...Buffer.from(ETH_WORLD_ID_IDENTITY_MANAGER.substring(2), "hex"),
+      ],
+      latestRootSignature: [
+        ...Buffer.from(LATEST_ROOT_SIGNATURE.substring(2), "hex"),
+      ],
+      // Use the program defaults.
+      verifyingKeyActivationDelay: null,
+      configChangeDelay: null,
     })
     .accountsPartial({
       programData,
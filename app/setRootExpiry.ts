@@ -1,14 +1,48 @@
 // NETWORK=testnet WALLET=~/.config/solana/your-key.json npx tsx app/setRootExpiry.ts
+// Proposes the change, then executes it when run again after the config change delay.
 
 import { BN } from "bn.js";
+import { derivePendingConfigChangeKey } from "../tests/helpers/pendingConfigChange";
+import { deriveVerificationTypeConfigKey } from "../tests/helpers/verificationTypeConfig";
 import { getEnv } from "./env";
 
 const { program } = getEnv();
 
 (async () => {
   const expiryInSecs = new BN(60 * 60 * 24 * 7);
-  const tx = await program.methods.setRootExpiry(expiryInSecs).rpc();
+  // Verification type 0 is the World ID Identity Manager on Ethereum.
+  const change = {
+    rootExpiry: { verificationType: [0], rootExpiry: expiryInSecs },
+  };
+  const pendingConfigChange = derivePendingConfigChangeKey(
+    program.programId,
+    change
+  );
+  const pending = await program.account.pendingConfigChange.fetchNullable(
+    pendingConfigChange
+  );
+  if (pending === null) {
+    const tx = await program.methods
+      .proposeConfigChange(change)
+      .accountsPartial({ pendingConfigChange })
+      .rpc();
+    console.log(
+      `Successfully proposed root expiry (${expiryInSecs.toString()}s): ${tx}`
+    );
+    return;
+  }
+  const tx = await program.methods
+    .executeConfigChange()
+    .accountsPartial({
+      pendingConfigChange,
+      verificationTypeConfig: deriveVerificationTypeConfigKey(
+        program.programId,
+        0
+      ),
+      refundRecipient: pending.refundRecipient,
+    })
+    .rpc();
   console.log(
-    `Successfully set root expiry (${expiryInSecs.toString()}s): ${tx}`
+    `Successfully set root expiry (${pending.change.rootExpiry.rootExpiry.toString()}s): ${tx}`
   );
 })();
@@ -1,10 +1,18 @@
+// NETWORK=testnet WALLET=~/.config/solana/your-key.json npx tsx app/verifyGroth16Proof.ts
+
+import { web3 } from "@coral-xyz/anchor";
+import { deriveConfigKey } from "../tests/helpers/config";
+import { deriveLatestRootKey } from "../tests/helpers/latestRoot";
+import { deriveRootKey } from "../tests/helpers/root";
 import {
   appIdActionToExternalNullifierHash,
   hashToField,
 } from "../tests/helpers/utils/hashing";
+import { deriveVerificationTypeConfigKey } from "../tests/helpers/verificationTypeConfig";
+import { deriveVerifyingKeyKey } from "../tests/helpers/verifyingKey";
 import { getEnv } from "./env";
 
-const { program } = getEnv();
+const { VERIFICATION_TYPE, program } = getEnv();
 (async () => {
   // This is the default anvil wallet
   const signal = "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266";
@@ -31,15 +39,42 @@ const { program } = getEnv();
     ...Buffer.from(result.nullifier_hash.substring(2), "hex"),
   ];
   const proof = [...Buffer.from(result.proof.substring(2), "hex")];
+  const verificationTypeConfig = deriveVerificationTypeConfigKey(
+    program.programId,
+    VERIFICATION_TYPE
+  );
+  // The verifying key is omitted when the verification type uses the built-in key.
+  const { verifyingKeyId } =
+    await program.account.verificationTypeConfig.fetch(verificationTypeConfig);
   const tx = await program.methods
     .verifyGroth16Proof(
       rootHash,
-      [0],
+      [VERIFICATION_TYPE],
       signalHash,
       nullifierHash,
       externalNullifierHash,
       proof
     )
+    .accountsPartial({
+      root: deriveRootKey(
+        program.programId,
+        Buffer.from(rootHash),
+        VERIFICATION_TYPE
+      ),
+      latestRoot: deriveLatestRootKey(program.programId, VERIFICATION_TYPE),
+      config: deriveConfigKey(program.programId),
+      verificationTypeConfig,
+      verifyingKey:
+        verifyingKeyId === null
+          ? null
+          : deriveVerifyingKeyKey(program.programId, verifyingKeyId[0]),
+      instructions: web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+      eventAuthority: web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("__event_authority")],
+        program.programId
+      )[0],
+      program: program.programId,
+    })
     .rpc();
   console.log("Successfully verified:", tx);
 })();
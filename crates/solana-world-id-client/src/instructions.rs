@@ -0,0 +1,1513 @@
+//! Builders for every instruction of the Solana World ID Program.
+//!
+//! Accounts which are fixed or derived from the instruction arguments are filled in, so callers
+//! only provide signers and accounts which cannot be derived.
+
+use anchor_lang::{
+    prelude::*,
+    solana_program::{bpf_loader_upgradeable, instruction::Instruction, system_program, sysvar},
+    InstructionData,
+};
+use solana_world_id_program::{
+    accounts, instruction,
+    state::{ConfigChange, Role, Root},
+    BatchVerificationMode, Groth16ProofArgs, InitializeArgs, RegisterVerificationTypeArgs,
+    RegisterVerifyingKeyArgs, ID,
+};
+
+use crate::pda::{
+    derive_admin_set_key, derive_config_key, derive_event_authority_key, derive_guardian_set_key,
+    derive_latest_root_key, derive_nullifier_key, derive_pending_config_change_key,
+    derive_program_data_key, derive_relayer_allowlist_key, derive_root_key,
+    derive_upgrade_lock_key, derive_verification_type_config_key, derive_verifying_key_key,
+};
+
+fn build(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+pub fn initialize(payer: Pubkey, deployer: Pubkey, args: InitializeArgs) -> Instruction {
+    let verification_type = *Root::VERIFICATION_TYPE_QUERY;
+    build(
+        accounts::Initialize {
+            payer,
+            deployer,
+            program_data: derive_program_data_key(),
+            config: derive_config_key(),
+            latest_root: derive_latest_root_key(verification_type),
+            verification_type_config: derive_verification_type_config_key(verification_type),
+            system_program: system_program::ID,
+        },
+        instruction::Initialize { args },
+    )
+}
+
+/// `owner` is the owner stored in the legacy config.
+pub fn migrate_config(payer: Pubkey, owner: Pubkey) -> Instruction {
+    build(
+        accounts::MigrateConfig {
+            payer,
+            owner,
+            config: derive_config_key(),
+            verification_type_config: derive_verification_type_config_key(
+                *Root::VERIFICATION_TYPE_QUERY,
+            ),
+            system_program: system_program::ID,
+        },
+        instruction::MigrateConfig {},
+    )
+}
+
+pub fn migrate_root(payer: Pubkey, root_hash: [u8; 32], verification_type: [u8; 1]) -> Instruction {
+    build(
+        accounts::MigrateRoot {
+            payer,
+            root: derive_root_key(root_hash, verification_type),
+            system_program: system_program::ID,
+        },
+        instruction::MigrateRoot {},
+    )
+}
+
+/// `guardian_signatures` must sign when the account is created, but not when appending to it.
+pub fn post_signatures(
+    payer: Pubkey,
+    guardian_signatures: Pubkey,
+    signatures: Vec<[u8; 66]>,
+    total_signatures: u8,
+) -> Instruction {
+    build(
+        accounts::PostSignatures {
+            payer,
+            guardian_signatures,
+            system_program: system_program::ID,
+        },
+        instruction::PostSignatures {
+            guardian_signatures: signatures,
+            total_signatures,
+        },
+    )
+}
+
+/// `refund_recipient` must be the payer of `guardian_signatures`, which is closed to it.
+/// `relayer_allowlist` passes the relayer allowlist, which is required while it is enabled.
+#[allow(clippy::too_many_arguments)]
+pub fn update_root_with_query(
+    payer: Pubkey,
+    relayer_allowlist: bool,
+    guardian_signatures: Pubkey,
+    refund_recipient: Pubkey,
+    bytes: Vec<u8>,
+    root_hash: [u8; 32],
+    verification_type: [u8; 1],
+    guardian_set_index: u32,
+) -> Instruction {
+    build(
+        accounts::UpdateRootWithQuery {
+            payer,
+            guardian_set: derive_guardian_set_key(guardian_set_index),
+            guardian_signatures,
+            root: derive_root_key(root_hash, verification_type),
+            latest_root: derive_latest_root_key(verification_type),
+            config: derive_config_key(),
+            relayer_allowlist: relayer_allowlist.then(derive_relayer_allowlist_key),
+            verification_type_config: derive_verification_type_config_key(verification_type),
+            refund_recipient,
+            system_program: system_program::ID,
+            event_authority: derive_event_authority_key(),
+            program: ID,
+        },
+        instruction::UpdateRootWithQuery {
+            bytes,
+            root_hash,
+            verification_type,
+            guardian_set_index,
+        },
+    )
+}
+
+/// Must be immediately preceded by a secp256k1 program instruction which verifies the guardian
+/// signatures over the query message hash. `relayer_allowlist` passes the relayer allowlist, which
+/// is required while it is enabled.
+pub fn update_root_with_query_precompile(
+    payer: Pubkey,
+    relayer_allowlist: bool,
+    bytes: Vec<u8>,
+    root_hash: [u8; 32],
+    verification_type: [u8; 1],
+    guardian_set_index: u32,
+) -> Instruction {
+    build(
+        accounts::UpdateRootWithQueryPrecompile {
+            payer,
+            guardian_set: derive_guardian_set_key(guardian_set_index),
+            root: derive_root_key(root_hash, verification_type),
+            latest_root: derive_latest_root_key(verification_type),
+            config: derive_config_key(),
+            relayer_allowlist: relayer_allowlist.then(derive_relayer_allowlist_key),
+            verification_type_config: derive_verification_type_config_key(verification_type),
+            instructions: sysvar::instructions::ID,
+            system_program: system_program::ID,
+            event_authority: derive_event_authority_key(),
+            program: ID,
+        },
+        instruction::UpdateRootWithQueryPrecompile {
+            bytes,
+            root_hash,
+            verification_type,
+            guardian_set_index,
+        },
+    )
+}
+
+pub fn update_root_with_vaa(
+    payer: Pubkey,
+    posted_vaa: Pubkey,
+    root_hash: [u8; 32],
+    verification_type: [u8; 1],
+) -> Instruction {
+    build(
+        accounts::UpdateRootWithVaa {
+            payer,
+            posted_vaa,
+            root: derive_root_key(root_hash, verification_type),
+            latest_root: derive_latest_root_key(verification_type),
+            config: derive_config_key(),
+            verification_type_config: derive_verification_type_config_key(verification_type),
+            system_program: system_program::ID,
+            event_authority: derive_event_authority_key(),
+            program: ID,
+        },
+        instruction::UpdateRootWithVaa {
+            root_hash,
+            verification_type,
+        },
+    )
+}
+
+/// `refund_recipient` must be the payer of the root.
+pub fn clean_up_root(
+    root_hash: [u8; 32],
+    verification_type: [u8; 1],
+    refund_recipient: Pubkey,
+) -> Instruction {
+    build(
+        accounts::CleanUpRoot {
+            root: derive_root_key(root_hash, verification_type),
+            latest_root: derive_latest_root_key(verification_type),
+            config: derive_config_key(),
+            verification_type_config: derive_verification_type_config_key(verification_type),
+            refund_recipient,
+            event_authority: derive_event_authority_key(),
+            program: ID,
+        },
+        instruction::CleanUpRoot {},
+    )
+}
+
+pub fn close_signatures(guardian_signatures: Pubkey, refund_recipient: Pubkey) -> Instruction {
+    build(
+        accounts::CloseSignatures {
+            guardian_signatures,
+            refund_recipient,
+            event_authority: derive_event_authority_key(),
+            program: ID,
+        },
+        instruction::CloseSignatures {},
+    )
+}
+
+pub fn transfer_ownership(owner: Pubkey, new_owner: Pubkey) -> Instruction {
+    build(
+        accounts::TransferOwnership {
+            config: derive_config_key(),
+            owner,
+            new_owner,
+            upgrade_lock: derive_upgrade_lock_key(),
+            program_data: derive_program_data_key(),
+            bpf_loader_upgradeable_program: bpf_loader_upgradeable::ID,
+            event_authority: derive_event_authority_key(),
+            program: ID,
+        },
+        instruction::TransferOwnership {},
+    )
+}
+
+/// Signed by the pending owner to accept, or by the current owner to cancel.
+pub fn claim_ownership(new_owner: Pubkey) -> Instruction {
+    build(
+        accounts::ClaimOwnership {
+            config: derive_config_key(),
+            upgrade_lock: derive_upgrade_lock_key(),
+            new_owner,
+            program_data: derive_program_data_key(),
+            bpf_loader_upgradeable_program: bpf_loader_upgradeable::ID,
+            event_authority: derive_event_authority_key(),
+            program: ID,
+        },
+        instruction::ClaimOwnership {},
+    )
+}
+
+pub fn register_verification_type(
+    payer: Pubkey,
+    owner: Pubkey,
+    verification_type: [u8; 1],
+    args: RegisterVerificationTypeArgs,
+) -> Instruction {
+    build(
+        accounts::RegisterVerificationType {
+            payer,
+            owner,
+            config: derive_config_key(),
+            verification_type_config: derive_verification_type_config_key(verification_type),
+            latest_root: derive_latest_root_key(verification_type),
+            system_program: system_program::ID,
+            event_authority: derive_event_authority_key(),
+            program: ID,
+        },
+        instruction::RegisterVerificationType {
+            verification_type,
+            args,
+        },
+    )
+}
+
+pub fn set_verification_type_enabled(
+    authority: Pubkey,
+    verification_type: [u8; 1],
+    enabled: bool,
+) -> Instruction {
+    build(
+        accounts::SetVerificationTypeEnabled {
+            authority,
+            config: derive_config_key(),
+            verification_type_config: derive_verification_type_config_key(verification_type),
+            event_authority: derive_event_authority_key(),
+            program: ID,
+        },
+        instruction::SetVerificationTypeEnabled {
+            verification_type,
+            enabled,
+        },
+    )
+}
+
+pub fn register_verifying_key(
+    payer: Pubkey,
+    owner: Pubkey,
+    key_id: [u8; 1],
+    args: RegisterVerifyingKeyArgs,
+) -> Instruction {
+    build(
+        accounts::RegisterVerifyingKey {
+            payer,
+            owner,
+            config: derive_config_key(),
+            verifying_key: derive_verifying_key_key(key_id),
+            system_program: system_program::ID,
+            event_authority: derive_event_authority_key(),
+            program: ID,
+        },
+        instruction::RegisterVerifyingKey { key_id, args },
+    )
+}
+
+pub fn retire_verifying_key(owner: Pubkey, key_id: [u8; 1]) -> Instruction {
+    build(
+        accounts::RetireVerifyingKey {
+            owner,
+            config: derive_config_key(),
+            verifying_key: derive_verifying_key_key(key_id),
+            event_authority: derive_event_authority_key(),
+            program: ID,
+        },
+        instruction::RetireVerifyingKey { key_id },
+    )
+}
+
+/// `verifying_key_id` is `None` to switch the verification type back to the built-in key.
+pub fn propose_verifying_key(
+    owner: Pubkey,
+    verification_type: [u8; 1],
+    verifying_key_id: Option<[u8; 1]>,
+    grace_period: u64,
+) -> Instruction {
+    build(
+        accounts::ProposeVerifyingKey {
+            owner,
+            config: derive_config_key(),
+            verification_type_config: derive_verification_type_config_key(verification_type),
+            verifying_key: verifying_key_id.map(derive_verifying_key_key),
+            event_authority: derive_event_authority_key(),
+            program: ID,
+        },
+        instruction::ProposeVerifyingKey {
+            verification_type,
+            grace_period,
+        },
+    )
+}
+
+/// `verifying_key_id` must be the pending verifying key of the verification type.
+pub fn activate_verifying_key(
+    owner: Pubkey,
+    verification_type: [u8; 1],
+    verifying_key_id: Option<[u8; 1]>,
+) -> Instruction {
+    build(
+        accounts::ActivateVerifyingKey {
+            owner,
+            config: derive_config_key(),
+            verification_type_config: derive_verification_type_config_key(verification_type),
+            verifying_key: verifying_key_id.map(derive_verifying_key_key),
+            event_authority: derive_event_authority_key(),
+            program: ID,
+        },
+        instruction::ActivateVerifyingKey { verification_type },
+    )
+}
+
+/// Grants `role` to `grantee`, replacing its current holder.
+pub fn grant_role(owner: Pubkey, role: Role, grantee: Pubkey) -> Instruction {
+    build(
+        accounts::GrantRole {
+            owner,
+            config: derive_config_key(),
+            event_authority: derive_event_authority_key(),
+            program: ID,
+        },
+        instruction::GrantRole { role, grantee },
+    )
+}
+
+pub fn revoke_role(owner: Pubkey, role: Role) -> Instruction {
+    build(
+        accounts::RevokeRole {
+            owner,
+            config: derive_config_key(),
+            event_authority: derive_event_authority_key(),
+            program: ID,
+        },
+        instruction::RevokeRole { role },
+    )
+}
+
+/// `authority` is the owner or the pauser. `flags` is a combination of `Config::PAUSE_*`.
+pub fn pause(authority: Pubkey, flags: u8) -> Instruction {
+    build(
+        accounts::Pause {
+            authority,
+            config: derive_config_key(),
+            event_authority: derive_event_authority_key(),
+            program: ID,
+        },
+        instruction::Pause { flags },
+    )
+}
+
+pub fn unpause(owner: Pubkey, flags: u8) -> Instruction {
+    build(
+        accounts::Unpause {
+            owner,
+            config: derive_config_key(),
+            event_authority: derive_event_authority_key(),
+            program: ID,
+        },
+        instruction::Unpause { flags },
+    )
+}
+
+/// `previous_root_hash` is the root which the latest root is rolled back to when revoking the
+/// latest root. Without it, the revoked root stays the latest root.
+pub fn revoke_root(
+    authority: Pubkey,
+    root_hash: [u8; 32],
+    verification_type: [u8; 1],
+    previous_root_hash: Option<[u8; 32]>,
+) -> Instruction {
+    build(
+        accounts::RevokeRoot {
+            authority,
+            config: derive_config_key(),
+            root: derive_root_key(root_hash, verification_type),
+            latest_root: derive_latest_root_key(verification_type),
+            verification_type_config: derive_verification_type_config_key(verification_type),
+            previous_root: previous_root_hash
+                .map(|previous_root_hash| derive_root_key(previous_root_hash, verification_type)),
+            event_authority: derive_event_authority_key(),
+            program: ID,
+        },
+        instruction::RevokeRoot {
+            root_hash,
+            verification_type,
+        },
+    )
+}
+
+/// `authority` is the owner or the config manager.
+pub fn propose_config_change(
+    payer: Pubkey,
+    authority: Pubkey,
+    change: ConfigChange,
+) -> Instruction {
+    build(
+        accounts::ProposeConfigChange {
+            payer,
+            authority,
+            config: derive_config_key(),
+            pending_config_change: derive_pending_config_change_key(&change),
+            system_program: system_program::ID,
+            event_authority: derive_event_authority_key(),
+            program: ID,
+        },
+        instruction::ProposeConfigChange { change },
+    )
+}
+
+/// `change` must be the pending change and `refund_recipient` the payer of its proposal.
+pub fn execute_config_change(
+    authority: Pubkey,
+    change: ConfigChange,
+    refund_recipient: Pubkey,
+) -> Instruction {
+    build(
+        accounts::ExecuteConfigChange {
+            authority,
+            config: derive_config_key(),
+            pending_config_change: derive_pending_config_change_key(&change),
+            verification_type_config: change
+                .verification_type()
+                .map(derive_verification_type_config_key),
+            refund_recipient,
+            event_authority: derive_event_authority_key(),
+            program: ID,
+        },
+        instruction::ExecuteConfigChange {},
+    )
+}
+
+/// `change` must be the pending change and `refund_recipient` the payer of its proposal.
+pub fn cancel_config_change(
+    authority: Pubkey,
+    change: ConfigChange,
+    refund_recipient: Pubkey,
+) -> Instruction {
+    build(
+        accounts::CancelConfigChange {
+            authority,
+            config: derive_config_key(),
+            pending_config_change: derive_pending_config_change_key(&change),
+            refund_recipient,
+            event_authority: derive_event_authority_key(),
+            program: ID,
+        },
+        instruction::CancelConfigChange {},
+    )
+}
+
+pub fn initialize_admin_set(
+    payer: Pubkey,
+    owner: Pubkey,
+    members: Vec<Pubkey>,
+    threshold: u8,
+) -> Instruction {
+    build(
+        accounts::InitializeAdminSet {
+            payer,
+            owner,
+            config: derive_config_key(),
+            admin_set: derive_admin_set_key(),
+            system_program: system_program::ID,
+            event_authority: derive_event_authority_key(),
+            program: ID,
+        },
+        instruction::InitializeAdminSet { members, threshold },
+    )
+}
+
+fn update_admin_set() -> accounts::UpdateAdminSet {
+    accounts::UpdateAdminSet {
+        admin_set: derive_admin_set_key(),
+        event_authority: derive_event_authority_key(),
+        program: ID,
+    }
+}
+
+/// Signed by the admin set, so it must be wrapped with [`execute_as_admin_set`].
+pub fn add_admin_set_member(member: Pubkey) -> Instruction {
+    build(
+        update_admin_set(),
+        instruction::AddAdminSetMember { member },
+    )
+}
+
+/// Signed by the admin set, so it must be wrapped with [`execute_as_admin_set`].
+pub fn remove_admin_set_member(member: Pubkey) -> Instruction {
+    build(
+        update_admin_set(),
+        instruction::RemoveAdminSetMember { member },
+    )
+}
+
+/// Signed by the admin set, so it must be wrapped with [`execute_as_admin_set`].
+pub fn set_admin_set_threshold(threshold: u8) -> Instruction {
+    build(
+        update_admin_set(),
+        instruction::SetAdminSetThreshold { threshold },
+    )
+}
+
+/// Wraps `ix`, e.g. an owner instruction built with the admin set PDA
+/// ([`derive_admin_set_key`]) as its owner, to be signed by the admin set once `signers` sign.
+pub fn execute_as_admin_set(ix: Instruction, signers: &[Pubkey]) -> Instruction {
+    let admin_set = derive_admin_set_key();
+    let mut execute = build(
+        accounts::ExecuteAsAdminSet {
+            admin_set,
+            target_program: ix.program_id,
+            event_authority: derive_event_authority_key(),
+            program: ID,
+        },
+        instruction::ExecuteAsAdminSet { data: ix.data },
+    );
+    // Only the program can sign for the admin set.
+    execute
+        .accounts
+        .extend(ix.accounts.into_iter().map(|meta| AccountMeta {
+            is_signer: meta.is_signer && meta.pubkey != admin_set,
+            ..meta
+        }));
+    execute.accounts.extend(
+        signers
+            .iter()
+            .map(|signer| AccountMeta::new_readonly(*signer, true)),
+    );
+    execute
+}
+
+pub fn initialize_relayer_allowlist(
+    payer: Pubkey,
+    owner: Pubkey,
+    relayers: Vec<Pubkey>,
+) -> Instruction {
+    build(
+        accounts::InitializeRelayerAllowlist {
+            payer,
+            owner,
+            config: derive_config_key(),
+            relayer_allowlist: derive_relayer_allowlist_key(),
+            system_program: system_program::ID,
+            event_authority: derive_event_authority_key(),
+            program: ID,
+        },
+        instruction::InitializeRelayerAllowlist { relayers },
+    )
+}
+
+fn update_relayer_allowlist(owner: Pubkey) -> accounts::UpdateRelayerAllowlist {
+    accounts::UpdateRelayerAllowlist {
+        owner,
+        config: derive_config_key(),
+        relayer_allowlist: derive_relayer_allowlist_key(),
+        event_authority: derive_event_authority_key(),
+        program: ID,
+    }
+}
+
+pub fn add_relayer(owner: Pubkey, relayer: Pubkey) -> Instruction {
+    build(
+        update_relayer_allowlist(owner),
+        instruction::AddRelayer { relayer },
+    )
+}
+
+pub fn remove_relayer(owner: Pubkey, relayer: Pubkey) -> Instruction {
+    build(
+        update_relayer_allowlist(owner),
+        instruction::RemoveRelayer { relayer },
+    )
+}
+
+pub fn set_relayer_allowlist_enabled(owner: Pubkey, enabled: bool) -> Instruction {
+    build(
+        accounts::SetRelayerAllowlistEnabled {
+            owner,
+            config: derive_config_key(),
+            relayer_allowlist: derive_relayer_allowlist_key(),
+            event_authority: derive_event_authority_key(),
+            program: ID,
+        },
+        instruction::SetRelayerAllowlistEnabled { enabled },
+    )
+}
+
+/// `verifying_key_id` must be the verifying key of the verification type, `None` when it uses the
+/// built-in key.
+pub fn verify_groth16_proof(
+    verification_type: [u8; 1],
+    verifying_key_id: Option<[u8; 1]>,
+    args: Groth16ProofArgs,
+) -> Instruction {
+    build(
+        accounts::VerifyGroth16Proof {
+            root: derive_root_key(args.root_hash, verification_type),
+            latest_root: derive_latest_root_key(verification_type),
+            config: derive_config_key(),
+            verification_type_config: derive_verification_type_config_key(verification_type),
+            verifying_key: verifying_key_id.map(derive_verifying_key_key),
+            instructions: sysvar::instructions::ID,
+            event_authority: derive_event_authority_key(),
+            program: ID,
+        },
+        instruction::VerifyGroth16Proof {
+            root_hash: args.root_hash,
+            verification_type,
+            signal_hash: args.signal_hash,
+            nullifier_hash: args.nullifier_hash,
+            external_nullifier_hash: args.external_nullifier_hash,
+            proof: args.proof,
+        },
+    )
+}
+
+/// Verifies a proof whose signal hash and external nullifier hash are derived on-chain from
+/// `signal`, `app_id` and `action`.
+#[allow(clippy::too_many_arguments)]
+pub fn verify_groth16_proof_with_raw_inputs(
+    verification_type: [u8; 1],
+    verifying_key_id: Option<[u8; 1]>,
+    root_hash: [u8; 32],
+    app_id: String,
+    action: String,
+    signal: Vec<u8>,
+    nullifier_hash: [u8; 32],
+    proof: [u8; 256],
+) -> Instruction {
+    build(
+        accounts::VerifyGroth16ProofWithRawInputs {
+            root: derive_root_key(root_hash, verification_type),
+            latest_root: derive_latest_root_key(verification_type),
+            config: derive_config_key(),
+            verification_type_config: derive_verification_type_config_key(verification_type),
+            verifying_key: verifying_key_id.map(derive_verifying_key_key),
+            instructions: sysvar::instructions::ID,
+            event_authority: derive_event_authority_key(),
+            program: ID,
+        },
+        instruction::VerifyGroth16ProofWithRawInputs {
+            root_hash,
+            verification_type,
+            app_id,
+            action,
+            signal,
+            nullifier_hash,
+            proof,
+        },
+    )
+}
+
+/// The distinct Root accounts of `proofs` are appended as remaining accounts.
+pub fn verify_groth16_proofs(
+    verification_type: [u8; 1],
+    verifying_key_id: Option<[u8; 1]>,
+    proofs: Vec<Groth16ProofArgs>,
+    mode: BatchVerificationMode,
+) -> Instruction {
+    let mut root_hashes: Vec<[u8; 32]> = Vec::with_capacity(proofs.len());
+    for args in proofs.iter() {
+        if !root_hashes.contains(&args.root_hash) {
+            root_hashes.push(args.root_hash);
+        }
+    }
+
+    let mut ix = build(
+        accounts::VerifyGroth16Proofs {
+            latest_root: derive_latest_root_key(verification_type),
+            config: derive_config_key(),
+            verification_type_config: derive_verification_type_config_key(verification_type),
+            verifying_key: verifying_key_id.map(derive_verifying_key_key),
+            instructions: sysvar::instructions::ID,
+            event_authority: derive_event_authority_key(),
+            program: ID,
+        },
+        instruction::VerifyGroth16Proofs {
+            verification_type,
+            proofs,
+            mode,
+        },
+    );
+    ix.accounts.extend(root_hashes.into_iter().map(|root_hash| {
+        AccountMeta::new_readonly(derive_root_key(root_hash, verification_type), false)
+    }));
+    ix
+}
+
+/// `payer` pays for the Nullifier account and may be a PDA of the calling program.
+pub fn verify_and_consume_nullifier(
+    payer: Pubkey,
+    verification_type: [u8; 1],
+    verifying_key_id: Option<[u8; 1]>,
+    args: Groth16ProofArgs,
+) -> Instruction {
+    build(
+        accounts::VerifyAndConsumeNullifier {
+            payer,
+            root: derive_root_key(args.root_hash, verification_type),
+            latest_root: derive_latest_root_key(verification_type),
+            config: derive_config_key(),
+            verification_type_config: derive_verification_type_config_key(verification_type),
+            verifying_key: verifying_key_id.map(derive_verifying_key_key),
+            nullifier: derive_nullifier_key(args.external_nullifier_hash, args.nullifier_hash),
+            instructions: sysvar::instructions::ID,
+            system_program: system_program::ID,
+            event_authority: derive_event_authority_key(),
+            program: ID,
+        },
+        instruction::VerifyAndConsumeNullifier {
+            root_hash: args.root_hash,
+            verification_type,
+            signal_hash: args.signal_hash,
+            nullifier_hash: args.nullifier_hash,
+            external_nullifier_hash: args.external_nullifier_hash,
+            proof: args.proof,
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use solana_world_id_program::{
+        self as world_id,
+        state::{
+            AdminSet, Config, GuardianSignatures, LatestRoot, PendingConfigChange,
+            RelayerAllowlist, RootSource, VerificationTypeConfig, VerifyingKey,
+        },
+    };
+
+    use super::*;
+
+    // The builders are validated by running the program's own account validation (`try_accounts`)
+    // over accounts created for each of their account metas.
+
+    const VERIFICATION_TYPE: [u8; 1] = [0];
+    const ROOT_HASH: [u8; 32] = [1; 32];
+    const LATEST_ROOT_HASH: [u8; 32] = [2; 32];
+    const KEY_ID: [u8; 1] = [1];
+    const ROOT_EXPIRY_CHANGE: ConfigChange = ConfigChange::RootExpiry {
+        verification_type: VERIFICATION_TYPE,
+        root_expiry: 60,
+    };
+
+    struct TestAccount {
+        key: Pubkey,
+        is_signer: bool,
+        is_writable: bool,
+        lamports: u64,
+        data: Vec<u8>,
+        owner: Pubkey,
+        executable: bool,
+    }
+
+    impl TestAccount {
+        fn info(&mut self) -> AccountInfo<'_> {
+            AccountInfo::new(
+                &self.key,
+                self.is_signer,
+                self.is_writable,
+                &mut self.lamports,
+                &mut self.data,
+                &self.owner,
+                self.executable,
+                0,
+            )
+        }
+    }
+
+    /// Creates an account for every account meta of `ix`, using `program_accounts` as the data of
+    /// accounts owned by the program.
+    fn test_accounts(ix: &Instruction, program_accounts: &[(Pubkey, Vec<u8>)]) -> Vec<TestAccount> {
+        ix.accounts
+            .iter()
+            .map(|meta| {
+                let program_account = program_accounts.iter().find(|(key, _)| *key == meta.pubkey);
+                let (owner, data, executable) = match program_account {
+                    _ if meta.pubkey == ID => (bpf_loader_upgradeable::ID, vec![], true),
+                    Some((_, data)) => (ID, data.clone(), false),
+                    None => (system_program::ID, vec![], false),
+                };
+                TestAccount {
+                    key: meta.pubkey,
+                    is_signer: meta.is_signer,
+                    is_writable: meta.is_writable,
+                    lamports: 1,
+                    data,
+                    owner,
+                    executable,
+                }
+            })
+            .collect()
+    }
+
+    fn serialize<T: AccountSerialize>(account: &T) -> Vec<u8> {
+        let mut data = vec![];
+        account.try_serialize(&mut data).unwrap();
+        data
+    }
+
+    fn bump(seeds: &[&[u8]]) -> u8 {
+        Pubkey::find_program_address(seeds, &ID).1
+    }
+
+    fn config_account(owner: Pubkey) -> Config {
+        Config {
+            bump: bump(&[Config::SEED_PREFIX]),
+            owner,
+            pending_owner: None,
+            verifying_key_activation_delay: 0,
+            paused: 0,
+            pauser: None,
+            config_change_delay: 0,
+            config_manager: None,
+            root_revoker: None,
+            relayer_allowlist_enabled: false,
+        }
+    }
+
+    fn config(owner: Pubkey) -> (Pubkey, Vec<u8>) {
+        (derive_config_key(), serialize(&config_account(owner)))
+    }
+
+    fn paused_config(owner: Pubkey, pauser: Option<Pubkey>, paused: u8) -> (Pubkey, Vec<u8>) {
+        let mut account = config_account(owner);
+        account.pauser = pauser;
+        account.paused = paused;
+        (derive_config_key(), serialize(&account))
+    }
+
+    fn role_config(owner: Pubkey, role: Role, grantee: Pubkey) -> (Pubkey, Vec<u8>) {
+        let mut account = config_account(owner);
+        *account.role_mut(role) = Some(grantee);
+        (derive_config_key(), serialize(&account))
+    }
+
+    fn latest_root() -> (Pubkey, Vec<u8>) {
+        let account = LatestRoot {
+            bump: bump(&[LatestRoot::SEED_PREFIX, &VERIFICATION_TYPE]),
+            read_block_number: 1,
+            read_block_hash: [0; 32],
+            read_block_time: 0,
+            root: LATEST_ROOT_HASH,
+            verification_type: VERIFICATION_TYPE,
+        };
+        (
+            derive_latest_root_key(VERIFICATION_TYPE),
+            serialize(&account),
+        )
+    }
+
+    fn verification_type_config(verifying_key_id: Option<[u8; 1]>) -> (Pubkey, Vec<u8>) {
+        let account = VerificationTypeConfig {
+            bump: bump(&[VerificationTypeConfig::SEED_PREFIX, &VERIFICATION_TYPE]),
+            verification_type: VERIFICATION_TYPE,
+            enabled: true,
+            root_expiry: 0,
+            allowed_update_staleness: 0,
+            source: RootSource::Query {
+                chain_id: 2,
+                contract: [0; 20],
+                selector: [0; 4],
+            },
+            verifying_key_id,
+            pending_verifying_key: None,
+            previous_verifying_key: None,
+        };
+        (
+            derive_verification_type_config_key(VERIFICATION_TYPE),
+            serialize(&account),
+        )
+    }
+
+    fn root(refund_recipient: Pubkey) -> (Pubkey, Vec<u8>) {
+        let account = Root {
+            bump: bump(&[Root::SEED_PREFIX, &ROOT_HASH, &VERIFICATION_TYPE]),
+            read_block_number: 0,
+            read_block_hash: [0; 32],
+            read_block_time: 0,
+            refund_recipient,
+            root: ROOT_HASH,
+            verification_type: VERIFICATION_TYPE,
+            revoked: false,
+        };
+        (
+            derive_root_key(ROOT_HASH, VERIFICATION_TYPE),
+            serialize(&account),
+        )
+    }
+
+    fn admin_set(members: Vec<Pubkey>) -> (Pubkey, Vec<u8>) {
+        let account = AdminSet {
+            bump: bump(&[AdminSet::SEED_PREFIX]),
+            threshold: 1,
+            members,
+        };
+        (derive_admin_set_key(), serialize(&account))
+    }
+
+    fn relayer_allowlist(relayers: Vec<Pubkey>) -> (Pubkey, Vec<u8>) {
+        let account = RelayerAllowlist {
+            bump: bump(&[RelayerAllowlist::SEED_PREFIX]),
+            relayers,
+        };
+        (derive_relayer_allowlist_key(), serialize(&account))
+    }
+
+    fn verifying_key(retired: bool) -> (Pubkey, Vec<u8>) {
+        let account = VerifyingKey {
+            bump: bump(&[VerifyingKey::SEED_PREFIX, &KEY_ID]),
+            key_id: KEY_ID,
+            retired,
+            alpha_g1: [0; 64],
+            beta_g2: [0; 128],
+            gamma_g2: [0; 128],
+            delta_g2: [0; 128],
+            ic: [[0; 64]; 5],
+        };
+        (derive_verifying_key_key(KEY_ID), serialize(&account))
+    }
+
+    fn pending_config_change(refund_recipient: Pubkey) -> (Pubkey, Vec<u8>) {
+        let account = PendingConfigChange {
+            bump: bump(&[PendingConfigChange::SEED_PREFIX, &ROOT_EXPIRY_CHANGE.seed()]),
+            change: ROOT_EXPIRY_CHANGE,
+            executable_at: 0,
+            refund_recipient,
+        };
+        (
+            derive_pending_config_change_key(&ROOT_EXPIRY_CHANGE),
+            serialize(&account),
+        )
+    }
+
+    fn proof_args() -> Groth16ProofArgs {
+        Groth16ProofArgs {
+            root_hash: ROOT_HASH,
+            signal_hash: [3; 32],
+            nullifier_hash: [4; 32],
+            external_nullifier_hash: [5; 32],
+            proof: [0; 256],
+        }
+    }
+
+    #[test]
+    fn test_verify_groth16_proof_accounts() {
+        let ix = verify_groth16_proof(VERIFICATION_TYPE, None, proof_args());
+        let mut accounts = test_accounts(
+            &ix,
+            &[
+                root(Pubkey::new_unique()),
+                latest_root(),
+                config(Pubkey::new_unique()),
+                verification_type_config(None),
+            ],
+        );
+        let infos: Vec<AccountInfo> = accounts.iter_mut().map(TestAccount::info).collect();
+        world_id::VerifyGroth16Proof::try_accounts(
+            &ID,
+            &mut infos.as_slice(),
+            &ix.data[8..],
+            &mut Default::default(),
+            &mut BTreeSet::new(),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_verify_groth16_proof_with_verifying_key_accounts() {
+        let ix = verify_groth16_proof(VERIFICATION_TYPE, Some(KEY_ID), proof_args());
+        let mut accounts = test_accounts(
+            &ix,
+            &[
+                root(Pubkey::new_unique()),
+                latest_root(),
+                config(Pubkey::new_unique()),
+                verification_type_config(Some(KEY_ID)),
+                verifying_key(false),
+            ],
+        );
+        let infos: Vec<AccountInfo> = accounts.iter_mut().map(TestAccount::info).collect();
+        world_id::VerifyGroth16Proof::try_accounts(
+            &ID,
+            &mut infos.as_slice(),
+            &ix.data[8..],
+            &mut Default::default(),
+            &mut BTreeSet::new(),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_verify_groth16_proof_with_raw_inputs_accounts() {
+        let ix = verify_groth16_proof_with_raw_inputs(
+            VERIFICATION_TYPE,
+            None,
+            ROOT_HASH,
+            "app_staging_0".to_string(),
+            "action".to_string(),
+            vec![6; 20],
+            [4; 32],
+            [0; 256],
+        );
+        let mut accounts = test_accounts(
+            &ix,
+            &[
+                root(Pubkey::new_unique()),
+                latest_root(),
+                config(Pubkey::new_unique()),
+                verification_type_config(None),
+            ],
+        );
+        let infos: Vec<AccountInfo> = accounts.iter_mut().map(TestAccount::info).collect();
+        world_id::VerifyGroth16ProofWithRawInputs::try_accounts(
+            &ID,
+            &mut infos.as_slice(),
+            &ix.data[8..],
+            &mut Default::default(),
+            &mut BTreeSet::new(),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_verify_groth16_proofs_accounts() {
+        let ix = verify_groth16_proofs(
+            VERIFICATION_TYPE,
+            None,
+            vec![proof_args(), proof_args()],
+            BatchVerificationMode::Atomic,
+        );
+        let mut accounts = test_accounts(
+            &ix,
+            &[
+                latest_root(),
+                config(Pubkey::new_unique()),
+                verification_type_config(None),
+            ],
+        );
+        let infos: Vec<AccountInfo> = accounts.iter_mut().map(TestAccount::info).collect();
+        let mut remaining_accounts = infos.as_slice();
+        world_id::VerifyGroth16Proofs::try_accounts(
+            &ID,
+            &mut remaining_accounts,
+            &ix.data[8..],
+            &mut Default::default(),
+            &mut BTreeSet::new(),
+        )
+        .unwrap();
+        // Each distinct root is passed once.
+        let root_keys: Vec<Pubkey> = remaining_accounts.iter().map(|info| info.key()).collect();
+        assert_eq!(
+            root_keys,
+            vec![derive_root_key(ROOT_HASH, VERIFICATION_TYPE)]
+        );
+    }
+
+    #[test]
+    fn test_clean_up_root_accounts() {
+        let refund_recipient = Pubkey::new_unique();
+        let ix = clean_up_root(ROOT_HASH, VERIFICATION_TYPE, refund_recipient);
+        let mut accounts = test_accounts(
+            &ix,
+            &[
+                root(refund_recipient),
+                latest_root(),
+                config(Pubkey::new_unique()),
+                verification_type_config(None),
+            ],
+        );
+        let infos: Vec<AccountInfo> = accounts.iter_mut().map(TestAccount::info).collect();
+        world_id::CleanUpRoot::try_accounts(
+            &ID,
+            &mut infos.as_slice(),
+            &ix.data[8..],
+            &mut Default::default(),
+            &mut BTreeSet::new(),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_close_signatures_accounts() {
+        let guardian_signatures = Pubkey::new_unique();
+        let refund_recipient = Pubkey::new_unique();
+        let ix = close_signatures(guardian_signatures, refund_recipient);
+        let account = GuardianSignatures {
+            refund_recipient,
+            guardian_signatures: vec![[0; 66]],
+        };
+        let mut accounts = test_accounts(&ix, &[(guardian_signatures, serialize(&account))]);
+        let infos: Vec<AccountInfo> = accounts.iter_mut().map(TestAccount::info).collect();
+        world_id::CloseSignatures::try_accounts(
+            &ID,
+            &mut infos.as_slice(),
+            &ix.data[8..],
+            &mut Default::default(),
+            &mut BTreeSet::new(),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_execute_config_change_accounts() {
+        let owner = Pubkey::new_unique();
+        let refund_recipient = Pubkey::new_unique();
+        let ix = execute_config_change(owner, ROOT_EXPIRY_CHANGE, refund_recipient);
+        let mut accounts = test_accounts(
+            &ix,
+            &[
+                config(owner),
+                pending_config_change(refund_recipient),
+                verification_type_config(None),
+            ],
+        );
+        let infos: Vec<AccountInfo> = accounts.iter_mut().map(TestAccount::info).collect();
+        world_id::ExecuteConfigChange::try_accounts(
+            &ID,
+            &mut infos.as_slice(),
+            &ix.data[8..],
+            &mut Default::default(),
+            &mut BTreeSet::new(),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_execute_config_change_rejects_other_refund_recipient() {
+        let owner = Pubkey::new_unique();
+        let ix = execute_config_change(owner, ROOT_EXPIRY_CHANGE, Pubkey::new_unique());
+        let mut accounts = test_accounts(
+            &ix,
+            &[
+                config(owner),
+                pending_config_change(Pubkey::new_unique()),
+                verification_type_config(None),
+            ],
+        );
+        let infos: Vec<AccountInfo> = accounts.iter_mut().map(TestAccount::info).collect();
+        assert!(world_id::ExecuteConfigChange::try_accounts(
+            &ID,
+            &mut infos.as_slice(),
+            &ix.data[8..],
+            &mut Default::default(),
+            &mut BTreeSet::new(),
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_retire_verifying_key_accounts() {
+        let owner = Pubkey::new_unique();
+        let ix = retire_verifying_key(owner, KEY_ID);
+        let mut accounts = test_accounts(&ix, &[config(owner), verifying_key(false)]);
+        let infos: Vec<AccountInfo> = accounts.iter_mut().map(TestAccount::info).collect();
+        world_id::RetireVerifyingKey::try_accounts(
+            &ID,
+            &mut infos.as_slice(),
+            &ix.data[8..],
+            &mut Default::default(),
+            &mut BTreeSet::new(),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_cancel_config_change_accounts() {
+        let owner = Pubkey::new_unique();
+        let config_manager = Pubkey::new_unique();
+        let refund_recipient = Pubkey::new_unique();
+        for authority in [owner, config_manager] {
+            let ix = cancel_config_change(authority, ROOT_EXPIRY_CHANGE, refund_recipient);
+            let mut accounts = test_accounts(
+                &ix,
+                &[
+                    role_config(owner, Role::ConfigManager, config_manager),
+                    pending_config_change(refund_recipient),
+                ],
+            );
+            let infos: Vec<AccountInfo> = accounts.iter_mut().map(TestAccount::info).collect();
+            world_id::CancelConfigChange::try_accounts(
+                &ID,
+                &mut infos.as_slice(),
+                &ix.data[8..],
+                &mut Default::default(),
+                &mut BTreeSet::new(),
+            )
+            .unwrap();
+        }
+    }
+
+    #[test]
+    fn test_propose_verifying_key_accounts() {
+        let owner = Pubkey::new_unique();
+        for verifying_key_id in [Some(KEY_ID), None] {
+            let ix = propose_verifying_key(owner, VERIFICATION_TYPE, verifying_key_id, 60);
+            let mut accounts = test_accounts(
+                &ix,
+                &[
+                    config(owner),
+                    verification_type_config(None),
+                    verifying_key(false),
+                ],
+            );
+            let infos: Vec<AccountInfo> = accounts.iter_mut().map(TestAccount::info).collect();
+            let accounts = world_id::ProposeVerifyingKey::try_accounts(
+                &ID,
+                &mut infos.as_slice(),
+                &ix.data[8..],
+                &mut Default::default(),
+                &mut BTreeSet::new(),
+            )
+            .unwrap();
+            assert_eq!(
+                accounts.verifying_key.map(|key| key.key_id),
+                verifying_key_id
+            );
+        }
+    }
+
+    #[test]
+    fn test_propose_verifying_key_rejects_retired_key() {
+        let owner = Pubkey::new_unique();
+        let ix = propose_verifying_key(owner, VERIFICATION_TYPE, Some(KEY_ID), 60);
+        let mut accounts = test_accounts(
+            &ix,
+            &[
+                config(owner),
+                verification_type_config(None),
+                verifying_key(true),
+            ],
+        );
+        let infos: Vec<AccountInfo> = accounts.iter_mut().map(TestAccount::info).collect();
+        assert!(world_id::ProposeVerifyingKey::try_accounts(
+            &ID,
+            &mut infos.as_slice(),
+            &ix.data[8..],
+            &mut Default::default(),
+            &mut BTreeSet::new(),
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_activate_verifying_key_accounts() {
+        let owner = Pubkey::new_unique();
+        let ix = activate_verifying_key(owner, VERIFICATION_TYPE, Some(KEY_ID));
+        let mut accounts = test_accounts(
+            &ix,
+            &[
+                config(owner),
+                verification_type_config(None),
+                verifying_key(false),
+            ],
+        );
+        let infos: Vec<AccountInfo> = accounts.iter_mut().map(TestAccount::info).collect();
+        world_id::ActivateVerifyingKey::try_accounts(
+            &ID,
+            &mut infos.as_slice(),
+            &ix.data[8..],
+            &mut Default::default(),
+            &mut BTreeSet::new(),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_verify_groth16_proof_rejects_paused_verification() {
+        let ix = verify_groth16_proof(VERIFICATION_TYPE, None, proof_args());
+        let mut accounts = test_accounts(
+            &ix,
+            &[
+                root(Pubkey::new_unique()),
+                latest_root(),
+                paused_config(Pubkey::new_unique(), None, Config::PAUSE_PROOF_VERIFICATION),
+                verification_type_config(None),
+            ],
+        );
+        let infos: Vec<AccountInfo> = accounts.iter_mut().map(TestAccount::info).collect();
+        assert!(world_id::VerifyGroth16Proof::try_accounts(
+            &ID,
+            &mut infos.as_slice(),
+            &ix.data[8..],
+            &mut Default::default(),
+            &mut BTreeSet::new(),
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_pause_accounts() {
+        let owner = Pubkey::new_unique();
+        let pauser = Pubkey::new_unique();
+        for authority in [owner, pauser] {
+            let ix = pause(authority, Config::PAUSE_ALL);
+            let mut accounts = test_accounts(&ix, &[paused_config(owner, Some(pauser), 0)]);
+            let infos: Vec<AccountInfo> = accounts.iter_mut().map(TestAccount::info).collect();
+            world_id::Pause::try_accounts(
+                &ID,
+                &mut infos.as_slice(),
+                &ix.data[8..],
+                &mut Default::default(),
+                &mut BTreeSet::new(),
+            )
+            .unwrap();
+        }
+    }
+
+    #[test]
+    fn test_pause_rejects_other_signer() {
+        let owner = Pubkey::new_unique();
+        let ix = pause(Pubkey::new_unique(), Config::PAUSE_ALL);
+        let mut accounts = test_accounts(&ix, &[paused_config(owner, None, 0)]);
+        let infos: Vec<AccountInfo> = accounts.iter_mut().map(TestAccount::info).collect();
+        assert!(world_id::Pause::try_accounts(
+            &ID,
+            &mut infos.as_slice(),
+            &ix.data[8..],
+            &mut Default::default(),
+            &mut BTreeSet::new(),
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_revoke_root_accounts() {
+        let owner = Pubkey::new_unique();
+        let root_revoker = Pubkey::new_unique();
+        for authority in [owner, root_revoker] {
+            let ix = revoke_root(authority, ROOT_HASH, VERIFICATION_TYPE, None);
+            let mut accounts = test_accounts(
+                &ix,
+                &[
+                    role_config(owner, Role::RootRevoker, root_revoker),
+                    root(Pubkey::new_unique()),
+                    latest_root(),
+                    verification_type_config(None),
+                ],
+            );
+            let infos: Vec<AccountInfo> = accounts.iter_mut().map(TestAccount::info).collect();
+            world_id::RevokeRoot::try_accounts(
+                &ID,
+                &mut infos.as_slice(),
+                &ix.data[8..],
+                &mut Default::default(),
+                &mut BTreeSet::new(),
+            )
+            .unwrap();
+        }
+    }
+
+    #[test]
+    fn test_revoke_root_rejects_other_role() {
+        let owner = Pubkey::new_unique();
+        let config_manager = Pubkey::new_unique();
+        let ix = revoke_root(config_manager, ROOT_HASH, VERIFICATION_TYPE, None);
+        let mut accounts = test_accounts(
+            &ix,
+            &[
+                role_config(owner, Role::ConfigManager, config_manager),
+                root(Pubkey::new_unique()),
+                latest_root(),
+                verification_type_config(None),
+            ],
+        );
+        let infos: Vec<AccountInfo> = accounts.iter_mut().map(TestAccount::info).collect();
+        assert!(world_id::RevokeRoot::try_accounts(
+            &ID,
+            &mut infos.as_slice(),
+            &ix.data[8..],
+            &mut Default::default(),
+            &mut BTreeSet::new(),
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_add_admin_set_member_accounts() {
+        let ix = add_admin_set_member(Pubkey::new_unique());
+        let mut accounts = test_accounts(&ix, &[admin_set(vec![Pubkey::new_unique()])]);
+        let infos: Vec<AccountInfo> = accounts.iter_mut().map(TestAccount::info).collect();
+        world_id::UpdateAdminSet::try_accounts(
+            &ID,
+            &mut infos.as_slice(),
+            &ix.data[8..],
+            &mut Default::default(),
+            &mut BTreeSet::new(),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_execute_as_admin_set_accounts() {
+        let member = Pubkey::new_unique();
+        let ix = execute_as_admin_set(revoke_role(derive_admin_set_key(), Role::Pauser), &[member]);
+        // Only the members sign the transaction, the program signs for the admin set.
+        let signers: Vec<Pubkey> = ix
+            .accounts
+            .iter()
+            .filter(|meta| meta.is_signer)
+            .map(|meta| meta.pubkey)
+            .collect();
+        assert_eq!(signers, vec![member]);
+        let mut accounts = test_accounts(&ix, &[admin_set(vec![member])]);
+        let infos: Vec<AccountInfo> = accounts.iter_mut().map(TestAccount::info).collect();
+        world_id::ExecuteAsAdminSet::try_accounts(
+            &ID,
+            &mut infos.as_slice(),
+            &ix.data[8..],
+            &mut Default::default(),
+            &mut BTreeSet::new(),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_add_relayer_accounts() {
+        let owner = Pubkey::new_unique();
+        let ix = add_relayer(owner, Pubkey::new_unique());
+        let mut accounts = test_accounts(&ix, &[config(owner), relayer_allowlist(vec![])]);
+        let infos: Vec<AccountInfo> = accounts.iter_mut().map(TestAccount::info).collect();
+        world_id::UpdateRelayerAllowlist::try_accounts(
+            &ID,
+            &mut infos.as_slice(),
+            &ix.data[8..],
+            &mut Default::default(),
+            &mut BTreeSet::new(),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_set_relayer_allowlist_enabled_accounts() {
+        let owner = Pubkey::new_unique();
+        let ix = set_relayer_allowlist_enabled(owner, true);
+        let mut accounts = test_accounts(
+            &ix,
+            &[config(owner), relayer_allowlist(vec![Pubkey::new_unique()])],
+        );
+        let infos: Vec<AccountInfo> = accounts.iter_mut().map(TestAccount::info).collect();
+        world_id::SetRelayerAllowlistEnabled::try_accounts(
+            &ID,
+            &mut infos.as_slice(),
+            &ix.data[8..],
+            &mut Default::default(),
+            &mut BTreeSet::new(),
+        )
+        .unwrap();
+    }
+}
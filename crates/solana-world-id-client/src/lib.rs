@@ -0,0 +1,25 @@
+//! Client helpers for the Solana World ID Program.
+//!
+//! [`pda`] derives every address used by the program and [`instructions`] builds an
+//! [`Instruction`](anchor_lang::solana_program::instruction::Instruction) for every instruction in
+//! the program. The builders are assembled from the program's own generated `accounts` and
+//! `instruction` types, so a change to an instruction's accounts or arguments fails to compile here.
+//! [`query`] serializes the Wormhole Query requests accepted by update_root_with_query and
+//! [`hashing`] computes signal and external nullifier hashes as World ID does.
+//! [`proof`] converts World ID proofs into the layout of the verify instructions and [`verifier`]
+//! pre-checks them off-chain with the program's own checks.
+
+pub mod instructions;
+
+pub mod pda;
+
+pub mod proof;
+
+pub mod query;
+
+pub mod verifier;
+
+pub use solana_world_id_program::{
+    self as program, error, event, hashing, state, BatchVerificationMode, Groth16ProofArgs,
+    InitializeArgs, RegisterVerificationTypeArgs, RegisterVerifyingKeyArgs, VerificationResult, ID,
+};
@@ -0,0 +1,95 @@
+//! Program derived addresses used by the Solana World ID Program.
+
+use anchor_lang::{prelude::Pubkey, solana_program::bpf_loader_upgradeable};
+use solana_world_id_program::{
+    state::{
+        AdminSet, Config, ConfigChange, LatestRoot, Nullifier, PendingConfigChange,
+        RelayerAllowlist, Root, VerificationTypeConfig, VerifyingKey, WormholeGuardianSet,
+    },
+    ID,
+};
+use wormhole_solana_consts::CORE_BRIDGE_PROGRAM_ID;
+
+/// Seed of the PDA which holds the upgrade authority during an ownership transfer.
+pub const UPGRADE_LOCK_SEED: &[u8] = b"upgrade_lock";
+
+/// Seed of the PDA which signs the self-CPIs of `emit_cpi!`.
+pub const EVENT_AUTHORITY_SEED: &[u8] = b"__event_authority";
+
+pub fn derive_config_key() -> Pubkey {
+    Pubkey::find_program_address(&[Config::SEED_PREFIX], &ID).0
+}
+
+pub fn derive_latest_root_key(verification_type: [u8; 1]) -> Pubkey {
+    Pubkey::find_program_address(&[LatestRoot::SEED_PREFIX, &verification_type], &ID).0
+}
+
+pub fn derive_root_key(root_hash: [u8; 32], verification_type: [u8; 1]) -> Pubkey {
+    Pubkey::find_program_address(&[Root::SEED_PREFIX, &root_hash, &verification_type], &ID).0
+}
+
+pub fn derive_verification_type_config_key(verification_type: [u8; 1]) -> Pubkey {
+    Pubkey::find_program_address(
+        &[VerificationTypeConfig::SEED_PREFIX, &verification_type],
+        &ID,
+    )
+    .0
+}
+
+pub fn derive_nullifier_key(external_nullifier_hash: [u8; 32], nullifier_hash: [u8; 32]) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            Nullifier::SEED_PREFIX,
+            &external_nullifier_hash,
+            &nullifier_hash,
+        ],
+        &ID,
+    )
+    .0
+}
+
+pub fn derive_verifying_key_key(key_id: [u8; 1]) -> Pubkey {
+    Pubkey::find_program_address(&[VerifyingKey::SEED_PREFIX, &key_id], &ID).0
+}
+
+pub fn derive_pending_config_change_key(change: &ConfigChange) -> Pubkey {
+    Pubkey::find_program_address(&[PendingConfigChange::SEED_PREFIX, &change.seed()], &ID).0
+}
+
+pub fn derive_admin_set_key() -> Pubkey {
+    Pubkey::find_program_address(&[AdminSet::SEED_PREFIX], &ID).0
+}
+
+pub fn derive_relayer_allowlist_key() -> Pubkey {
+    Pubkey::find_program_address(&[RelayerAllowlist::SEED_PREFIX], &ID).0
+}
+
+pub fn derive_upgrade_lock_key() -> Pubkey {
+    Pubkey::find_program_address(&[UPGRADE_LOCK_SEED], &ID).0
+}
+
+pub fn derive_event_authority_key() -> Pubkey {
+    Pubkey::find_program_address(&[EVENT_AUTHORITY_SEED], &ID).0
+}
+
+/// ProgramData account of this program, owned by the upgradeable BPF loader.
+pub fn derive_program_data_key() -> Pubkey {
+    Pubkey::find_program_address(&[ID.as_ref()], &bpf_loader_upgradeable::id()).0
+}
+
+/// Config account of the Core Bridge, which holds the current guardian set index.
+pub fn derive_core_bridge_config_key() -> Pubkey {
+    Pubkey::find_program_address(&[b"Bridge"], &CORE_BRIDGE_PROGRAM_ID).0
+}
+
+/// GuardianSet account of the Core Bridge.
+pub fn derive_guardian_set_key(guardian_set_index: u32) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            WormholeGuardianSet::SEED_PREFIX,
+            &guardian_set_index.to_be_bytes(),
+        ],
+        &CORE_BRIDGE_PROGRAM_ID,
+    )
+    .0
+}
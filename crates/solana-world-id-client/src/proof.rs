@@ -0,0 +1,280 @@
+//! Conversion of World ID proofs into the `proof: [u8; 256]` argument of the verify instructions.
+//!
+//! The argument is proof A (G1), B (G2) and C (G1) as 32-byte big-endian field elements, with each
+//! G2 coordinate ordered imaginary part first as in EIP-197. This is exactly the ABI encoding of the
+//! `uint256[8]` proof returned by IDKit and the World ID sign-in flow, which is only checked here.
+//!
+//! Unlike the usual groth16-solana setup, proof A is not negated:
+//! [`VERIFYING_KEY`](crate::program::VERIFYING_KEY) is taken from World ID's gnark-generated
+//! SemaphoreVerifier, whose beta, gamma and delta are already negated. snarkjs proofs are converted
+//! the same way as by `snarkjs zkey export soliditycalldata`.
+
+use std::fmt;
+
+use serde::Deserialize;
+
+/// Length of the proof argument of the verify instructions.
+pub const PROOF_LEN: usize = 256;
+
+/// Modulus of the BN254 base field, which every coordinate must be less than.
+pub const BASE_FIELD_MODULUS: [u8; 32] = [
+    0x30, 0x64, 0x4e, 0x72, 0xe1, 0x31, 0xa0, 0x29, 0xb8, 0x50, 0x45, 0xb6, 0x81, 0x81, 0x58, 0x5d,
+    0x97, 0x81, 0x6a, 0x91, 0x68, 0x71, 0xca, 0x8d, 0x3c, 0x20, 0x8c, 0x16, 0xd8, 0x7c, 0xfd, 0x47,
+];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ProofFormatError {
+    /// The ABI-encoded proof is not 256 bytes long.
+    InvalidLength(usize),
+    InvalidHex,
+    InvalidJson(String),
+    /// A coordinate is not a decimal number less than [`BASE_FIELD_MODULUS`].
+    InvalidCoordinate(String),
+    /// A snarkjs point is not normalized to affine coordinates.
+    NotAffine,
+}
+
+impl fmt::Display for ProofFormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidLength(len) => {
+                write!(f, "invalid proof length {len}, expected {PROOF_LEN}")
+            }
+            Self::InvalidHex => write!(f, "invalid hex proof"),
+            Self::InvalidJson(reason) => write!(f, "invalid snarkjs proof: {reason}"),
+            Self::InvalidCoordinate(coordinate) => write!(f, "invalid coordinate {coordinate}"),
+            Self::NotAffine => write!(f, "snarkjs proof is not in affine coordinates"),
+        }
+    }
+}
+
+impl std::error::Error for ProofFormatError {}
+
+/// Converts an ABI-encoded `uint256[8]` proof, checking that every coordinate is a field element.
+pub fn from_abi(bytes: &[u8]) -> Result<[u8; PROOF_LEN], ProofFormatError> {
+    let proof: [u8; PROOF_LEN] = bytes
+        .try_into()
+        .map_err(|_| ProofFormatError::InvalidLength(bytes.len()))?;
+    for word in proof.chunks_exact(32) {
+        if word >= BASE_FIELD_MODULUS.as_slice() {
+            return Err(ProofFormatError::InvalidCoordinate(format!(
+                "0x{}",
+                hex::encode(word)
+            )));
+        }
+    }
+    Ok(proof)
+}
+
+/// Converts a hex ABI-encoded proof, with or without `0x`, such as the `proof` of IDKit's
+/// `ISuccessResult`.
+pub fn from_abi_hex(proof: &str) -> Result<[u8; PROOF_LEN], ProofFormatError> {
+    let proof = proof.strip_prefix("0x").unwrap_or(proof);
+    from_abi(&hex::decode(proof).map_err(|_| ProofFormatError::InvalidHex)?)
+}
+
+/// A proof as written to `proof.json` by `snarkjs groth16 prove`, with decimal coordinates in
+/// projective form. Other fields, such as `protocol` and `curve`, are ignored.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+pub struct SnarkjsProof {
+    pub pi_a: [String; 3],
+    /// Coordinates are ordered real part first.
+    pub pi_b: [[String; 2]; 3],
+    pub pi_c: [String; 3],
+}
+
+impl SnarkjsProof {
+    /// Converts to the argument of the verify instructions.
+    pub fn to_proof(&self) -> Result<[u8; PROOF_LEN], ProofFormatError> {
+        if self.pi_a[2] != "1" || self.pi_b[2] != ["1", "0"] || self.pi_c[2] != "1" {
+            return Err(ProofFormatError::NotAffine);
+        }
+        let coordinates = [
+            &self.pi_a[0],
+            &self.pi_a[1],
+            &self.pi_b[0][1],
+            &self.pi_b[0][0],
+            &self.pi_b[1][1],
+            &self.pi_b[1][0],
+            &self.pi_c[0],
+            &self.pi_c[1],
+        ];
+        let mut proof = [0; PROOF_LEN];
+        for (word, coordinate) in proof.chunks_exact_mut(32).zip(coordinates) {
+            word.copy_from_slice(&parse_coordinate(coordinate)?);
+        }
+        Ok(proof)
+    }
+}
+
+/// Converts the contents of a snarkjs `proof.json`.
+pub fn from_snarkjs_json(json: &str) -> Result<[u8; PROOF_LEN], ProofFormatError> {
+    serde_json::from_str::<SnarkjsProof>(json)
+        .map_err(|e| ProofFormatError::InvalidJson(e.to_string()))?
+        .to_proof()
+}
+
+/// Parses a decimal field element to 32 big-endian bytes.
+fn parse_coordinate(coordinate: &str) -> Result<[u8; 32], ProofFormatError> {
+    let invalid = || ProofFormatError::InvalidCoordinate(coordinate.to_string());
+    if coordinate.is_empty() {
+        return Err(invalid());
+    }
+    let mut value = [0u8; 32];
+    for c in coordinate.chars() {
+        let mut carry = c.to_digit(10).ok_or_else(invalid)?;
+        for byte in value.iter_mut().rev() {
+            let product = u32::from(*byte) * 10 + carry;
+            *byte = product as u8;
+            carry = product >> 8;
+        }
+        if carry != 0 {
+            return Err(invalid());
+        }
+    }
+    if value >= BASE_FIELD_MODULUS {
+        return Err(invalid());
+    }
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use solana_world_id_program::{
+        hashing::{external_nullifier_hash, signal_hash},
+        verify_groth16,
+    };
+
+    use super::*;
+
+    // The example ISuccessResult of IDKit used by the TypeScript tests.
+    const PROOF: &str = "0x1eef0990c59b6985413ba1589afb6a6b673a4723ea3215923850b89c61aceeee2748da443e3fdf161456b05cb82bc2e6f1ace9e67cdaf76732ff9bf31e6b82b724d7c33a4998f4cd2d1c9f2c90a249910be0aa23b882c9756371769d1af1923d0509d6791ff17c3604425a4a113b5103c014c8f724edc649f1417e56b6cdb4422896c0d5492652ebb1e001016431ae457b58361b5b6cbea25565236362f06fae098cf3493c1c299313672ebd85fde41607261491e3cc57dea51ee7951b47020b21b73fceef2b8fb59c6f5f0302dffa36f9ec01319a257f20052b83f2d7a3232c22f5d0600fb036a5b8d2a3d6d92a3c043b665510d97bf9020510b5f9c692a9a5";
+    const MERKLE_ROOT: &str = "05628ccef5b585f9a5afb764d22835f2c71b10beb4b212e45ec9e4d0354c9764";
+    const NULLIFIER_HASH: &str = "2aa975196dc1f4f9f57b8195bea9c61331e0012ec25484ed569782c49145721a";
+    const SIGNAL: &str = "f39fd6e51aad88f6f4ce6ab8827279cfffb92266";
+    const APP_ID: &str = "app_staging_7d23b838b02776cebd87b86ac3248641";
+    const ACTION: &str = "testing";
+
+    fn verify(proof: &[u8; PROOF_LEN]) -> bool {
+        verify_groth16(
+            None,
+            hex::decode(MERKLE_ROOT).unwrap().try_into().unwrap(),
+            signal_hash(&hex::decode(SIGNAL).unwrap()),
+            hex::decode(NULLIFIER_HASH).unwrap().try_into().unwrap(),
+            external_nullifier_hash(APP_ID, ACTION),
+            proof,
+        )
+        .is_ok()
+    }
+
+    /// Formats 32 big-endian bytes as a decimal number, as snarkjs does.
+    fn to_decimal(word: &[u8]) -> String {
+        let mut value = word.to_vec();
+        let mut digits = Vec::new();
+        while value.iter().any(|&byte| byte != 0) {
+            let mut remainder = 0;
+            for byte in value.iter_mut() {
+                let dividend = (remainder << 8) | u32::from(*byte);
+                *byte = (dividend / 10) as u8;
+                remainder = dividend % 10;
+            }
+            digits.push(char::from_digit(remainder, 10).unwrap());
+        }
+        if digits.is_empty() {
+            digits.push('0');
+        }
+        digits.iter().rev().collect()
+    }
+
+    /// The snarkjs proof of an ABI-encoded one.
+    fn to_snarkjs(proof: &[u8; PROOF_LEN]) -> SnarkjsProof {
+        let words: Vec<String> = proof.chunks_exact(32).map(to_decimal).collect();
+        SnarkjsProof {
+            pi_a: [words[0].clone(), words[1].clone(), "1".to_string()],
+            pi_b: [
+                [words[3].clone(), words[2].clone()],
+                [words[5].clone(), words[4].clone()],
+                ["1".to_string(), "0".to_string()],
+            ],
+            pi_c: [words[6].clone(), words[7].clone(), "1".to_string()],
+        }
+    }
+
+    /// Negates a G1 point: (x, y) -> (x, p - y).
+    fn negate_g1(point: &mut [u8]) {
+        let mut borrow = 0;
+        for i in (32..64).rev() {
+            let difference = i16::from(BASE_FIELD_MODULUS[i - 32]) - i16::from(point[i]) - borrow;
+            point[i] = difference.rem_euclid(256) as u8;
+            borrow = i16::from(difference < 0);
+        }
+    }
+
+    #[test]
+    fn abi_proof_verifies_as_is() {
+        let proof = from_abi_hex(PROOF).unwrap();
+        assert_eq!(proof.to_vec(), hex::decode(&PROOF[2..]).unwrap());
+        assert!(verify(&proof));
+    }
+
+    #[test]
+    fn negated_proof_a_does_not_verify() {
+        let mut proof = from_abi_hex(PROOF).unwrap();
+        negate_g1(&mut proof[..64]);
+        assert!(!verify(&proof));
+    }
+
+    #[test]
+    fn snarkjs_proof_round_trips() {
+        let proof = from_abi_hex(PROOF).unwrap();
+        let snarkjs = to_snarkjs(&proof);
+        let json = format!(
+            r#"{{"pi_a":{:?},"pi_b":{:?},"pi_c":{:?},"protocol":"groth16","curve":"bn128"}}"#,
+            snarkjs.pi_a, snarkjs.pi_b, snarkjs.pi_c
+        );
+        let converted = from_snarkjs_json(&json).unwrap();
+        assert_eq!(converted, proof);
+        assert!(verify(&converted));
+    }
+
+    #[test]
+    fn parses_coordinates() {
+        assert_eq!(parse_coordinate("0").unwrap(), [0; 32]);
+        let mut expected = [0; 32];
+        expected[30..].copy_from_slice(&1000u16.to_be_bytes());
+        assert_eq!(parse_coordinate("1000").unwrap(), expected);
+        let modulus = to_decimal(&BASE_FIELD_MODULUS);
+        assert_eq!(
+            parse_coordinate(&modulus),
+            Err(ProofFormatError::InvalidCoordinate(modulus))
+        );
+        for invalid in ["", "-1", "0x1", "1".repeat(80).as_str()] {
+            assert_eq!(
+                parse_coordinate(invalid),
+                Err(ProofFormatError::InvalidCoordinate(invalid.to_string()))
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_abi_proofs() {
+        assert_eq!(
+            from_abi(&[0; 255]),
+            Err(ProofFormatError::InvalidLength(255))
+        );
+        assert_eq!(from_abi_hex("0xzz"), Err(ProofFormatError::InvalidHex));
+        let mut proof = [0; PROOF_LEN];
+        proof[32..64].copy_from_slice(&BASE_FIELD_MODULUS);
+        assert!(matches!(
+            from_abi(&proof),
+            Err(ProofFormatError::InvalidCoordinate(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_projective_snarkjs_proofs() {
+        let mut snarkjs = to_snarkjs(&from_abi_hex(PROOF).unwrap());
+        snarkjs.pi_c[2] = "2".to_string();
+        assert_eq!(snarkjs.to_proof(), Err(ProofFormatError::NotAffine));
+    }
+}
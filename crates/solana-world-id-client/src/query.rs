@@ -0,0 +1,110 @@
+//! Wormhole Query requests for roots accepted by update_root_with_query.
+//!
+//! See https://github.com/wormhole-foundation/wormhole/blob/main/whitepapers/0013_ccq.md for the
+//! serialization. All integers are big-endian.
+
+use solana_world_id_program::state::RootSource;
+
+pub const QUERY_REQUEST_VERSION: u8 = 1;
+pub const ETH_CALL_QUERY_TYPE: u8 = 1;
+
+/// A single `eth_call` of an EthCall query.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EthCallData {
+    pub to: [u8; 20],
+    pub data: Vec<u8>,
+}
+
+/// Serializes an off-chain QueryRequest with a single EthCall query.
+pub fn serialize_eth_call_query_request(
+    nonce: u32,
+    chain_id: u16,
+    block_tag: &str,
+    call_data: &[EthCallData],
+) -> Vec<u8> {
+    let mut query = Vec::new();
+    query.extend_from_slice(&(block_tag.len() as u32).to_be_bytes());
+    query.extend_from_slice(block_tag.as_bytes());
+    query.push(call_data.len() as u8);
+    for call in call_data {
+        query.extend_from_slice(&call.to);
+        query.extend_from_slice(&(call.data.len() as u32).to_be_bytes());
+        query.extend_from_slice(&call.data);
+    }
+
+    let mut request = Vec::new();
+    request.push(QUERY_REQUEST_VERSION);
+    request.extend_from_slice(&nonce.to_be_bytes());
+    // Number of per chain queries.
+    request.push(1);
+    request.extend_from_slice(&chain_id.to_be_bytes());
+    request.push(ETH_CALL_QUERY_TYPE);
+    request.extend_from_slice(&(query.len() as u32).to_be_bytes());
+    request.extend_from_slice(&query);
+    request
+}
+
+/// Block tag of an EthCall query for a specific block, e.g. `0x5f4b2a`.
+pub fn block_tag(block_number: u64) -> String {
+    format!("0x{block_number:x}")
+}
+
+/// Serializes the QueryRequest for the latest root of a Query source at `block_number`.
+/// Returns `None` for other sources.
+pub fn latest_root_query_request(
+    nonce: u32,
+    source: &RootSource,
+    block_number: u64,
+) -> Option<Vec<u8>> {
+    match source {
+        RootSource::Query {
+            chain_id,
+            contract,
+            selector,
+        } => Some(serialize_eth_call_query_request(
+            nonce,
+            *chain_id,
+            &block_tag(block_number),
+            &[EthCallData {
+                to: *contract,
+                data: selector.to_vec(),
+            }],
+        )),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_latest_root_query_request() {
+        let source = RootSource::Query {
+            chain_id: 2,
+            contract: [0xaa; 20],
+            selector: [0xd7, 0xb0, 0xfe, 0xf1],
+        };
+        let request = latest_root_query_request(42, &source, 0x5f4b2a).unwrap();
+
+        let mut expected = vec![1, 0, 0, 0, 42, 1, 0, 2, 1];
+        // block tag (12) + number of calls (1) + to (20) + data length (4) + data (4)
+        expected.extend_from_slice(&41u32.to_be_bytes());
+        expected.extend_from_slice(&8u32.to_be_bytes());
+        expected.extend_from_slice(b"0x5f4b2a");
+        expected.push(1);
+        expected.extend_from_slice(&[0xaa; 20]);
+        expected.extend_from_slice(&4u32.to_be_bytes());
+        expected.extend_from_slice(&[0xd7, 0xb0, 0xfe, 0xf1]);
+        assert_eq!(request, expected);
+    }
+
+    #[test]
+    fn test_latest_root_query_request_rejects_vaa_source() {
+        let source = RootSource::Vaa {
+            emitter_chain: 2,
+            emitter_address: [0; 32],
+        };
+        assert!(latest_root_query_request(42, &source, 1).is_none());
+    }
+}
@@ -0,0 +1,247 @@
+//! Off-chain equivalent of verify_groth16_proof, to pre-check a proof before paying for a
+//! transaction.
+//!
+//! Both functions run the program's own [`check_root`] and [`verify_groth16`], so a proof accepted
+//! here at a given timestamp is accepted on-chain at the same timestamp, and a rejected one fails
+//! with the same [`SolanaWorldIDProgramError`](crate::error::SolanaWorldIDProgramError).
+//! The accounts are the fetched `Root`, `LatestRoot` and `VerificationTypeConfig` of the proof's
+//! root hash and verification type, and its `VerifyingKey` if it does not use the built-in key,
+//! see [`pda`](crate::pda).
+
+use anchor_lang::{error::ErrorCode, prelude::*};
+use solana_world_id_program::{
+    check_root, check_verifying_key,
+    state::{LatestRoot, Root, VerificationTypeConfig, VerifyingKey},
+    verify_groth16, Groth16ProofArgs, VerificationResult,
+};
+
+/// Evaluates the freshness of `root` at `timestamp` (in seconds) without verifying a proof.
+///
+/// Fails if proofs may no longer be verified against the root, otherwise returns the
+/// [`VerificationResult`] which verify_groth16_proof would return for a valid proof.
+pub fn evaluate_root(
+    root: &Root,
+    latest_root: &LatestRoot,
+    verification_type_config: &VerificationTypeConfig,
+    timestamp: u64,
+) -> Result<VerificationResult> {
+    // On-chain, the accounts are the PDAs of a single verification type.
+    require!(
+        root.verification_type == verification_type_config.verification_type
+            && latest_root.verification_type == verification_type_config.verification_type,
+        ErrorCode::ConstraintSeeds
+    );
+    check_root(root, latest_root, verification_type_config, timestamp)?;
+    Ok(VerificationResult::new(
+        root,
+        latest_root,
+        verification_type_config,
+        timestamp,
+    ))
+}
+
+/// Verifies `args` as verify_groth16_proof would at `timestamp` (in seconds), with `verifying_key`
+/// passed as its optional `verifying_key` account.
+pub fn verify_groth16_proof(
+    args: &Groth16ProofArgs,
+    root: &Root,
+    latest_root: &LatestRoot,
+    verification_type_config: &VerificationTypeConfig,
+    verifying_key: Option<&VerifyingKey>,
+    timestamp: u64,
+) -> Result<VerificationResult> {
+    // On-chain, the Root account is the PDA of the proof's root hash.
+    require!(root.root == args.root_hash, ErrorCode::ConstraintSeeds);
+    let result = evaluate_root(root, latest_root, verification_type_config, timestamp)?;
+    check_verifying_key(verification_type_config, verifying_key, timestamp)?;
+    verify_groth16(
+        verifying_key,
+        args.root_hash,
+        args.signal_hash,
+        args.nullifier_hash,
+        args.external_nullifier_hash,
+        &args.proof,
+    )?;
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use anchor_lang::error::Error;
+    use solana_world_id_program::{
+        error::SolanaWorldIDProgramError,
+        state::{PreviousVerifyingKey, RootSource},
+    };
+
+    use super::*;
+
+    const VERIFICATION_TYPE: [u8; 1] = [0];
+    const ROOT_EXPIRY: u64 = 24 * 60 * 60;
+    /// Read block time of [`root`], in seconds.
+    const READ_BLOCK_TIME: u64 = 1_720_000_000;
+
+    fn root(root: [u8; 32]) -> Root {
+        Root {
+            bump: 255,
+            read_block_number: 1,
+            read_block_hash: [0; 32],
+            read_block_time: READ_BLOCK_TIME * 1_000_000,
+            refund_recipient: Pubkey::default(),
+            root,
+            verification_type: VERIFICATION_TYPE,
+            revoked: false,
+        }
+    }
+
+    fn latest_root(root: [u8; 32]) -> LatestRoot {
+        LatestRoot {
+            bump: 255,
+            read_block_number: 2,
+            read_block_hash: [0; 32],
+            read_block_time: READ_BLOCK_TIME * 1_000_000,
+            root,
+            verification_type: VERIFICATION_TYPE,
+        }
+    }
+
+    fn config(enabled: bool) -> VerificationTypeConfig {
+        VerificationTypeConfig {
+            bump: 255,
+            verification_type: VERIFICATION_TYPE,
+            enabled,
+            root_expiry: ROOT_EXPIRY,
+            allowed_update_staleness: 5 * 60,
+            source: RootSource::Query {
+                chain_id: 2,
+                contract: [0; 20],
+                selector: [0; 4],
+            },
+            verifying_key_id: None,
+            pending_verifying_key: None,
+            previous_verifying_key: None,
+        }
+    }
+
+    #[test]
+    fn evaluates_unexpired_root() {
+        let result = evaluate_root(
+            &root([1; 32]),
+            &latest_root([2; 32]),
+            &config(true),
+            READ_BLOCK_TIME + 60,
+        )
+        .unwrap();
+        assert_eq!(
+            result,
+            VerificationResult {
+                root: [1; 32],
+                read_block_time: READ_BLOCK_TIME * 1_000_000,
+                is_latest_root: false,
+                seconds_until_expiry: ROOT_EXPIRY - 60,
+            }
+        );
+    }
+
+    #[test]
+    fn root_expires_after_root_expiry() {
+        let root = root([1; 32]);
+        let latest_root = latest_root([2; 32]);
+        let config = config(true);
+        let expiry_time = READ_BLOCK_TIME + ROOT_EXPIRY;
+        let result = evaluate_root(&root, &latest_root, &config, expiry_time).unwrap();
+        assert_eq!(result.seconds_until_expiry, 0);
+        assert_eq!(
+            evaluate_root(&root, &latest_root, &config, expiry_time + 1).unwrap_err(),
+            Error::from(SolanaWorldIDProgramError::RootExpired)
+        );
+    }
+
+    #[test]
+    fn latest_root_never_expires() {
+        let result = evaluate_root(
+            &root([1; 32]),
+            &latest_root([1; 32]),
+            &config(true),
+            READ_BLOCK_TIME + 2 * ROOT_EXPIRY,
+        )
+        .unwrap();
+        assert!(result.is_latest_root);
+        assert_eq!(result.seconds_until_expiry, 0);
+    }
+
+    #[test]
+    fn rejects_disabled_verification_type() {
+        assert_eq!(
+            evaluate_root(
+                &root([1; 32]),
+                &latest_root([1; 32]),
+                &config(false),
+                READ_BLOCK_TIME
+            )
+            .unwrap_err(),
+            Error::from(SolanaWorldIDProgramError::VerificationTypeDisabled)
+        );
+    }
+
+    #[test]
+    fn rejects_revoked_root() {
+        let mut root = root([1; 32]);
+        root.revoked = true;
+        assert_eq!(
+            evaluate_root(&root, &latest_root([1; 32]), &config(true), READ_BLOCK_TIME)
+                .unwrap_err(),
+            Error::from(SolanaWorldIDProgramError::RootRevoked)
+        );
+    }
+
+    #[test]
+    fn rejects_root_of_another_verification_type() {
+        let mut root = root([1; 32]);
+        root.verification_type = [1];
+        assert_eq!(
+            evaluate_root(&root, &latest_root([1; 32]), &config(true), READ_BLOCK_TIME)
+                .unwrap_err(),
+            Error::from(ErrorCode::ConstraintSeeds)
+        );
+    }
+
+    #[test]
+    fn requires_verifying_key_of_verification_type() {
+        let mut config = config(true);
+        config.verifying_key_id = Some([1]);
+        let args = Groth16ProofArgs {
+            root_hash: [1; 32],
+            signal_hash: [0; 32],
+            nullifier_hash: [0; 32],
+            external_nullifier_hash: [0; 32],
+            proof: [0; 256],
+        };
+        assert_eq!(
+            verify_groth16_proof(
+                &args,
+                &root([1; 32]),
+                &latest_root([1; 32]),
+                &config,
+                None,
+                READ_BLOCK_TIME
+            )
+            .unwrap_err(),
+            Error::from(SolanaWorldIDProgramError::VerifyingKeyNotProvided)
+        );
+    }
+
+    #[test]
+    fn accepts_previous_verifying_key_until_grace_period_ends() {
+        let mut config = config(true);
+        config.verifying_key_id = Some([1]);
+        config.previous_verifying_key = Some(PreviousVerifyingKey {
+            key_id: None,
+            accepted_until: READ_BLOCK_TIME,
+        });
+        assert!(check_verifying_key(&config, None, READ_BLOCK_TIME).is_ok());
+        assert_eq!(
+            check_verifying_key(&config, None, READ_BLOCK_TIME + 1).unwrap_err(),
+            Error::from(SolanaWorldIDProgramError::VerifyingKeyGracePeriodEnded)
+        );
+    }
+}
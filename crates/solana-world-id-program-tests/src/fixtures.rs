@@ -0,0 +1,93 @@
+//! Core Bridge accounts of `tests/accounts/core_bridge_mainnet`, shared with the TypeScript tests.
+
+use std::{fs, path::Path};
+
+use anchor_lang::prelude::Pubkey;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::Deserialize;
+use solana_sdk::{account::Account, pubkey};
+
+pub const CORE_BRIDGE_MAINNET_ACCOUNTS: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/../../tests/accounts/core_bridge_mainnet"
+);
+
+/// A single devnet guardian, i.e. `MockGuardianSet::devnet(1)`.
+pub const MOCK_GUARDIAN_SET_INDEX: u32 = 5;
+/// Same guardian as guardian set 5, expired since 2023.
+pub const EXPIRED_MOCK_GUARDIAN_SET_INDEX: u32 = 6;
+/// 19 guardians of which only the first is a devnet guardian.
+pub const NO_QUORUM_MOCK_GUARDIAN_SET_INDEX: u32 = 7;
+/// The first two devnet guardians, i.e. `MockGuardianSet::devnet(2)`.
+pub const TWO_MOCK_GUARDIAN_SET_INDEX: u32 = 8;
+
+/// Emitter of the posted VAAs below, unless stated otherwise.
+pub const VAA_EMITTER_CHAIN: u16 = 2;
+pub const VAA_EMITTER_ADDRESS: [u8; 32] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0xe0, 0xa4, 0xf8, 0xb6, 0xd9, 0xe3, 0x34, 0xb3, 0x7c, 0x76,
+    0xe9, 0x3d, 0x1e, 0x60, 0x42, 0x0e, 0x08, 0x48, 0x71, 0x94,
+];
+
+// Posted VAAs, all published at u32::MAX so that they are never stale on the wall clock of the
+// test validator, with a root payload.
+/// Sequence 0.
+pub const POSTED_VAA_ROOT_0: Pubkey = pubkey!("HEnBrfFNaS5NMuwvu47rczf5kv9bzdWTVGY5a6719tQE");
+/// Sequence 1.
+pub const POSTED_VAA_ROOT_1: Pubkey = pubkey!("AiGakTVfp1XRdxdTgZe5fb9ZhDCmGbxPBXmjJAX9KxkS");
+/// Sequence 0, with a different root than [`POSTED_VAA_ROOT_0`].
+pub const POSTED_VAA_STALE_SEQUENCE: Pubkey =
+    pubkey!("B4orqtzvNufDmauNdkFbN9iBE3ZHkRdsLM26byemC3RQ");
+/// Payload id 2 instead of 1.
+pub const POSTED_VAA_INVALID_PAYLOAD: Pubkey =
+    pubkey!("3Qk1zTpCDQydf4wsyRNNUD2DAbjVT2qnsFSQr2aNpg3V");
+/// Emitted from chain 4.
+pub const POSTED_VAA_INVALID_EMITTER_CHAIN: Pubkey =
+    pubkey!("BTDVbGGKiB27RjhHLMyqW51kWvy4xDjLEbbDp6GjzYF1");
+/// Emitted by another contract on chain 2.
+pub const POSTED_VAA_INVALID_EMITTER_ADDRESS: Pubkey =
+    pubkey!("3KqeNgwwyWAxH2FeVorExLWVpxYwZJLzTmknNTcwCdWt");
+
+/// An account as dumped by `solana account --output json`.
+#[derive(Deserialize)]
+struct AccountFixture {
+    pubkey: String,
+    account: AccountFixtureData,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AccountFixtureData {
+    lamports: u64,
+    /// Data and its encoding, which is always base64.
+    data: (String, String),
+    owner: String,
+    executable: bool,
+    rent_epoch: u64,
+}
+
+pub fn load_account(path: &Path) -> (Pubkey, Account) {
+    let json = fs::read_to_string(path)
+        .unwrap_or_else(|e| panic!("failed to read {}: {e}", path.display()));
+    let fixture: AccountFixture = serde_json::from_str(&json)
+        .unwrap_or_else(|e| panic!("invalid account {}: {e}", path.display()));
+    assert_eq!(fixture.account.data.1, "base64", "{}", path.display());
+    let account = Account {
+        lamports: fixture.account.lamports,
+        data: STANDARD.decode(&fixture.account.data.0).unwrap(),
+        owner: fixture.account.owner.parse().unwrap(),
+        executable: fixture.account.executable,
+        rent_epoch: fixture.account.rent_epoch,
+    };
+    (fixture.pubkey.parse().unwrap(), account)
+}
+
+/// Every account of [`CORE_BRIDGE_MAINNET_ACCOUNTS`].
+pub fn core_bridge_accounts() -> Vec<(Pubkey, Account)> {
+    let mut paths: Vec<_> = fs::read_dir(CORE_BRIDGE_MAINNET_ACCOUNTS)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    paths.sort();
+    paths.iter().map(|path| load_account(path)).collect()
+}
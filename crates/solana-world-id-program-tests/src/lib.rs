@@ -0,0 +1,453 @@
+//! Harness for integration tests of the Solana World ID Program on solana-program-test.
+//!
+//! [`TestContext::start`] runs the program natively alongside the Core Bridge accounts of
+//! [`fixtures`] (the same guardian sets and posted VAAs as the TypeScript tests), initializes it and
+//! sets the clock to [`START_TIME`]. Roots of [`QUERY_VERIFICATION_TYPE`] are read from a fake
+//! Ethereum and signed by the devnet guardian of [`MOCK_GUARDIAN_SET_INDEX`] with a
+//! [`QueryProxyMock`].
+
+pub mod fixtures;
+pub mod proof;
+pub mod secp256k1;
+
+use anchor_lang::{
+    prelude::{AccountInfo, Pubkey},
+    solana_program::{
+        bpf_loader_upgradeable::{self, UpgradeableLoaderState},
+        entrypoint::ProgramResult,
+        instruction::{Instruction, InstructionError},
+        native_token::LAMPORTS_PER_SOL,
+        sysvar::clock::Clock,
+    },
+    AccountDeserialize,
+};
+use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    account::Account,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+use solana_world_id_client::{
+    error::SolanaWorldIDProgramError,
+    instructions,
+    pda::derive_program_data_key,
+    program::{ETH_CHAIN_ID, ETH_WORLD_ID_IDENTITY_MANAGER, LATEST_ROOT_SIGNATURE, VERIFYING_KEY},
+    query::latest_root_query_request,
+    state::{ConfigChange, RootSource, WormholePostedVaa},
+    InitializeArgs, RegisterVerificationTypeArgs, RegisterVerifyingKeyArgs, ID,
+};
+use wormhole_query_mock::{
+    EthCallResponse, MockChain, MockEvmState, MockGuardianSet, MockQueryResponse, QueryProxyMock,
+};
+
+pub use fixtures::*;
+
+/// Long before the posted VAAs of [`fixtures`] were published.
+pub const START_TIME: i64 = 1_720_000_060;
+pub const ROOT_EXPIRY: u64 = 24 * 60 * 60;
+pub const ALLOWED_UPDATE_STALENESS: u64 = 5 * 60;
+pub const VERIFYING_KEY_ACTIVATION_DELAY: u64 = 60 * 60;
+pub const CONFIG_CHANGE_DELAY: u64 = 60 * 60;
+
+/// Registered by initialize with [`eth_root_source`].
+pub const QUERY_VERIFICATION_TYPE: [u8; 1] = [0];
+/// Registered by [`TestContext::register_vaa_verification_type`].
+pub const VAA_VERIFICATION_TYPE: [u8; 1] = [2];
+
+/// Registered by [`TestContext::register_verifying_key`].
+pub const VERIFYING_KEY_ID: [u8; 1] = [1];
+
+const START_BLOCK_NUMBER: u64 = 20_000_000;
+
+/// The built-in verifying key, which [`valid_proof`](proof::valid_proof) is valid for.
+pub fn built_in_verifying_key() -> RegisterVerifyingKeyArgs {
+    RegisterVerifyingKeyArgs {
+        alpha_g1: VERIFYING_KEY.vk_alpha_g1,
+        beta_g2: VERIFYING_KEY.vk_beta_g2,
+        gamma_g2: VERIFYING_KEY.vk_gamme_g2,
+        delta_g2: VERIFYING_KEY.vk_delta_g2,
+        ic: VERIFYING_KEY.vk_ic.try_into().unwrap(),
+    }
+}
+
+/// Source set by initialize for the network feature the program was built with.
+pub fn eth_root_source() -> RootSource {
+    RootSource::Query {
+        chain_id: ETH_CHAIN_ID,
+        contract: ETH_WORLD_ID_IDENTITY_MANAGER,
+        selector: LATEST_ROOT_SIGNATURE,
+    }
+}
+
+fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &[u8],
+) -> ProgramResult {
+    // The Anchor entrypoint requires the account infos to live as long as their data, which the
+    // signature of a native processor cannot express. The copied account infos are owned for the
+    // duration of the instruction and freed afterwards.
+    let accounts = Box::into_raw(accounts.to_vec().into_boxed_slice());
+    // SAFETY: `accounts` is valid until freed below, and the entrypoint does not keep any borrow
+    // of it once it returns.
+    let result = solana_world_id_client::program::entry(program_id, unsafe { &*accounts }, data);
+    // SAFETY: `accounts` was allocated by `Box::into_raw` above and is no longer borrowed.
+    drop(unsafe { Box::from_raw(accounts) });
+    result
+}
+
+/// ProgramData of the program, which is only read by initialize and the admin instructions.
+fn program_data_account(upgrade_authority: Pubkey) -> Account {
+    Account::new_data(
+        LAMPORTS_PER_SOL,
+        &UpgradeableLoaderState::ProgramData {
+            slot: 0,
+            upgrade_authority_address: Some(upgrade_authority),
+        },
+        &bpf_loader_upgradeable::id(),
+    )
+    .unwrap()
+}
+
+/// Asserts that `result` failed with `error`.
+pub fn assert_error(result: Result<(), BanksClientError>, error: SolanaWorldIDProgramError) {
+    let code = u32::from(error);
+    match result.map_err(|e| e.unwrap()) {
+        Err(TransactionError::InstructionError(_, InstructionError::Custom(actual))) => {
+            assert_eq!(actual, code, "expected {error:?} ({code})")
+        }
+        other => panic!("expected {error:?} ({code}), got {other:?}"),
+    }
+}
+
+pub struct TestContext {
+    pub context: ProgramTestContext,
+    /// Upgrade authority of the program, and its owner once initialized.
+    pub owner: Keypair,
+    /// Answers root queries from a fake Ethereum, see [`TestContext::next_block`].
+    pub proxy: QueryProxyMock,
+    nonce: u32,
+}
+
+impl TestContext {
+    /// Starts at [`START_TIME`] with the program initialized with [`TestContext::initialize_args`].
+    pub async fn start() -> Self {
+        let mut ctx = Self::start_uninitialized().await;
+        let initialize =
+            instructions::initialize(ctx.payer(), ctx.owner.pubkey(), Self::initialize_args());
+        ctx.send_as_owner(&[initialize]).await.unwrap();
+        ctx
+    }
+
+    /// Starts at [`START_TIME`] with the program deployed by the owner but not initialized.
+    pub async fn start_uninitialized() -> Self {
+        let mut program_test = ProgramTest::new(
+            "solana_world_id_program",
+            ID,
+            processor!(process_instruction),
+        );
+        for (address, account) in core_bridge_accounts() {
+            program_test.add_account(address, account);
+        }
+        let owner = Keypair::new();
+        program_test.add_account(
+            derive_program_data_key(),
+            program_data_account(owner.pubkey()),
+        );
+        let context = program_test.start_with_context().await;
+
+        let chain = MockChain::new(START_BLOCK_NUMBER, START_TIME as u64 * 1_000_000);
+        let proxy = QueryProxyMock::new(
+            MockGuardianSet::devnet(1),
+            MockEvmState::default().with_chain(ETH_CHAIN_ID, chain),
+        );
+        let mut ctx = Self {
+            context,
+            owner,
+            proxy,
+            nonce: 0,
+        };
+        ctx.set_time(START_TIME).await;
+        ctx
+    }
+
+    pub fn initialize_args() -> InitializeArgs {
+        InitializeArgs {
+            root_expiry: ROOT_EXPIRY,
+            allowed_update_staleness: ALLOWED_UPDATE_STALENESS,
+            eth_chain_id: None,
+            eth_world_id_identity_manager: None,
+            latest_root_signature: None,
+            verifying_key_activation_delay: Some(VERIFYING_KEY_ACTIVATION_DELAY),
+            config_change_delay: Some(CONFIG_CHANGE_DELAY),
+        }
+    }
+
+    pub fn payer(&self) -> Pubkey {
+        self.context.payer.pubkey()
+    }
+
+    /// Sends `instructions` paid for by the payer, which always signs.
+    pub async fn send(
+        &mut self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> Result<(), BanksClientError> {
+        let mut all_signers = vec![&self.context.payer];
+        all_signers.extend_from_slice(signers);
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&self.context.payer.pubkey()),
+            &all_signers,
+            self.context.last_blockhash,
+        );
+        self.context
+            .banks_client
+            .process_transaction(transaction)
+            .await
+    }
+
+    /// Sends `instructions` signed by the payer and the owner.
+    pub async fn send_as_owner(
+        &mut self,
+        instructions: &[Instruction],
+    ) -> Result<(), BanksClientError> {
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&self.context.payer.pubkey()),
+            &[&self.context.payer, &self.owner],
+            self.context.last_blockhash,
+        );
+        self.context
+            .banks_client
+            .process_transaction(transaction)
+            .await
+    }
+
+    /// Simulates `instructions` paid for by the payer and returns the data returned by the program.
+    pub async fn simulate_return_data(
+        &mut self,
+        instructions: &[Instruction],
+    ) -> Result<Vec<u8>, BanksClientError> {
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&self.context.payer.pubkey()),
+            &[&self.context.payer],
+            self.context.last_blockhash,
+        );
+        let simulation = self
+            .context
+            .banks_client
+            .simulate_transaction(transaction)
+            .await?;
+        if let Some(Err(error)) = simulation.result {
+            return Err(BanksClientError::TransactionError(error));
+        }
+        Ok(simulation
+            .simulation_details
+            .and_then(|details| details.return_data)
+            .map(|return_data| return_data.data)
+            .unwrap_or_default())
+    }
+
+    /// Waits for a new blockhash, which is needed to send a transaction identical to an earlier one.
+    pub async fn refresh_blockhash(&mut self) {
+        self.context.get_new_latest_blockhash().await.unwrap();
+    }
+
+    pub async fn account<T: AccountDeserialize>(&mut self, address: Pubkey) -> T {
+        let account = self
+            .context
+            .banks_client
+            .get_account(address)
+            .await
+            .unwrap()
+            .unwrap_or_else(|| panic!("account {address} not found"));
+        T::try_deserialize(&mut account.data.as_slice()).unwrap()
+    }
+
+    pub async fn now(&mut self) -> i64 {
+        let clock: Clock = self.context.banks_client.get_sysvar().await.unwrap();
+        clock.unix_timestamp
+    }
+
+    pub async fn set_time(&mut self, unix_timestamp: i64) {
+        let mut clock: Clock = self.context.banks_client.get_sysvar().await.unwrap();
+        clock.unix_timestamp = unix_timestamp;
+        self.context.set_sysvar(&clock);
+    }
+
+    /// Moves the clock forward by `seconds`.
+    pub async fn warp(&mut self, seconds: u64) {
+        let now = self.now().await;
+        self.set_time(now + seconds as i64).await;
+    }
+
+    /// Produces a block on the fake Ethereum at the current time, at which `latestRoot()` returns
+    /// `root`, and returns its block number.
+    pub async fn next_block(&mut self, root: [u8; 32]) -> u64 {
+        let now = self.now().await as u64;
+        let chain = self.proxy.state.chain_mut(ETH_CHAIN_ID).unwrap();
+        chain.advance(1, 0);
+        chain.block_time = now * 1_000_000;
+        chain.set_call(
+            ETH_WORLD_ID_IDENTITY_MANAGER,
+            LATEST_ROOT_SIGNATURE.to_vec(),
+            root.to_vec(),
+        );
+        chain.block_number
+    }
+
+    /// A response of the latest block on the fake Ethereum with arbitrary `results`.
+    pub fn eth_call_response(&self, results: Vec<Vec<u8>>) -> EthCallResponse {
+        let chain = &self.proxy.state.chains[&ETH_CHAIN_ID];
+        EthCallResponse {
+            block_number: chain.block_number,
+            block_hash: chain.block_hash,
+            block_time: chain.block_time,
+            results,
+        }
+    }
+
+    pub fn next_nonce(&mut self) -> u32 {
+        self.nonce += 1;
+        self.nonce
+    }
+
+    /// Produces a block at which `latestRoot()` returns `root` and returns the signed response of
+    /// its root query.
+    pub async fn mock_root_response(&mut self, root: [u8; 32]) -> MockQueryResponse {
+        let block_number = self.next_block(root).await;
+        let nonce = self.next_nonce();
+        let request = latest_root_query_request(nonce, &eth_root_source(), block_number).unwrap();
+        self.proxy.mock(&request).unwrap()
+    }
+
+    /// Posts `signatures` to a new GuardianSignatures account and returns it.
+    pub async fn post_signatures(&mut self, signatures: Vec<[u8; 66]>) -> Keypair {
+        let guardian_signatures = Keypair::new();
+        let total_signatures = signatures.len() as u8;
+        let ix = instructions::post_signatures(
+            self.payer(),
+            guardian_signatures.pubkey(),
+            signatures,
+            total_signatures,
+        );
+        self.send(&[ix], &[&guardian_signatures]).await.unwrap();
+        guardian_signatures
+    }
+
+    /// Posts the signatures of `response` and updates the root of [`QUERY_VERIFICATION_TYPE`].
+    pub async fn update_root_with_query(
+        &mut self,
+        response: &MockQueryResponse,
+        root_hash: [u8; 32],
+        guardian_set_index: u32,
+    ) -> Result<(), BanksClientError> {
+        let guardian_signatures = self.post_signatures(response.signatures.clone()).await;
+        let ix = instructions::update_root_with_query(
+            self.payer(),
+            false,
+            guardian_signatures.pubkey(),
+            self.payer(),
+            response.bytes.clone(),
+            root_hash,
+            QUERY_VERIFICATION_TYPE,
+            guardian_set_index,
+        );
+        self.send(&[ix], &[]).await
+    }
+
+    /// Updates the root of [`QUERY_VERIFICATION_TYPE`] to `root`, which must be new.
+    pub async fn update_root(&mut self, root: [u8; 32]) {
+        let response = self.mock_root_response(root).await;
+        self.update_root_with_query(&response, root, MOCK_GUARDIAN_SET_INDEX)
+            .await
+            .unwrap();
+    }
+
+    /// Registers [`VAA_VERIFICATION_TYPE`] for roots emitted by the posted VAAs of [`fixtures`].
+    pub async fn register_vaa_verification_type(&mut self) {
+        let ix = instructions::register_verification_type(
+            self.payer(),
+            self.owner.pubkey(),
+            VAA_VERIFICATION_TYPE,
+            RegisterVerificationTypeArgs {
+                root_expiry: ROOT_EXPIRY,
+                allowed_update_staleness: ALLOWED_UPDATE_STALENESS,
+                source: RootSource::Vaa {
+                    emitter_chain: VAA_EMITTER_CHAIN,
+                    emitter_address: VAA_EMITTER_ADDRESS,
+                },
+            },
+        );
+        self.send_as_owner(&[ix]).await.unwrap();
+    }
+
+    /// The root in the payload of a posted VAA, which need not have a valid payload id.
+    pub async fn posted_vaa_root(&mut self, posted_vaa: Pubkey) -> [u8; 32] {
+        let posted_vaa: WormholePostedVaa = self.account(posted_vaa).await;
+        posted_vaa.payload[1..33].try_into().unwrap()
+    }
+
+    /// Updates the root of [`VAA_VERIFICATION_TYPE`] with a posted VAA.
+    pub async fn update_root_with_vaa(
+        &mut self,
+        posted_vaa: Pubkey,
+        root_hash: [u8; 32],
+    ) -> Result<(), BanksClientError> {
+        let ix = instructions::update_root_with_vaa(
+            self.payer(),
+            posted_vaa,
+            root_hash,
+            VAA_VERIFICATION_TYPE,
+        );
+        self.send(&[ix], &[]).await
+    }
+
+    /// Registers [`built_in_verifying_key`] as [`VERIFYING_KEY_ID`] and makes it the key of
+    /// [`QUERY_VERIFICATION_TYPE`].
+    pub async fn register_verifying_key(&mut self) {
+        let ix = instructions::register_verifying_key(
+            self.payer(),
+            self.owner.pubkey(),
+            VERIFYING_KEY_ID,
+            built_in_verifying_key(),
+        );
+        self.send_as_owner(&[ix]).await.unwrap();
+        self.rotate_verifying_key(Some(VERIFYING_KEY_ID), 0).await;
+    }
+
+    /// Proposes `verifying_key_id` as the key of [`QUERY_VERIFICATION_TYPE`] and activates it once
+    /// the activation delay has passed.
+    pub async fn rotate_verifying_key(
+        &mut self,
+        verifying_key_id: Option<[u8; 1]>,
+        grace_period: u64,
+    ) {
+        let ix = instructions::propose_verifying_key(
+            self.owner.pubkey(),
+            QUERY_VERIFICATION_TYPE,
+            verifying_key_id,
+            grace_period,
+        );
+        self.send_as_owner(&[ix]).await.unwrap();
+        self.warp(VERIFYING_KEY_ACTIVATION_DELAY).await;
+        let ix = instructions::activate_verifying_key(
+            self.owner.pubkey(),
+            QUERY_VERIFICATION_TYPE,
+            verifying_key_id,
+        );
+        self.send_as_owner(&[ix]).await.unwrap();
+    }
+
+    /// Proposes `change`, paid for by the payer, and executes it once the config change delay has
+    /// passed.
+    pub async fn change_config(&mut self, change: ConfigChange) {
+        let ix = instructions::propose_config_change(self.payer(), self.owner.pubkey(), change);
+        self.send_as_owner(&[ix]).await.unwrap();
+        self.warp(CONFIG_CHANGE_DELAY).await;
+        let ix = instructions::execute_config_change(self.owner.pubkey(), change, self.payer());
+        self.send_as_owner(&[ix]).await.unwrap();
+    }
+}
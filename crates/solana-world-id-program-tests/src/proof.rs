@@ -0,0 +1,31 @@
+//! The valid proof of the TypeScript tests, an example `ISuccessResult` of IDKit.
+
+use solana_world_id_client::{
+    hashing::{external_nullifier_hash, signal_hash},
+    Groth16ProofArgs,
+};
+
+/// Root from Sepolia at block 6243824, when the proof was generated.
+const MERKLE_ROOT: &str = "05628ccef5b585f9a5afb764d22835f2c71b10beb4b212e45ec9e4d0354c9764";
+const NULLIFIER_HASH: &str = "2aa975196dc1f4f9f57b8195bea9c61331e0012ec25484ed569782c49145721a";
+const PROOF: &str = "1eef0990c59b6985413ba1589afb6a6b673a4723ea3215923850b89c61aceeee2748da443e3fdf161456b05cb82bc2e6f1ace9e67cdaf76732ff9bf31e6b82b724d7c33a4998f4cd2d1c9f2c90a249910be0aa23b882c9756371769d1af1923d0509d6791ff17c3604425a4a113b5103c014c8f724edc649f1417e56b6cdb4422896c0d5492652ebb1e001016431ae457b58361b5b6cbea25565236362f06fae098cf3493c1c299313672ebd85fde41607261491e3cc57dea51ee7951b47020b21b73fceef2b8fb59c6f5f0302dffa36f9ec01319a257f20052b83f2d7a3232c22f5d0600fb036a5b8d2a3d6d92a3c043b665510d97bf9020510b5f9c692a9a5";
+/// The default anvil wallet.
+pub const SIGNAL: &str = "f39fd6e51aad88f6f4ce6ab8827279cfffb92266";
+/// An example app and action created via https://developer.worldcoin.org
+pub const APP_ID: &str = "app_staging_7d23b838b02776cebd87b86ac3248641";
+pub const ACTION: &str = "testing";
+
+fn decode<const N: usize>(hex: &str) -> [u8; N] {
+    hex::decode(hex).unwrap().try_into().unwrap()
+}
+
+/// A valid proof against the root [`Groth16ProofArgs::root_hash`], which must be posted first.
+pub fn valid_proof() -> Groth16ProofArgs {
+    Groth16ProofArgs {
+        root_hash: decode(MERKLE_ROOT),
+        signal_hash: signal_hash(&decode::<20>(SIGNAL)),
+        nullifier_hash: decode(NULLIFIER_HASH),
+        external_nullifier_hash: external_nullifier_hash(APP_ID, ACTION),
+        proof: decode(PROOF),
+    }
+}
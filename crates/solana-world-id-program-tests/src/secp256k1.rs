@@ -0,0 +1,56 @@
+//! secp256k1 program instructions which verify guardian signatures for
+//! update_root_with_query_precompile. Same as `tests/helpers/secp256k1.ts`.
+
+use anchor_lang::solana_program::{instruction::Instruction, keccak, secp256k1_program};
+use wormhole_query_sdk::MESSAGE_PREFIX;
+
+// https://docs.solanalabs.com/runtime/programs#secp256k1-program
+const SIGNATURE_OFFSETS_SIZE: usize = 11;
+const ETH_ADDRESS_SIZE: usize = 20;
+const SIGNATURE_SIZE: usize = 65;
+
+/// Message verified by the secp256k1 program, which hashes it once more before recovery.
+pub fn query_message(bytes: &[u8]) -> Vec<u8> {
+    [MESSAGE_PREFIX, &keccak::hash(bytes).0].concat()
+}
+
+/// Creates a secp256k1 program instruction which verifies every signature over the query message
+/// of `bytes`.
+///
+/// `signatures` are in the form returned by `MockGuardianSet::sign`, `guardian_keys` are the
+/// addresses of the guardians which signed, by guardian index, and `instruction_index` is the index
+/// of this instruction in the transaction.
+pub fn secp256k1_instruction(
+    signatures: &[[u8; 66]],
+    guardian_keys: &[[u8; 20]],
+    bytes: &[u8],
+    instruction_index: u8,
+) -> Instruction {
+    let message = query_message(bytes);
+    let data_start = 1 + signatures.len() * SIGNATURE_OFFSETS_SIZE;
+    let entry_size = ETH_ADDRESS_SIZE + SIGNATURE_SIZE;
+    let message_offset = data_start + signatures.len() * entry_size;
+    let mut data = vec![0; message_offset + message.len()];
+    data[0] = signatures.len() as u8;
+    for (i, signature) in signatures.iter().enumerate() {
+        let eth_address_offset = data_start + i * entry_size;
+        let signature_offset = eth_address_offset + ETH_ADDRESS_SIZE;
+        let offsets = 1 + i * SIGNATURE_OFFSETS_SIZE;
+        data[offsets..offsets + 2].copy_from_slice(&(signature_offset as u16).to_le_bytes());
+        data[offsets + 2] = instruction_index;
+        data[offsets + 3..offsets + 5].copy_from_slice(&(eth_address_offset as u16).to_le_bytes());
+        data[offsets + 5] = instruction_index;
+        data[offsets + 6..offsets + 8].copy_from_slice(&(message_offset as u16).to_le_bytes());
+        data[offsets + 8..offsets + 10].copy_from_slice(&(message.len() as u16).to_le_bytes());
+        data[offsets + 10] = instruction_index;
+        data[eth_address_offset..signature_offset]
+            .copy_from_slice(&guardian_keys[usize::from(signature[0])]);
+        data[signature_offset..signature_offset + SIGNATURE_SIZE].copy_from_slice(&signature[1..]);
+    }
+    data[message_offset..].copy_from_slice(&message);
+    Instruction {
+        program_id: secp256k1_program::ID,
+        accounts: vec![],
+        data,
+    }
+}
@@ -0,0 +1,14 @@
+use solana_sdk::signature::{Keypair, Signer};
+use solana_world_id_client::{error::SolanaWorldIDProgramError, instructions};
+use solana_world_id_program_tests::*;
+
+#[tokio::test]
+pub async fn rejects_claim_by_other_signer() {
+    let mut ctx = TestContext::start().await;
+    let other = Keypair::new();
+    let ix = instructions::claim_ownership(other.pubkey());
+    assert_error(
+        ctx.send(&[ix], &[&other]).await,
+        SolanaWorldIDProgramError::InvalidPendingOwner,
+    );
+}
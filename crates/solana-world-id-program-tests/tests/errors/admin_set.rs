@@ -0,0 +1,141 @@
+use solana_sdk::signature::{Keypair, Signer};
+use solana_world_id_client::{
+    error::SolanaWorldIDProgramError,
+    instructions,
+    pda::{derive_admin_set_key, derive_config_key},
+    state::{AdminSet, Config, Role},
+};
+use solana_world_id_program_tests::*;
+
+const THRESHOLD: u8 = 2;
+
+/// Starts with an admin set of three members and a threshold of two, which owns the program.
+async fn start() -> (TestContext, [Keypair; 3]) {
+    let mut ctx = TestContext::start().await;
+    let members = [Keypair::new(), Keypair::new(), Keypair::new()];
+    let ix = instructions::initialize_admin_set(
+        ctx.payer(),
+        ctx.owner.pubkey(),
+        members.iter().map(Keypair::pubkey).collect(),
+        THRESHOLD,
+    );
+    ctx.send_as_owner(&[ix]).await.unwrap();
+    let ix = instructions::transfer_ownership(ctx.owner.pubkey(), derive_admin_set_key());
+    ctx.send_as_owner(&[ix]).await.unwrap();
+    let ix = instructions::execute_as_admin_set(
+        instructions::claim_ownership(derive_admin_set_key()),
+        &[members[0].pubkey(), members[1].pubkey()],
+    );
+    ctx.send(&[ix], &[&members[0], &members[1]]).await.unwrap();
+    (ctx, members)
+}
+
+#[tokio::test]
+pub async fn executes_owner_instruction_with_threshold_signers() {
+    let (mut ctx, members) = start().await;
+    let pauser = Keypair::new().pubkey();
+    let ix = instructions::execute_as_admin_set(
+        instructions::grant_role(derive_admin_set_key(), Role::Pauser, pauser),
+        &[members[1].pubkey(), members[2].pubkey()],
+    );
+    ctx.send(&[ix], &[&members[1], &members[2]]).await.unwrap();
+    let config: Config = ctx.account(derive_config_key()).await;
+    assert_eq!(config.owner, derive_admin_set_key());
+    assert_eq!(config.pauser, Some(pauser));
+    // The previous owner no longer signs alone.
+    let ix = instructions::revoke_role(ctx.owner.pubkey(), Role::Pauser);
+    assert!(ctx.send_as_owner(&[ix]).await.is_err());
+}
+
+#[tokio::test]
+pub async fn rejects_below_threshold() {
+    let (mut ctx, members) = start().await;
+    let ix = instructions::execute_as_admin_set(
+        instructions::revoke_role(derive_admin_set_key(), Role::Pauser),
+        &[members[0].pubkey()],
+    );
+    assert_error(
+        ctx.send(&[ix], &[&members[0]]).await,
+        SolanaWorldIDProgramError::AdminSetThresholdNotMet,
+    );
+}
+
+#[tokio::test]
+pub async fn rejects_invalid_admin_set() {
+    let mut ctx = TestContext::start().await;
+    let members = vec![Keypair::new().pubkey(), Keypair::new().pubkey()];
+    let ix = instructions::initialize_admin_set(ctx.payer(), ctx.owner.pubkey(), members, 3);
+    assert_error(
+        ctx.send_as_owner(&[ix]).await,
+        SolanaWorldIDProgramError::InvalidAdminSet,
+    );
+}
+
+#[tokio::test]
+pub async fn rejects_removal_of_unknown_member() {
+    let (mut ctx, members) = start().await;
+    let ix = instructions::execute_as_admin_set(
+        instructions::remove_admin_set_member(Keypair::new().pubkey()),
+        &[members[0].pubkey(), members[1].pubkey()],
+    );
+    assert_error(
+        ctx.send(&[ix], &[&members[0], &members[1]]).await,
+        SolanaWorldIDProgramError::AdminSetMemberNotFound,
+    );
+}
+
+#[tokio::test]
+pub async fn updates_members_and_threshold() {
+    let (mut ctx, members) = start().await;
+    let new_member = Keypair::new();
+    // The new member approves the instructions following its addition.
+    let approvals = [
+        members[1].pubkey(),
+        members[2].pubkey(),
+        new_member.pubkey(),
+    ];
+    let ixs = [
+        instructions::add_admin_set_member(new_member.pubkey()),
+        instructions::set_admin_set_threshold(3),
+        instructions::remove_admin_set_member(members[0].pubkey()),
+    ]
+    .map(|ix| instructions::execute_as_admin_set(ix, &approvals));
+    ctx.send(&ixs, &[&members[1], &members[2], &new_member])
+        .await
+        .unwrap();
+    let admin_set: AdminSet = ctx.account(derive_admin_set_key()).await;
+    assert_eq!(
+        admin_set.members,
+        vec![
+            members[1].pubkey(),
+            members[2].pubkey(),
+            new_member.pubkey()
+        ]
+    );
+    assert_eq!(admin_set.threshold, 3);
+    // The removed member no longer counts towards the threshold.
+    let ix = instructions::execute_as_admin_set(
+        instructions::revoke_role(derive_admin_set_key(), Role::Pauser),
+        &[
+            members[0].pubkey(),
+            members[1].pubkey(),
+            members[2].pubkey(),
+        ],
+    );
+    assert_error(
+        ctx.send(&[ix], &[&members[0], &members[1], &members[2]])
+            .await,
+        SolanaWorldIDProgramError::AdminSetThresholdNotMet,
+    );
+    let ix = instructions::execute_as_admin_set(
+        instructions::revoke_role(derive_admin_set_key(), Role::Pauser),
+        &[
+            members[1].pubkey(),
+            members[2].pubkey(),
+            new_member.pubkey(),
+        ],
+    );
+    ctx.send(&[ix], &[&members[1], &members[2], &new_member])
+        .await
+        .unwrap();
+}
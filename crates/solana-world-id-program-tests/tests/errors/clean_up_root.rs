@@ -0,0 +1,42 @@
+use solana_program_test::BanksClientError;
+use solana_world_id_client::{error::SolanaWorldIDProgramError, instructions};
+use solana_world_id_program_tests::*;
+
+const ROOT: [u8; 32] = [1; 32];
+const OTHER_ROOT: [u8; 32] = [2; 32];
+
+async fn clean_up_root(ctx: &mut TestContext, root_hash: [u8; 32]) -> Result<(), BanksClientError> {
+    let ix = instructions::clean_up_root(root_hash, QUERY_VERIFICATION_TYPE, ctx.payer());
+    ctx.send(&[ix], &[]).await
+}
+
+#[tokio::test]
+pub async fn cleans_up_expired_root() {
+    let mut ctx = TestContext::start().await;
+    ctx.update_root(ROOT).await;
+    ctx.update_root(OTHER_ROOT).await;
+    ctx.warp(ROOT_EXPIRY + 1).await;
+    clean_up_root(&mut ctx, ROOT).await.unwrap();
+}
+
+#[tokio::test]
+pub async fn rejects_latest_root() {
+    let mut ctx = TestContext::start().await;
+    ctx.update_root(ROOT).await;
+    ctx.warp(ROOT_EXPIRY + 1).await;
+    assert_error(
+        clean_up_root(&mut ctx, ROOT).await,
+        SolanaWorldIDProgramError::RootIsLatest,
+    );
+}
+
+#[tokio::test]
+pub async fn rejects_unexpired_root() {
+    let mut ctx = TestContext::start().await;
+    ctx.update_root(ROOT).await;
+    ctx.update_root(OTHER_ROOT).await;
+    assert_error(
+        clean_up_root(&mut ctx, ROOT).await,
+        SolanaWorldIDProgramError::RootUnexpired,
+    );
+}
@@ -0,0 +1,251 @@
+use anchor_lang::solana_program::instruction::AccountMeta;
+use solana_sdk::signature::Signer;
+use solana_world_id_client::{
+    error::SolanaWorldIDProgramError,
+    instructions,
+    pda::{derive_config_key, derive_verification_type_config_key},
+    program::{ETH_CHAIN_ID, LATEST_ROOT_SIGNATURE},
+    state::{Config, ConfigChange, RootSource, VerificationTypeConfig},
+    InitializeArgs, RegisterVerificationTypeArgs, ID,
+};
+use solana_world_id_program_tests::*;
+
+const ROOT_EXPIRY_CHANGE: ConfigChange = ConfigChange::RootExpiry {
+    verification_type: QUERY_VERIFICATION_TYPE,
+    root_expiry: 60 * 60,
+};
+
+/// Index of the verification type config in the accounts of execute_config_change.
+const VERIFICATION_TYPE_CONFIG_INDEX: usize = 3;
+
+async fn propose(ctx: &mut TestContext, change: ConfigChange) {
+    let ix = instructions::propose_config_change(ctx.payer(), ctx.owner.pubkey(), change);
+    ctx.send_as_owner(&[ix]).await.unwrap();
+}
+
+#[tokio::test]
+pub async fn executes_change_after_delay() {
+    let mut ctx = TestContext::start().await;
+    ctx.change_config(ROOT_EXPIRY_CHANGE).await;
+    let verification_type_config: VerificationTypeConfig = ctx
+        .account(derive_verification_type_config_key(QUERY_VERIFICATION_TYPE))
+        .await;
+    assert_eq!(verification_type_config.root_expiry, 60 * 60);
+    ctx.change_config(ConfigChange::ConfigChangeDelay {
+        config_change_delay: 0,
+    })
+    .await;
+    let config: Config = ctx.account(derive_config_key()).await;
+    assert_eq!(config.config_change_delay, 0);
+}
+
+#[tokio::test]
+pub async fn executes_root_source_change() {
+    let mut ctx = TestContext::start().await;
+    let source = RootSource::Query {
+        chain_id: ETH_CHAIN_ID,
+        contract: [0xaa; 20],
+        selector: LATEST_ROOT_SIGNATURE,
+    };
+    ctx.change_config(ConfigChange::RootSource {
+        verification_type: QUERY_VERIFICATION_TYPE,
+        source,
+    })
+    .await;
+    let verification_type_config: VerificationTypeConfig = ctx
+        .account(derive_verification_type_config_key(QUERY_VERIFICATION_TYPE))
+        .await;
+    assert_eq!(verification_type_config.source, source);
+    // Queries of the previous source are no longer accepted.
+    let response = ctx.mock_root_response([1; 32]).await;
+    assert_error(
+        ctx.update_root_with_query(&response, [1; 32], MOCK_GUARDIAN_SET_INDEX)
+            .await,
+        SolanaWorldIDProgramError::InvalidRequestContract,
+    );
+}
+
+#[tokio::test]
+pub async fn rejects_root_source_of_another_chain() {
+    let mut ctx = TestContext::start().await;
+    for source in [
+        RootSource::Query {
+            chain_id: ETH_CHAIN_ID + 1,
+            contract: [0xaa; 20],
+            selector: LATEST_ROOT_SIGNATURE,
+        },
+        RootSource::Vaa {
+            emitter_chain: ETH_CHAIN_ID,
+            emitter_address: VAA_EMITTER_ADDRESS,
+        },
+    ] {
+        let change = ConfigChange::RootSource {
+            verification_type: QUERY_VERIFICATION_TYPE,
+            source,
+        };
+        propose(&mut ctx, change).await;
+        ctx.warp(CONFIG_CHANGE_DELAY).await;
+        let ix = instructions::execute_config_change(ctx.owner.pubkey(), change, ctx.payer());
+        assert_error(
+            ctx.send_as_owner(&[ix]).await,
+            SolanaWorldIDProgramError::RootSourceChainMismatch,
+        );
+        let ix = instructions::cancel_config_change(ctx.owner.pubkey(), change, ctx.payer());
+        ctx.send_as_owner(&[ix]).await.unwrap();
+    }
+}
+
+#[tokio::test]
+pub async fn rejects_out_of_bounds_value() {
+    let mut ctx = TestContext::start().await;
+    for change in [
+        ConfigChange::RootExpiry {
+            verification_type: QUERY_VERIFICATION_TYPE,
+            root_expiry: 0,
+        },
+        ConfigChange::RootExpiry {
+            verification_type: QUERY_VERIFICATION_TYPE,
+            root_expiry: ConfigChange::MAX_ROOT_EXPIRY + 1,
+        },
+        ConfigChange::AllowedUpdateStaleness {
+            verification_type: QUERY_VERIFICATION_TYPE,
+            allowed_update_staleness: ConfigChange::MAX_ALLOWED_UPDATE_STALENESS + 1,
+        },
+        ConfigChange::VerifyingKeyActivationDelay {
+            verifying_key_activation_delay: ConfigChange::MAX_VERIFYING_KEY_ACTIVATION_DELAY + 1,
+        },
+        ConfigChange::ConfigChangeDelay {
+            config_change_delay: ConfigChange::MAX_CONFIG_CHANGE_DELAY + 1,
+        },
+    ] {
+        let ix = instructions::propose_config_change(ctx.payer(), ctx.owner.pubkey(), change);
+        assert_error(
+            ctx.send_as_owner(&[ix]).await,
+            SolanaWorldIDProgramError::ConfigValueOutOfBounds,
+        );
+    }
+}
+
+#[tokio::test]
+pub async fn rejects_out_of_bounds_registration() {
+    let mut ctx = TestContext::start().await;
+    for (root_expiry, allowed_update_staleness) in [
+        (0, ALLOWED_UPDATE_STALENESS),
+        (ConfigChange::MAX_ROOT_EXPIRY + 1, ALLOWED_UPDATE_STALENESS),
+        (ROOT_EXPIRY, ConfigChange::MAX_ALLOWED_UPDATE_STALENESS + 1),
+    ] {
+        let ix = instructions::register_verification_type(
+            ctx.payer(),
+            ctx.owner.pubkey(),
+            VAA_VERIFICATION_TYPE,
+            RegisterVerificationTypeArgs {
+                root_expiry,
+                allowed_update_staleness,
+                source: RootSource::Vaa {
+                    emitter_chain: VAA_EMITTER_CHAIN,
+                    emitter_address: VAA_EMITTER_ADDRESS,
+                },
+            },
+        );
+        assert_error(
+            ctx.send_as_owner(&[ix]).await,
+            SolanaWorldIDProgramError::ConfigValueOutOfBounds,
+        );
+    }
+}
+
+#[tokio::test]
+pub async fn rejects_out_of_bounds_initialization() {
+    let mut ctx = TestContext::start_uninitialized().await;
+    for args in [
+        InitializeArgs {
+            root_expiry: 0,
+            ..TestContext::initialize_args()
+        },
+        InitializeArgs {
+            root_expiry: ConfigChange::MAX_ROOT_EXPIRY + 1,
+            ..TestContext::initialize_args()
+        },
+        InitializeArgs {
+            allowed_update_staleness: ConfigChange::MAX_ALLOWED_UPDATE_STALENESS + 1,
+            ..TestContext::initialize_args()
+        },
+        InitializeArgs {
+            verifying_key_activation_delay: Some(
+                ConfigChange::MAX_VERIFYING_KEY_ACTIVATION_DELAY + 1,
+            ),
+            ..TestContext::initialize_args()
+        },
+        InitializeArgs {
+            config_change_delay: Some(ConfigChange::MAX_CONFIG_CHANGE_DELAY + 1),
+            ..TestContext::initialize_args()
+        },
+    ] {
+        let ix = instructions::initialize(ctx.payer(), ctx.owner.pubkey(), args);
+        assert_error(
+            ctx.send_as_owner(&[ix]).await,
+            SolanaWorldIDProgramError::ConfigValueOutOfBounds,
+        );
+    }
+}
+
+#[tokio::test]
+pub async fn rejects_execution_before_delay() {
+    let mut ctx = TestContext::start().await;
+    propose(&mut ctx, ROOT_EXPIRY_CHANGE).await;
+    ctx.warp(CONFIG_CHANGE_DELAY - 1).await;
+    let ix =
+        instructions::execute_config_change(ctx.owner.pubkey(), ROOT_EXPIRY_CHANGE, ctx.payer());
+    assert_error(
+        ctx.send_as_owner(&[ix]).await,
+        SolanaWorldIDProgramError::ConfigChangeDelayNotElapsed,
+    );
+}
+
+#[tokio::test]
+pub async fn rejects_missing_verification_type_config() {
+    let mut ctx = TestContext::start().await;
+    propose(&mut ctx, ROOT_EXPIRY_CHANGE).await;
+    ctx.warp(CONFIG_CHANGE_DELAY).await;
+    let mut ix =
+        instructions::execute_config_change(ctx.owner.pubkey(), ROOT_EXPIRY_CHANGE, ctx.payer());
+    // Omitted optional accounts are passed as the program id.
+    ix.accounts[VERIFICATION_TYPE_CONFIG_INDEX] = AccountMeta::new_readonly(ID, false);
+    assert_error(
+        ctx.send_as_owner(&[ix]).await,
+        SolanaWorldIDProgramError::VerificationTypeConfigNotProvided,
+    );
+}
+
+#[tokio::test]
+pub async fn rejects_config_of_another_verification_type() {
+    let mut ctx = TestContext::start().await;
+    ctx.register_vaa_verification_type().await;
+    propose(&mut ctx, ROOT_EXPIRY_CHANGE).await;
+    ctx.warp(CONFIG_CHANGE_DELAY).await;
+    let mut ix =
+        instructions::execute_config_change(ctx.owner.pubkey(), ROOT_EXPIRY_CHANGE, ctx.payer());
+    ix.accounts[VERIFICATION_TYPE_CONFIG_INDEX] = AccountMeta::new(
+        derive_verification_type_config_key(VAA_VERIFICATION_TYPE),
+        false,
+    );
+    assert_error(
+        ctx.send_as_owner(&[ix]).await,
+        SolanaWorldIDProgramError::VerificationTypeConfigMismatch,
+    );
+}
+
+#[tokio::test]
+pub async fn cancels_change() {
+    let mut ctx = TestContext::start().await;
+    propose(&mut ctx, ROOT_EXPIRY_CHANGE).await;
+    let ix =
+        instructions::cancel_config_change(ctx.owner.pubkey(), ROOT_EXPIRY_CHANGE, ctx.payer());
+    ctx.send_as_owner(&[ix]).await.unwrap();
+    ctx.warp(CONFIG_CHANGE_DELAY).await;
+    let ix =
+        instructions::execute_config_change(ctx.owner.pubkey(), ROOT_EXPIRY_CHANGE, ctx.payer());
+    assert!(ctx.send_as_owner(&[ix]).await.is_err());
+    // The value can be proposed again once the pending change is cancelled.
+    propose(&mut ctx, ROOT_EXPIRY_CHANGE).await;
+}
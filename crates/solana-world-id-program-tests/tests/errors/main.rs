@@ -0,0 +1,170 @@
+//! Every `SolanaWorldIDProgramError` is returned by at least one test below, or documented as
+//! unreachable in [`COVERAGE`].
+
+mod admin;
+mod admin_set;
+mod clean_up_root;
+mod config_changes;
+mod migrate;
+mod pause;
+mod post_signatures;
+mod proofs;
+mod relayer_allowlist;
+mod revoke_root;
+mod roles;
+mod update_root_with_query;
+mod update_root_with_query_precompile;
+mod update_root_with_vaa;
+mod verifying_keys;
+
+use solana_world_id_client::error::SolanaWorldIDProgramError;
+
+// Tests are only referenced, so that removing one fails to compile.
+#[allow(dead_code)]
+enum Coverage {
+    /// A test which asserts the error.
+    Test(fn()),
+    /// Why no instruction can return the error.
+    Unreachable(&'static str),
+}
+
+macro_rules! coverage {
+    ($($error:ident => $coverage:expr,)*) => {
+        const COVERAGE: &[(SolanaWorldIDProgramError, Coverage)] =
+            &[$((SolanaWorldIDProgramError::$error, $coverage),)*];
+
+        /// Fails to compile when an error is missing from [`COVERAGE`].
+        #[allow(dead_code)]
+        fn exhaustive(error: SolanaWorldIDProgramError) {
+            match error {
+                $(SolanaWorldIDProgramError::$error => {})*
+            }
+        }
+    };
+}
+
+coverage! {
+    WriteAuthorityMismatch => Coverage::Test(post_signatures::rejects_append_by_non_initial_payer),
+    GuardianSetExpired => Coverage::Test(update_root_with_query::rejects_expired_guardian_set),
+    InvalidMessageHash => Coverage::Unreachable(
+        "defense-in-depth: MESSAGE_PREFIX followed by a keccak hash is always QUERY_MESSAGE_LEN",
+    ),
+    NoQuorum => Coverage::Test(update_root_with_query::rejects_no_quorum),
+    InvalidGuardianIndexNonIncreasing => Coverage::Test(update_root_with_query::rejects_duplicate_signatures),
+    InvalidGuardianIndexOutOfRange => Coverage::Test(update_root_with_query::rejects_guardian_index_out_of_range),
+    InvalidSignature => Coverage::Test(update_root_with_query::rejects_invalid_signature),
+    InvalidGuardianKeyRecovery => Coverage::Test(update_root_with_query::rejects_signature_of_another_message),
+    EmptyGuardianSignatures => Coverage::Test(post_signatures::rejects_empty_signatures),
+    FailedToParseResponse => Coverage::Test(update_root_with_query::rejects_unparseable_response),
+    InvalidNumberOfRequests => Coverage::Test(update_root_with_query::rejects_invalid_number_of_requests),
+    InvalidRequestChainId => Coverage::Test(update_root_with_query::rejects_invalid_request_chain_id),
+    InvalidRequestType => Coverage::Test(update_root_with_query::rejects_invalid_request_type),
+    InvalidRequestCallDataLength => Coverage::Test(update_root_with_query::rejects_invalid_request_call_data_length),
+    InvalidRequestContract => Coverage::Test(update_root_with_query::rejects_invalid_request_contract),
+    InvalidRequestSignature => Coverage::Test(update_root_with_query::rejects_invalid_request_signature),
+    InvalidNumberOfResponses => Coverage::Test(update_root_with_query::rejects_invalid_number_of_responses),
+    InvalidResponseChainId => Coverage::Test(update_root_with_query::rejects_invalid_response_chain_id),
+    StaleBlockNum => Coverage::Test(update_root_with_query::rejects_stale_block_number),
+    StaleBlockTime => Coverage::Test(update_root_with_query::rejects_stale_block_time),
+    InvalidResponseType => Coverage::Test(update_root_with_query::rejects_invalid_response_type),
+    InvalidResponseResultsLength => Coverage::Test(update_root_with_query::rejects_invalid_response_results_length),
+    InvalidResponseResultLength => Coverage::Test(update_root_with_query::rejects_invalid_response_result_length),
+    RootHashMismatch => Coverage::Test(update_root_with_query::rejects_root_hash_mismatch),
+    NoopExpiryUpdate => Coverage::Unreachable("deprecated, not returned by any instruction"),
+    RootUnexpired => Coverage::Test(clean_up_root::rejects_unexpired_root),
+    RootIsLatest => Coverage::Test(clean_up_root::rejects_latest_root),
+    VerificationTypeDisabled => Coverage::Test(update_root_with_query::rejects_disabled_verification_type),
+    InvalidRootSource => Coverage::Test(update_root_with_vaa::rejects_query_verification_type),
+    InvalidVaaEmitterChain => Coverage::Test(update_root_with_vaa::rejects_invalid_emitter_chain),
+    InvalidVaaEmitterAddress => Coverage::Test(update_root_with_vaa::rejects_invalid_emitter_address),
+    InvalidVaaPayload => Coverage::Test(update_root_with_vaa::rejects_invalid_payload),
+    StaleVaaSequence => Coverage::Test(update_root_with_vaa::rejects_stale_sequence),
+    InvalidSecp256k1Instruction => Coverage::Test(update_root_with_query_precompile::rejects_missing_secp256k1_instruction),
+    InvalidSecp256k1InstructionIndex => Coverage::Test(update_root_with_query_precompile::rejects_signatures_from_another_instruction),
+    InvalidSecp256k1Message => Coverage::Test(update_root_with_query_precompile::rejects_signatures_of_another_message),
+    UnknownGuardianKey => Coverage::Test(update_root_with_query_precompile::rejects_unknown_guardian_key),
+    RootUpdatesPaused => Coverage::Test(pause::rejects_paused_root_updates),
+    CleanupPaused => Coverage::Test(pause::rejects_paused_cleanup),
+    RelayerAllowlistNotProvided => Coverage::Test(relayer_allowlist::rejects_missing_relayer_allowlist),
+    RelayerNotAllowed => Coverage::Test(relayer_allowlist::rejects_relayer_not_allowed),
+    RootExpired => Coverage::Test(proofs::rejects_expired_root),
+    CreateGroth16VerifierFailed => Coverage::Unreachable(
+        "the proof and public inputs have fixed lengths and the inputs are range checked before Groth16Verifier::new",
+    ),
+    Groth16ProofVerificationFailed => Coverage::Test(proofs::rejects_invalid_proof),
+    NullifierAlreadyConsumed => Coverage::Test(proofs::rejects_consumed_nullifier),
+    EmptyProofBatch => Coverage::Test(proofs::rejects_empty_batch),
+    RootAccountNotProvided => Coverage::Test(proofs::rejects_missing_root_account),
+    RootVerificationTypeMismatch => Coverage::Test(proofs::rejects_root_of_another_verification_type),
+    DuplicateRootAccount => Coverage::Test(proofs::rejects_duplicate_root_account),
+    RootHashOutOfRange => Coverage::Test(proofs::rejects_root_hash_out_of_range),
+    SignalHashOutOfRange => Coverage::Test(proofs::rejects_signal_hash_out_of_range),
+    NullifierHashOutOfRange => Coverage::Test(proofs::rejects_nullifier_hash_out_of_range),
+    ExternalNullifierHashOutOfRange => Coverage::Test(proofs::rejects_external_nullifier_hash_out_of_range),
+    InvalidProofA => Coverage::Test(proofs::rejects_invalid_proof_a),
+    InvalidProofB => Coverage::Test(proofs::rejects_invalid_proof_b),
+    InvalidProofC => Coverage::Test(proofs::rejects_invalid_proof_c),
+    VerifyingKeyNotProvided => Coverage::Test(verifying_keys::rejects_missing_verifying_key),
+    VerifyingKeyMismatch => Coverage::Test(verifying_keys::rejects_key_of_another_verification_type),
+    RetiredVerifyingKey => Coverage::Test(verifying_keys::rejects_retired_verifying_key),
+    VerifyingKeyGracePeriodEnded => Coverage::Test(verifying_keys::rejects_previous_key_after_grace_period),
+    ProofVerificationPaused => Coverage::Test(pause::rejects_paused_proof_verification),
+    RootRevoked => Coverage::Test(revoke_root::rejects_revoked_root),
+    InvalidPendingOwner => Coverage::Test(admin::rejects_claim_by_other_signer),
+    InvalidVerifyingKey => Coverage::Test(verifying_keys::rejects_invalid_verifying_key),
+    NoPendingVerifyingKey => Coverage::Test(verifying_keys::rejects_activation_without_pending_key),
+    VerifyingKeyActivationDelayNotElapsed => Coverage::Test(verifying_keys::rejects_activation_before_delay),
+    InvalidPauser => Coverage::Test(pause::rejects_pause_by_other_signer),
+    InvalidPauseFlags => Coverage::Test(pause::rejects_invalid_pause_flags),
+    PreviousRootNotProvided => Coverage::Unreachable("deprecated, not returned by any instruction"),
+    InvalidPreviousRoot => Coverage::Test(revoke_root::rejects_revoked_root_as_its_own_previous_root),
+    ConfigValueOutOfBounds => Coverage::Test(config_changes::rejects_out_of_bounds_value),
+    ConfigChangeDelayNotElapsed => Coverage::Test(config_changes::rejects_execution_before_delay),
+    VerificationTypeConfigNotProvided => Coverage::Test(config_changes::rejects_missing_verification_type_config),
+    VerificationTypeConfigMismatch => Coverage::Test(config_changes::rejects_config_of_another_verification_type),
+    InvalidAdminSet => Coverage::Test(admin_set::rejects_invalid_admin_set),
+    AdminSetThresholdNotMet => Coverage::Test(admin_set::rejects_below_threshold),
+    AdminSetMemberNotFound => Coverage::Test(admin_set::rejects_removal_of_unknown_member),
+    InvalidConfigManager => Coverage::Test(roles::rejects_config_change_by_other_signer),
+    InvalidRootRevoker => Coverage::Test(roles::rejects_revoke_root_by_other_signer),
+    InvalidRelayerAllowlist => Coverage::Test(relayer_allowlist::rejects_duplicate_relayer),
+    RelayerNotFound => Coverage::Test(relayer_allowlist::rejects_removal_of_unknown_relayer),
+    PreviousRootNotOlder => Coverage::Test(revoke_root::rejects_newer_previous_root),
+    PreviousRootExpired => Coverage::Test(revoke_root::rejects_expired_previous_root),
+    ConfigAlreadyMigrated => Coverage::Test(migrate::rejects_migrated_config),
+    InvalidLegacyConfigOwner => Coverage::Test(migrate::rejects_legacy_config_of_other_owner),
+    RootAlreadyMigrated => Coverage::Test(migrate::rejects_migrated_root),
+    RootSourceChainMismatch => Coverage::Test(config_changes::rejects_root_source_of_another_chain),
+    OwnerOnlyConfigChange => Coverage::Test(roles::rejects_owner_only_config_change_by_config_manager),
+}
+
+#[test]
+fn every_error_is_covered() {
+    let codes: Vec<u32> = COVERAGE
+        .iter()
+        .map(|(error, _)| u32::from(*error))
+        .collect();
+    for (i, code) in codes.iter().enumerate() {
+        assert!(!codes[..i].contains(code), "error {code} is listed twice");
+    }
+}
+
+/// Codes from `InvalidRootSource` on are numbered contiguously within their section, so that a
+/// new code is always the next one of its section.
+#[test]
+fn codes_are_contiguous_from_invalid_root_source() {
+    let start = COVERAGE
+        .iter()
+        .position(|(error, _)| matches!(error, SolanaWorldIDProgramError::InvalidRootSource))
+        .unwrap();
+    let codes: Vec<u32> = COVERAGE[start..]
+        .iter()
+        .map(|(error, _)| u32::from(*error) - anchor_lang::error::ERROR_CODE_OFFSET)
+        .collect();
+    for pair in codes.windows(2) {
+        // 0x1xx, 0x2xx and 0x10xx are the root management, proof verification and admin sections.
+        if pair[0] >> 8 == pair[1] >> 8 {
+            assert_eq!(pair[1], pair[0] + 1, "gap after error {:#x}", pair[0]);
+        }
+    }
+}
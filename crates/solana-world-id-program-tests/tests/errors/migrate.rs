@@ -0,0 +1,145 @@
+use anchor_lang::{AnchorSerialize, Discriminator, Space};
+use solana_sdk::{
+    account::{Account, AccountSharedData},
+    signature::{Keypair, Signer},
+};
+use solana_world_id_client::{
+    error::SolanaWorldIDProgramError,
+    instructions,
+    pda::{derive_config_key, derive_root_key, derive_verification_type_config_key},
+    program::DEFAULT_CONFIG_CHANGE_DELAY,
+    state::{Config, LegacyConfig, Root, VerificationTypeConfig},
+    ID,
+};
+use solana_world_id_program_tests::{proof::valid_proof, *};
+
+/// Replaces the config with one in the layout before verification types, as found by an upgrade
+/// of a deployed program.
+async fn downgrade_config(ctx: &mut TestContext) {
+    let config: Config = ctx.account(derive_config_key()).await;
+    let legacy_config = LegacyConfig {
+        bump: config.bump,
+        owner: config.owner,
+        pending_owner: None,
+        root_expiry: ROOT_EXPIRY,
+        allowed_update_staleness: ALLOWED_UPDATE_STALENESS,
+    };
+    let mut data = Config::DISCRIMINATOR.to_vec();
+    legacy_config.serialize(&mut data).unwrap();
+    data.resize(8 + LegacyConfig::INIT_SPACE, 0);
+    let rent = ctx.context.banks_client.get_rent().await.unwrap();
+    let account = Account {
+        lamports: rent.minimum_balance(data.len()),
+        data,
+        owner: ID,
+        executable: false,
+        rent_epoch: 0,
+    };
+    ctx.context
+        .set_account(&derive_config_key(), &account.into());
+    remove_verification_type_config(ctx);
+}
+
+/// The config of [`QUERY_VERIFICATION_TYPE`] did not exist before verification types.
+fn remove_verification_type_config(ctx: &mut TestContext) {
+    ctx.context.set_account(
+        &derive_verification_type_config_key(QUERY_VERIFICATION_TYPE),
+        &AccountSharedData::default(),
+    );
+}
+
+/// Drops the trailing `revoked` flag of the root of [`valid_proof`], as on a root created before
+/// roots could be revoked.
+async fn downgrade_root(ctx: &mut TestContext) {
+    let address = derive_root_key(valid_proof().root_hash, QUERY_VERIFICATION_TYPE);
+    let mut account = ctx
+        .context
+        .banks_client
+        .get_account(address)
+        .await
+        .unwrap()
+        .unwrap();
+    account.data.pop();
+    ctx.context.set_account(&address, &account.into());
+}
+
+#[tokio::test]
+pub async fn migrates_legacy_config() {
+    let mut ctx = TestContext::start().await;
+    downgrade_config(&mut ctx).await;
+    let ix = instructions::migrate_config(ctx.payer(), ctx.owner.pubkey());
+    ctx.send_as_owner(&[ix]).await.unwrap();
+    let config: Config = ctx.account(derive_config_key()).await;
+    assert_eq!(config.owner, ctx.owner.pubkey());
+    assert_eq!(config.config_change_delay, DEFAULT_CONFIG_CHANGE_DELAY);
+    let verification_type_config: VerificationTypeConfig = ctx
+        .account(derive_verification_type_config_key(QUERY_VERIFICATION_TYPE))
+        .await;
+    assert_eq!(verification_type_config.root_expiry, ROOT_EXPIRY);
+    assert_eq!(
+        verification_type_config.allowed_update_staleness,
+        ALLOWED_UPDATE_STALENESS
+    );
+    assert_eq!(verification_type_config.source, eth_root_source());
+    ctx.update_root([1; 32]).await;
+}
+
+#[tokio::test]
+pub async fn rejects_migrated_config() {
+    let mut ctx = TestContext::start().await;
+    remove_verification_type_config(&mut ctx);
+    let ix = instructions::migrate_config(ctx.payer(), ctx.owner.pubkey());
+    assert_error(
+        ctx.send_as_owner(&[ix]).await,
+        SolanaWorldIDProgramError::ConfigAlreadyMigrated,
+    );
+}
+
+#[tokio::test]
+pub async fn rejects_legacy_config_of_other_owner() {
+    let mut ctx = TestContext::start().await;
+    downgrade_config(&mut ctx).await;
+    let other = Keypair::new();
+    let ix = instructions::migrate_config(ctx.payer(), other.pubkey());
+    assert_error(
+        ctx.send(&[ix], &[&other]).await,
+        SolanaWorldIDProgramError::InvalidLegacyConfigOwner,
+    );
+}
+
+#[tokio::test]
+pub async fn migrates_legacy_root() {
+    let mut ctx = TestContext::start().await;
+    ctx.update_root(valid_proof().root_hash).await;
+    downgrade_root(&mut ctx).await;
+    let ix = instructions::migrate_root(
+        ctx.payer(),
+        valid_proof().root_hash,
+        QUERY_VERIFICATION_TYPE,
+    );
+    ctx.send(&[ix], &[]).await.unwrap();
+    let root: Root = ctx
+        .account(derive_root_key(
+            valid_proof().root_hash,
+            QUERY_VERIFICATION_TYPE,
+        ))
+        .await;
+    assert!(!root.revoked);
+    let ix = instructions::verify_groth16_proof(QUERY_VERIFICATION_TYPE, None, valid_proof());
+    ctx.send(&[ix], &[]).await.unwrap();
+}
+
+#[tokio::test]
+pub async fn rejects_migrated_root() {
+    let mut ctx = TestContext::start().await;
+    ctx.update_root(valid_proof().root_hash).await;
+    let ix = instructions::migrate_root(
+        ctx.payer(),
+        valid_proof().root_hash,
+        QUERY_VERIFICATION_TYPE,
+    );
+    assert_error(
+        ctx.send(&[ix], &[]).await,
+        SolanaWorldIDProgramError::RootAlreadyMigrated,
+    );
+}
@@ -0,0 +1,104 @@
+use solana_sdk::signature::{Keypair, Signer};
+use solana_world_id_client::{
+    error::SolanaWorldIDProgramError,
+    instructions,
+    state::{Config, Role},
+};
+use solana_world_id_program_tests::{proof::valid_proof, *};
+
+const OTHER_ROOT: [u8; 32] = [2; 32];
+
+async fn pause(ctx: &mut TestContext, flags: u8) {
+    let ix = instructions::pause(ctx.owner.pubkey(), flags);
+    ctx.send_as_owner(&[ix]).await.unwrap();
+}
+
+#[tokio::test]
+pub async fn rejects_paused_root_updates() {
+    let mut ctx = TestContext::start().await;
+    pause(&mut ctx, Config::PAUSE_ROOT_UPDATES).await;
+    let response = ctx.mock_root_response(OTHER_ROOT).await;
+    assert_error(
+        ctx.update_root_with_query(&response, OTHER_ROOT, MOCK_GUARDIAN_SET_INDEX)
+            .await,
+        SolanaWorldIDProgramError::RootUpdatesPaused,
+    );
+}
+
+#[tokio::test]
+pub async fn rejects_paused_proof_verification() {
+    let mut ctx = TestContext::start().await;
+    ctx.update_root(valid_proof().root_hash).await;
+    pause(&mut ctx, Config::PAUSE_PROOF_VERIFICATION).await;
+    let ix = instructions::verify_groth16_proof(QUERY_VERIFICATION_TYPE, None, valid_proof());
+    assert_error(
+        ctx.send(&[ix], &[]).await,
+        SolanaWorldIDProgramError::ProofVerificationPaused,
+    );
+}
+
+#[tokio::test]
+pub async fn rejects_paused_cleanup() {
+    let mut ctx = TestContext::start().await;
+    ctx.update_root(valid_proof().root_hash).await;
+    ctx.update_root(OTHER_ROOT).await;
+    ctx.warp(ROOT_EXPIRY + 1).await;
+    pause(&mut ctx, Config::PAUSE_CLEANUP).await;
+    let ix = instructions::clean_up_root(
+        valid_proof().root_hash,
+        QUERY_VERIFICATION_TYPE,
+        ctx.payer(),
+    );
+    assert_error(
+        ctx.send(&[ix], &[]).await,
+        SolanaWorldIDProgramError::CleanupPaused,
+    );
+}
+
+#[tokio::test]
+pub async fn pauses_independently() {
+    let mut ctx = TestContext::start().await;
+    ctx.update_root(valid_proof().root_hash).await;
+    pause(&mut ctx, Config::PAUSE_ROOT_UPDATES | Config::PAUSE_CLEANUP).await;
+    let ix = instructions::verify_groth16_proof(QUERY_VERIFICATION_TYPE, None, valid_proof());
+    ctx.send(&[ix], &[]).await.unwrap();
+}
+
+#[tokio::test]
+pub async fn pauser_pauses_and_owner_unpauses() {
+    let mut ctx = TestContext::start().await;
+    ctx.update_root(valid_proof().root_hash).await;
+    let pauser = Keypair::new();
+    let ix = instructions::grant_role(ctx.owner.pubkey(), Role::Pauser, pauser.pubkey());
+    ctx.send_as_owner(&[ix]).await.unwrap();
+    let ix = instructions::pause(pauser.pubkey(), Config::PAUSE_PROOF_VERIFICATION);
+    ctx.send(&[ix], &[&pauser]).await.unwrap();
+    // The pauser cannot unpause.
+    let ix = instructions::unpause(pauser.pubkey(), Config::PAUSE_PROOF_VERIFICATION);
+    assert!(ctx.send(&[ix], &[&pauser]).await.is_err());
+    let ix = instructions::unpause(ctx.owner.pubkey(), Config::PAUSE_PROOF_VERIFICATION);
+    ctx.send_as_owner(&[ix]).await.unwrap();
+    let ix = instructions::verify_groth16_proof(QUERY_VERIFICATION_TYPE, None, valid_proof());
+    ctx.send(&[ix], &[]).await.unwrap();
+}
+
+#[tokio::test]
+pub async fn rejects_pause_by_other_signer() {
+    let mut ctx = TestContext::start().await;
+    let other = Keypair::new();
+    let ix = instructions::pause(other.pubkey(), Config::PAUSE_ALL);
+    assert_error(
+        ctx.send(&[ix], &[&other]).await,
+        SolanaWorldIDProgramError::InvalidPauser,
+    );
+}
+
+#[tokio::test]
+pub async fn rejects_invalid_pause_flags() {
+    let mut ctx = TestContext::start().await;
+    let ix = instructions::pause(ctx.owner.pubkey(), !Config::PAUSE_ALL);
+    assert_error(
+        ctx.send_as_owner(&[ix]).await,
+        SolanaWorldIDProgramError::InvalidPauseFlags,
+    );
+}
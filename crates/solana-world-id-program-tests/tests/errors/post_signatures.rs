@@ -0,0 +1,33 @@
+use solana_sdk::signature::{Keypair, Signer};
+use solana_world_id_client::{error::SolanaWorldIDProgramError, instructions};
+use solana_world_id_program_tests::*;
+
+#[tokio::test]
+pub async fn rejects_empty_signatures() {
+    let mut ctx = TestContext::start().await;
+    let guardian_signatures = Keypair::new();
+    let ix = instructions::post_signatures(ctx.payer(), guardian_signatures.pubkey(), vec![], 0);
+    assert_error(
+        ctx.send(&[ix], &[&guardian_signatures]).await,
+        SolanaWorldIDProgramError::EmptyGuardianSignatures,
+    );
+}
+
+#[tokio::test]
+pub async fn rejects_append_by_non_initial_payer() {
+    let mut ctx = TestContext::start().await;
+    let response = ctx.mock_root_response([1; 32]).await;
+    let guardian_signatures = ctx.post_signatures(response.signatures.clone()).await;
+    // Appending does not create the account, so the other payer needs no lamports.
+    let other = Keypair::new();
+    let ix = instructions::post_signatures(
+        other.pubkey(),
+        guardian_signatures.pubkey(),
+        response.signatures,
+        2,
+    );
+    assert_error(
+        ctx.send(&[ix], &[&other]).await,
+        SolanaWorldIDProgramError::WriteAuthorityMismatch,
+    );
+}
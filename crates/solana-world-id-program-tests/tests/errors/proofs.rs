@@ -0,0 +1,231 @@
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_world_id_client::{
+    error::SolanaWorldIDProgramError, instructions, pda::derive_root_key, BatchVerificationMode,
+    Groth16ProofArgs,
+};
+use solana_world_id_program_tests::{proof::valid_proof, *};
+
+const OTHER_ROOT: [u8; 32] = [2; 32];
+
+/// Starts with the root of [`valid_proof`] as the latest root.
+async fn start() -> TestContext {
+    let mut ctx = TestContext::start().await;
+    ctx.update_root(valid_proof().root_hash).await;
+    ctx
+}
+
+fn verify_proofs(proofs: Vec<Groth16ProofArgs>) -> Instruction {
+    instructions::verify_groth16_proofs(
+        QUERY_VERIFICATION_TYPE,
+        None,
+        proofs,
+        BatchVerificationMode::Atomic,
+    )
+}
+
+#[tokio::test]
+pub async fn verifies_proof() {
+    let mut ctx = start().await;
+    let ix = instructions::verify_groth16_proof(QUERY_VERIFICATION_TYPE, None, valid_proof());
+    ctx.send(&[ix], &[]).await.unwrap();
+}
+
+#[tokio::test]
+pub async fn rejects_expired_root() {
+    let mut ctx = start().await;
+    ctx.update_root(OTHER_ROOT).await;
+    ctx.warp(ROOT_EXPIRY + 1).await;
+    let ix = instructions::verify_groth16_proof(QUERY_VERIFICATION_TYPE, None, valid_proof());
+    assert_error(
+        ctx.send(&[ix], &[]).await,
+        SolanaWorldIDProgramError::RootExpired,
+    );
+}
+
+#[tokio::test]
+pub async fn rejects_invalid_proof() {
+    let mut ctx = start().await;
+    let args = Groth16ProofArgs {
+        signal_hash: [0; 32],
+        ..valid_proof()
+    };
+    let ix = instructions::verify_groth16_proof(QUERY_VERIFICATION_TYPE, None, args);
+    assert_error(
+        ctx.send(&[ix], &[]).await,
+        SolanaWorldIDProgramError::Groth16ProofVerificationFailed,
+    );
+}
+
+#[tokio::test]
+pub async fn rejects_consumed_nullifier() {
+    let mut ctx = start().await;
+    let ix = instructions::verify_and_consume_nullifier(
+        ctx.payer(),
+        QUERY_VERIFICATION_TYPE,
+        None,
+        valid_proof(),
+    );
+    ctx.send(&[ix.clone()], &[]).await.unwrap();
+    ctx.refresh_blockhash().await;
+    assert_error(
+        ctx.send(&[ix], &[]).await,
+        SolanaWorldIDProgramError::NullifierAlreadyConsumed,
+    );
+}
+
+#[tokio::test]
+pub async fn rejects_empty_batch() {
+    let mut ctx = start().await;
+    assert_error(
+        ctx.send(&[verify_proofs(vec![])], &[]).await,
+        SolanaWorldIDProgramError::EmptyProofBatch,
+    );
+}
+
+#[tokio::test]
+pub async fn rejects_missing_root_account() {
+    let mut ctx = start().await;
+    let mut ix = verify_proofs(vec![valid_proof()]);
+    ix.accounts.pop();
+    assert_error(
+        ctx.send(&[ix], &[]).await,
+        SolanaWorldIDProgramError::RootAccountNotProvided,
+    );
+}
+
+#[tokio::test]
+pub async fn rejects_duplicate_root_account() {
+    let mut ctx = start().await;
+    let mut ix = verify_proofs(vec![valid_proof()]);
+    let root = ix.accounts.last().unwrap().clone();
+    ix.accounts.push(root);
+    assert_error(
+        ctx.send(&[ix], &[]).await,
+        SolanaWorldIDProgramError::DuplicateRootAccount,
+    );
+}
+
+#[tokio::test]
+pub async fn rejects_root_of_another_verification_type() {
+    let mut ctx = start().await;
+    ctx.register_vaa_verification_type().await;
+    let root_hash = ctx.posted_vaa_root(POSTED_VAA_ROOT_0).await;
+    ctx.update_root_with_vaa(POSTED_VAA_ROOT_0, root_hash)
+        .await
+        .unwrap();
+    let mut ix = verify_proofs(vec![Groth16ProofArgs {
+        root_hash,
+        ..valid_proof()
+    }]);
+    *ix.accounts.last_mut().unwrap() =
+        AccountMeta::new_readonly(derive_root_key(root_hash, VAA_VERIFICATION_TYPE), false);
+    assert_error(
+        ctx.send(&[ix], &[]).await,
+        SolanaWorldIDProgramError::RootVerificationTypeMismatch,
+    );
+}
+
+/// Greater than the scalar field modulus.
+const OUT_OF_RANGE: [u8; 32] = [0xff; 32];
+
+/// A G1 point which is not on the curve, as y^2 != x^3 + 3.
+fn off_curve_g1_point() -> [u8; 64] {
+    let mut point = [0; 64];
+    point[31] = 1;
+    point[63] = 3;
+    point
+}
+
+async fn assert_proof_error(
+    ctx: &mut TestContext,
+    args: Groth16ProofArgs,
+    error: SolanaWorldIDProgramError,
+) {
+    let ix = instructions::verify_groth16_proof(QUERY_VERIFICATION_TYPE, None, args);
+    assert_error(ctx.send(&[ix], &[]).await, error);
+}
+
+#[tokio::test]
+pub async fn rejects_root_hash_out_of_range() {
+    let mut ctx = start().await;
+    ctx.update_root(OUT_OF_RANGE).await;
+    let args = Groth16ProofArgs {
+        root_hash: OUT_OF_RANGE,
+        ..valid_proof()
+    };
+    assert_proof_error(
+        &mut ctx,
+        args,
+        SolanaWorldIDProgramError::RootHashOutOfRange,
+    )
+    .await;
+}
+
+#[tokio::test]
+pub async fn rejects_signal_hash_out_of_range() {
+    let mut ctx = start().await;
+    let args = Groth16ProofArgs {
+        signal_hash: OUT_OF_RANGE,
+        ..valid_proof()
+    };
+    assert_proof_error(
+        &mut ctx,
+        args,
+        SolanaWorldIDProgramError::SignalHashOutOfRange,
+    )
+    .await;
+}
+
+#[tokio::test]
+pub async fn rejects_nullifier_hash_out_of_range() {
+    let mut ctx = start().await;
+    let args = Groth16ProofArgs {
+        nullifier_hash: OUT_OF_RANGE,
+        ..valid_proof()
+    };
+    assert_proof_error(
+        &mut ctx,
+        args,
+        SolanaWorldIDProgramError::NullifierHashOutOfRange,
+    )
+    .await;
+}
+
+#[tokio::test]
+pub async fn rejects_external_nullifier_hash_out_of_range() {
+    let mut ctx = start().await;
+    let args = Groth16ProofArgs {
+        external_nullifier_hash: OUT_OF_RANGE,
+        ..valid_proof()
+    };
+    assert_proof_error(
+        &mut ctx,
+        args,
+        SolanaWorldIDProgramError::ExternalNullifierHashOutOfRange,
+    )
+    .await;
+}
+
+#[tokio::test]
+pub async fn rejects_invalid_proof_a() {
+    let mut ctx = start().await;
+    let mut args = valid_proof();
+    args.proof[0..64].copy_from_slice(&off_curve_g1_point());
+    assert_proof_error(&mut ctx, args, SolanaWorldIDProgramError::InvalidProofA).await;
+}
+
+#[tokio::test]
+pub async fn rejects_invalid_proof_b() {
+    let mut ctx = start().await;
+    let mut args = valid_proof();
+    args.proof[127] ^= 1;
+    assert_proof_error(&mut ctx, args, SolanaWorldIDProgramError::InvalidProofB).await;
+}
+
+#[tokio::test]
+pub async fn rejects_invalid_proof_c() {
+    let mut ctx = start().await;
+    let mut args = valid_proof();
+    args.proof[192..256].copy_from_slice(&off_curve_g1_point());
+    assert_proof_error(&mut ctx, args, SolanaWorldIDProgramError::InvalidProofC).await;
+}
@@ -0,0 +1,122 @@
+use solana_sdk::{
+    instruction::Instruction,
+    signature::{Keypair, Signer},
+};
+use solana_world_id_client::{error::SolanaWorldIDProgramError, instructions};
+use solana_world_id_program_tests::{secp256k1::secp256k1_instruction, *};
+
+const ROOT: [u8; 32] = [1; 32];
+
+/// Starts with the relayer allowlist enabled, listing the payer when `allow_payer` is set and only
+/// another relayer otherwise.
+async fn start(allow_payer: bool) -> TestContext {
+    let mut ctx = TestContext::start().await;
+    let relayer = if allow_payer {
+        ctx.payer()
+    } else {
+        Keypair::new().pubkey()
+    };
+    let ix =
+        instructions::initialize_relayer_allowlist(ctx.payer(), ctx.owner.pubkey(), vec![relayer]);
+    ctx.send_as_owner(&[ix]).await.unwrap();
+    let ix = instructions::set_relayer_allowlist_enabled(ctx.owner.pubkey(), true);
+    ctx.send_as_owner(&[ix]).await.unwrap();
+    ctx
+}
+
+/// Posts the signatures of a new root and returns the instruction which updates it, paid for by
+/// the payer.
+async fn update_root(ctx: &mut TestContext, relayer_allowlist: bool) -> Instruction {
+    let response = ctx.mock_root_response(ROOT).await;
+    let guardian_signatures = ctx.post_signatures(response.signatures).await;
+    instructions::update_root_with_query(
+        ctx.payer(),
+        relayer_allowlist,
+        guardian_signatures.pubkey(),
+        ctx.payer(),
+        response.bytes,
+        ROOT,
+        QUERY_VERIFICATION_TYPE,
+        MOCK_GUARDIAN_SET_INDEX,
+    )
+}
+
+#[tokio::test]
+pub async fn updates_root_by_allowed_relayer() {
+    let mut ctx = start(true).await;
+    let ix = update_root(&mut ctx, true).await;
+    ctx.send(&[ix], &[]).await.unwrap();
+}
+
+#[tokio::test]
+pub async fn rejects_relayer_not_allowed() {
+    let mut ctx = start(false).await;
+    let ix = update_root(&mut ctx, true).await;
+    assert_error(
+        ctx.send(&[ix], &[]).await,
+        SolanaWorldIDProgramError::RelayerNotAllowed,
+    );
+}
+
+#[tokio::test]
+pub async fn rejects_precompile_relayer_not_allowed() {
+    let mut ctx = start(false).await;
+    let response = ctx.mock_root_response(ROOT).await;
+    let secp256k1_ix = secp256k1_instruction(
+        &response.signatures,
+        &ctx.proxy.guardians.addresses(),
+        &response.bytes,
+        0,
+    );
+    let ix = instructions::update_root_with_query_precompile(
+        ctx.payer(),
+        true,
+        response.bytes,
+        ROOT,
+        QUERY_VERIFICATION_TYPE,
+        MOCK_GUARDIAN_SET_INDEX,
+    );
+    assert_error(
+        ctx.send(&[secp256k1_ix, ix], &[]).await,
+        SolanaWorldIDProgramError::RelayerNotAllowed,
+    );
+}
+
+#[tokio::test]
+pub async fn rejects_missing_relayer_allowlist() {
+    let mut ctx = start(true).await;
+    let ix = update_root(&mut ctx, false).await;
+    assert_error(
+        ctx.send(&[ix], &[]).await,
+        SolanaWorldIDProgramError::RelayerAllowlistNotProvided,
+    );
+}
+
+#[tokio::test]
+pub async fn updates_root_while_disabled() {
+    let mut ctx = start(false).await;
+    let ix = instructions::set_relayer_allowlist_enabled(ctx.owner.pubkey(), false);
+    ctx.send_as_owner(&[ix]).await.unwrap();
+    let ix = update_root(&mut ctx, false).await;
+    ctx.send(&[ix], &[]).await.unwrap();
+}
+
+#[tokio::test]
+pub async fn rejects_duplicate_relayer() {
+    let mut ctx = start(true).await;
+    let ix = instructions::add_relayer(ctx.owner.pubkey(), ctx.payer());
+    assert_error(
+        ctx.send_as_owner(&[ix]).await,
+        SolanaWorldIDProgramError::InvalidRelayerAllowlist,
+    );
+}
+
+#[tokio::test]
+pub async fn rejects_removal_of_unknown_relayer() {
+    let mut ctx = start(true).await;
+    let ix = instructions::remove_relayer(ctx.owner.pubkey(), Keypair::new().pubkey());
+    assert_error(
+        ctx.send_as_owner(&[ix]).await,
+        SolanaWorldIDProgramError::RelayerNotFound,
+    );
+}
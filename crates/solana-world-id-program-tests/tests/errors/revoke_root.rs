@@ -0,0 +1,154 @@
+use solana_sdk::signature::Signer;
+use solana_world_id_client::{
+    error::SolanaWorldIDProgramError, instructions, pda::derive_latest_root_key, state::LatestRoot,
+};
+use solana_world_id_program_tests::{proof::valid_proof, *};
+
+const OTHER_ROOT: [u8; 32] = [2; 32];
+
+/// Starts with the root of [`valid_proof`] followed by [`OTHER_ROOT`] as the latest root.
+async fn start() -> TestContext {
+    let mut ctx = TestContext::start().await;
+    ctx.update_root(valid_proof().root_hash).await;
+    ctx.update_root(OTHER_ROOT).await;
+    ctx
+}
+
+#[tokio::test]
+pub async fn rejects_revoked_root() {
+    let mut ctx = start().await;
+    let ix = instructions::revoke_root(
+        ctx.owner.pubkey(),
+        valid_proof().root_hash,
+        QUERY_VERIFICATION_TYPE,
+        None,
+    );
+    ctx.send_as_owner(&[ix]).await.unwrap();
+    let ix = instructions::verify_groth16_proof(QUERY_VERIFICATION_TYPE, None, valid_proof());
+    assert_error(
+        ctx.send(&[ix], &[]).await,
+        SolanaWorldIDProgramError::RootRevoked,
+    );
+}
+
+#[tokio::test]
+pub async fn cleans_up_revoked_root() {
+    let mut ctx = start().await;
+    let ix = instructions::revoke_root(
+        ctx.owner.pubkey(),
+        valid_proof().root_hash,
+        QUERY_VERIFICATION_TYPE,
+        None,
+    );
+    ctx.send_as_owner(&[ix]).await.unwrap();
+    let ix = instructions::clean_up_root(
+        valid_proof().root_hash,
+        QUERY_VERIFICATION_TYPE,
+        ctx.payer(),
+    );
+    ctx.send(&[ix], &[]).await.unwrap();
+}
+
+#[tokio::test]
+pub async fn rolls_back_latest_root() {
+    let mut ctx = start().await;
+    let ix = instructions::revoke_root(
+        ctx.owner.pubkey(),
+        OTHER_ROOT,
+        QUERY_VERIFICATION_TYPE,
+        Some(valid_proof().root_hash),
+    );
+    ctx.send_as_owner(&[ix]).await.unwrap();
+    let latest_root: LatestRoot = ctx
+        .account(derive_latest_root_key(QUERY_VERIFICATION_TYPE))
+        .await;
+    assert_eq!(latest_root.root, valid_proof().root_hash);
+    // The previous root is valid as the latest root, even once expired.
+    ctx.warp(ROOT_EXPIRY + 1).await;
+    let ix = instructions::verify_groth16_proof(QUERY_VERIFICATION_TYPE, None, valid_proof());
+    ctx.send(&[ix], &[]).await.unwrap();
+}
+
+#[tokio::test]
+pub async fn revokes_latest_root_without_previous_root() {
+    let mut ctx = TestContext::start().await;
+    ctx.update_root(valid_proof().root_hash).await;
+    let ix = instructions::revoke_root(
+        ctx.owner.pubkey(),
+        valid_proof().root_hash,
+        QUERY_VERIFICATION_TYPE,
+        None,
+    );
+    ctx.send_as_owner(&[ix]).await.unwrap();
+    let latest_root: LatestRoot = ctx
+        .account(derive_latest_root_key(QUERY_VERIFICATION_TYPE))
+        .await;
+    assert_eq!(latest_root.root, valid_proof().root_hash);
+    // The latest root is rejected once revoked.
+    let ix = instructions::verify_groth16_proof(QUERY_VERIFICATION_TYPE, None, valid_proof());
+    assert_error(
+        ctx.send(&[ix], &[]).await,
+        SolanaWorldIDProgramError::RootRevoked,
+    );
+    // A newer root replaces it as usual.
+    ctx.update_root(OTHER_ROOT).await;
+    let latest_root: LatestRoot = ctx
+        .account(derive_latest_root_key(QUERY_VERIFICATION_TYPE))
+        .await;
+    assert_eq!(latest_root.root, OTHER_ROOT);
+}
+
+#[tokio::test]
+pub async fn rejects_revoked_root_as_its_own_previous_root() {
+    let mut ctx = start().await;
+    let ix = instructions::revoke_root(
+        ctx.owner.pubkey(),
+        OTHER_ROOT,
+        QUERY_VERIFICATION_TYPE,
+        Some(OTHER_ROOT),
+    );
+    assert_error(
+        ctx.send_as_owner(&[ix]).await,
+        SolanaWorldIDProgramError::InvalidPreviousRoot,
+    );
+}
+
+#[tokio::test]
+pub async fn rejects_newer_previous_root() {
+    let mut ctx = start().await;
+    ctx.update_root([3; 32]).await;
+    let ix = instructions::revoke_root(
+        ctx.owner.pubkey(),
+        [3; 32],
+        QUERY_VERIFICATION_TYPE,
+        Some(valid_proof().root_hash),
+    );
+    ctx.send_as_owner(&[ix]).await.unwrap();
+    // OTHER_ROOT was read after the rolled back latest root.
+    let ix = instructions::revoke_root(
+        ctx.owner.pubkey(),
+        valid_proof().root_hash,
+        QUERY_VERIFICATION_TYPE,
+        Some(OTHER_ROOT),
+    );
+    assert_error(
+        ctx.send_as_owner(&[ix]).await,
+        SolanaWorldIDProgramError::PreviousRootNotOlder,
+    );
+}
+
+#[tokio::test]
+pub async fn rejects_expired_previous_root() {
+    let mut ctx = start().await;
+    ctx.warp(ROOT_EXPIRY + 1).await;
+    let ix = instructions::revoke_root(
+        ctx.owner.pubkey(),
+        OTHER_ROOT,
+        QUERY_VERIFICATION_TYPE,
+        Some(valid_proof().root_hash),
+    );
+    assert_error(
+        ctx.send_as_owner(&[ix]).await,
+        SolanaWorldIDProgramError::PreviousRootExpired,
+    );
+}
@@ -0,0 +1,152 @@
+use solana_sdk::signature::{Keypair, Signer};
+use solana_world_id_client::{
+    error::SolanaWorldIDProgramError,
+    instructions,
+    pda::{derive_config_key, derive_verification_type_config_key},
+    program::{ETH_CHAIN_ID, LATEST_ROOT_SIGNATURE},
+    state::{Config, ConfigChange, Role, RootSource, VerificationTypeConfig},
+};
+use solana_world_id_program_tests::{proof::valid_proof, *};
+
+const ROOT_EXPIRY_CHANGE: ConfigChange = ConfigChange::RootExpiry {
+    verification_type: QUERY_VERIFICATION_TYPE,
+    root_expiry: 60 * 60,
+};
+
+async fn grant_role(ctx: &mut TestContext, role: Role) -> Keypair {
+    let grantee = Keypair::new();
+    let ix = instructions::grant_role(ctx.owner.pubkey(), role, grantee.pubkey());
+    ctx.send_as_owner(&[ix]).await.unwrap();
+    grantee
+}
+
+#[tokio::test]
+pub async fn config_manager_changes_config() {
+    let mut ctx = TestContext::start().await;
+    let config_manager = grant_role(&mut ctx, Role::ConfigManager).await;
+    let ix = instructions::propose_config_change(
+        ctx.payer(),
+        config_manager.pubkey(),
+        ROOT_EXPIRY_CHANGE,
+    );
+    ctx.send(&[ix], &[&config_manager]).await.unwrap();
+    ctx.warp(CONFIG_CHANGE_DELAY).await;
+    let ix = instructions::execute_config_change(
+        config_manager.pubkey(),
+        ROOT_EXPIRY_CHANGE,
+        ctx.payer(),
+    );
+    ctx.send(&[ix], &[&config_manager]).await.unwrap();
+    let verification_type_config: VerificationTypeConfig = ctx
+        .account(derive_verification_type_config_key(QUERY_VERIFICATION_TYPE))
+        .await;
+    assert_eq!(verification_type_config.root_expiry, 60 * 60);
+    // Owner-only instructions stay with the owner.
+    let ix = instructions::transfer_ownership(config_manager.pubkey(), config_manager.pubkey());
+    assert!(ctx.send(&[ix], &[&config_manager]).await.is_err());
+}
+
+#[tokio::test]
+pub async fn rejects_owner_only_config_change_by_config_manager() {
+    let mut ctx = TestContext::start().await;
+    let config_manager = grant_role(&mut ctx, Role::ConfigManager).await;
+    for change in [
+        ConfigChange::RootSource {
+            verification_type: QUERY_VERIFICATION_TYPE,
+            source: RootSource::Query {
+                chain_id: ETH_CHAIN_ID,
+                contract: [0xaa; 20],
+                selector: LATEST_ROOT_SIGNATURE,
+            },
+        },
+        ConfigChange::ConfigChangeDelay {
+            config_change_delay: 0,
+        },
+        ConfigChange::VerifyingKeyActivationDelay {
+            verifying_key_activation_delay: 0,
+        },
+    ] {
+        let ix = instructions::propose_config_change(ctx.payer(), config_manager.pubkey(), change);
+        assert_error(
+            ctx.send(&[ix], &[&config_manager]).await,
+            SolanaWorldIDProgramError::OwnerOnlyConfigChange,
+        );
+        // Nor may it execute or cancel one proposed by the owner.
+        let ix = instructions::propose_config_change(ctx.payer(), ctx.owner.pubkey(), change);
+        ctx.send_as_owner(&[ix]).await.unwrap();
+        ctx.warp(CONFIG_CHANGE_DELAY).await;
+        let ix = instructions::execute_config_change(config_manager.pubkey(), change, ctx.payer());
+        assert_error(
+            ctx.send(&[ix], &[&config_manager]).await,
+            SolanaWorldIDProgramError::OwnerOnlyConfigChange,
+        );
+        let ix = instructions::cancel_config_change(config_manager.pubkey(), change, ctx.payer());
+        assert_error(
+            ctx.send(&[ix], &[&config_manager]).await,
+            SolanaWorldIDProgramError::OwnerOnlyConfigChange,
+        );
+    }
+}
+
+#[tokio::test]
+pub async fn rejects_config_change_by_other_signer() {
+    let mut ctx = TestContext::start().await;
+    // Other roles do not manage the config.
+    let pauser = grant_role(&mut ctx, Role::Pauser).await;
+    let ix = instructions::propose_config_change(ctx.payer(), pauser.pubkey(), ROOT_EXPIRY_CHANGE);
+    assert_error(
+        ctx.send(&[ix], &[&pauser]).await,
+        SolanaWorldIDProgramError::InvalidConfigManager,
+    );
+}
+
+#[tokio::test]
+pub async fn root_revoker_revokes_root() {
+    let mut ctx = TestContext::start().await;
+    ctx.update_root(valid_proof().root_hash).await;
+    let root_revoker = grant_role(&mut ctx, Role::RootRevoker).await;
+    let ix = instructions::revoke_root(
+        root_revoker.pubkey(),
+        valid_proof().root_hash,
+        QUERY_VERIFICATION_TYPE,
+        None,
+    );
+    ctx.send(&[ix], &[&root_revoker]).await.unwrap();
+    let ix = instructions::verify_groth16_proof(QUERY_VERIFICATION_TYPE, None, valid_proof());
+    assert_error(
+        ctx.send(&[ix], &[]).await,
+        SolanaWorldIDProgramError::RootRevoked,
+    );
+}
+
+#[tokio::test]
+pub async fn rejects_revoke_root_by_other_signer() {
+    let mut ctx = TestContext::start().await;
+    ctx.update_root(valid_proof().root_hash).await;
+    let config_manager = grant_role(&mut ctx, Role::ConfigManager).await;
+    let ix = instructions::revoke_root(
+        config_manager.pubkey(),
+        valid_proof().root_hash,
+        QUERY_VERIFICATION_TYPE,
+        None,
+    );
+    assert_error(
+        ctx.send(&[ix], &[&config_manager]).await,
+        SolanaWorldIDProgramError::InvalidRootRevoker,
+    );
+}
+
+#[tokio::test]
+pub async fn revokes_role() {
+    let mut ctx = TestContext::start().await;
+    let pauser = grant_role(&mut ctx, Role::Pauser).await;
+    let ix = instructions::revoke_role(ctx.owner.pubkey(), Role::Pauser);
+    ctx.send_as_owner(&[ix]).await.unwrap();
+    let config: Config = ctx.account(derive_config_key()).await;
+    assert_eq!(config.pauser, None);
+    let ix = instructions::pause(pauser.pubkey(), Config::PAUSE_ALL);
+    assert_error(
+        ctx.send(&[ix], &[&pauser]).await,
+        SolanaWorldIDProgramError::InvalidPauser,
+    );
+}
@@ -0,0 +1,426 @@
+use solana_sdk::signature::Signer;
+use solana_world_id_client::{
+    error::SolanaWorldIDProgramError,
+    instructions,
+    pda::derive_latest_root_key,
+    program::{ETH_CHAIN_ID, ETH_WORLD_ID_IDENTITY_MANAGER, LATEST_ROOT_SIGNATURE},
+    query::{block_tag, serialize_eth_call_query_request, EthCallData, QUERY_REQUEST_VERSION},
+    state::LatestRoot,
+};
+use solana_world_id_program_tests::*;
+use wormhole_query_mock::{MockQueryResponse, PerChainResponse};
+
+const ROOT: [u8; 32] = [1; 32];
+const OTHER_ROOT: [u8; 32] = [2; 32];
+const OTHER_CHAIN_ID: u16 = 4;
+const ETH_CALL_WITH_FINALITY_QUERY_TYPE: u8 = 3;
+
+fn latest_root_call() -> EthCallData {
+    EthCallData {
+        to: ETH_WORLD_ID_IDENTITY_MANAGER,
+        data: LATEST_ROOT_SIGNATURE.to_vec(),
+    }
+}
+
+/// A QueryRequest with `call_data` on `chain_id` at the next block, at which the root is [`ROOT`].
+async fn root_request(ctx: &mut TestContext, chain_id: u16, call_data: &[EthCallData]) -> Vec<u8> {
+    let block_number = ctx.next_block(ROOT).await;
+    let nonce = ctx.next_nonce();
+    serialize_eth_call_query_request(nonce, chain_id, &block_tag(block_number), call_data)
+}
+
+/// An EthCall response from `chain_id` at the latest block.
+fn eth_call(ctx: &TestContext, chain_id: u16, results: Vec<Vec<u8>>) -> PerChainResponse {
+    PerChainResponse::eth_call(chain_id, ctx.eth_call_response(results))
+}
+
+/// Signs a response to a QueryRequest with `call_data` on `chain_id`, which returns [`ROOT`] for
+/// each call.
+async fn respond_to_calls(
+    ctx: &mut TestContext,
+    chain_id: u16,
+    call_data: &[EthCallData],
+) -> MockQueryResponse {
+    let request = root_request(ctx, chain_id, call_data).await;
+    let results = call_data.iter().map(|_| ROOT.to_vec()).collect();
+    let response = eth_call(ctx, chain_id, results);
+    ctx.proxy.respond(&request, &[response])
+}
+
+/// Signs `responses` to a valid root query for the next block.
+async fn respond_to_root_request(
+    ctx: &mut TestContext,
+    responses: impl FnOnce(&TestContext) -> Vec<PerChainResponse>,
+) -> MockQueryResponse {
+    let request = root_request(ctx, ETH_CHAIN_ID, &[latest_root_call()]).await;
+    let responses = responses(ctx);
+    ctx.proxy.respond(&request, &responses)
+}
+
+#[tokio::test]
+pub async fn updates_root() {
+    let mut ctx = TestContext::start().await;
+    let response = ctx.mock_root_response(ROOT).await;
+    ctx.update_root_with_query(&response, ROOT, MOCK_GUARDIAN_SET_INDEX)
+        .await
+        .unwrap();
+    let latest_root: LatestRoot = ctx
+        .account(derive_latest_root_key(QUERY_VERIFICATION_TYPE))
+        .await;
+    assert_eq!(latest_root.root, ROOT);
+}
+
+#[tokio::test]
+pub async fn rejects_expired_guardian_set() {
+    let mut ctx = TestContext::start().await;
+    let response = ctx.mock_root_response(ROOT).await;
+    assert_error(
+        ctx.update_root_with_query(&response, ROOT, EXPIRED_MOCK_GUARDIAN_SET_INDEX)
+            .await,
+        SolanaWorldIDProgramError::GuardianSetExpired,
+    );
+}
+
+#[tokio::test]
+pub async fn rejects_no_quorum() {
+    let mut ctx = TestContext::start().await;
+    let response = ctx.mock_root_response(ROOT).await;
+    assert_error(
+        ctx.update_root_with_query(&response, ROOT, NO_QUORUM_MOCK_GUARDIAN_SET_INDEX)
+            .await,
+        SolanaWorldIDProgramError::NoQuorum,
+    );
+}
+
+#[tokio::test]
+pub async fn rejects_duplicate_signatures() {
+    let mut ctx = TestContext::start().await;
+    let mut response = ctx.mock_root_response(ROOT).await;
+    response.signatures = ctx.proxy.guardians.sign(&response.bytes, &[0, 0]);
+    assert_error(
+        ctx.update_root_with_query(&response, ROOT, MOCK_GUARDIAN_SET_INDEX)
+            .await,
+        SolanaWorldIDProgramError::InvalidGuardianIndexNonIncreasing,
+    );
+}
+
+#[tokio::test]
+pub async fn rejects_guardian_index_out_of_range() {
+    let mut ctx = TestContext::start().await;
+    let mut response = ctx.mock_root_response(ROOT).await;
+    response.signatures[0][0] = 1;
+    assert_error(
+        ctx.update_root_with_query(&response, ROOT, MOCK_GUARDIAN_SET_INDEX)
+            .await,
+        SolanaWorldIDProgramError::InvalidGuardianIndexOutOfRange,
+    );
+}
+
+#[tokio::test]
+pub async fn rejects_invalid_signature() {
+    let mut ctx = TestContext::start().await;
+    let mut response = ctx.mock_root_response(ROOT).await;
+    // Recovery ids are at most 3.
+    response.signatures[0][65] = 4;
+    assert_error(
+        ctx.update_root_with_query(&response, ROOT, MOCK_GUARDIAN_SET_INDEX)
+            .await,
+        SolanaWorldIDProgramError::InvalidSignature,
+    );
+}
+
+#[tokio::test]
+pub async fn rejects_signature_of_another_message() {
+    let mut ctx = TestContext::start().await;
+    let mut response = ctx.mock_root_response(ROOT).await;
+    response.signatures = ctx.proxy.guardians.sign(b"another message", &[0]);
+    assert_error(
+        ctx.update_root_with_query(&response, ROOT, MOCK_GUARDIAN_SET_INDEX)
+            .await,
+        SolanaWorldIDProgramError::InvalidGuardianKeyRecovery,
+    );
+}
+
+#[tokio::test]
+pub async fn rejects_unparseable_response() {
+    let mut ctx = TestContext::start().await;
+    let bytes = vec![0xff; 32];
+    let response = MockQueryResponse {
+        signatures: ctx.proxy.guardians.sign_quorum(&bytes),
+        bytes,
+    };
+    assert_error(
+        ctx.update_root_with_query(&response, ROOT, MOCK_GUARDIAN_SET_INDEX)
+            .await,
+        SolanaWorldIDProgramError::FailedToParseResponse,
+    );
+}
+
+#[tokio::test]
+pub async fn rejects_invalid_number_of_requests() {
+    let mut ctx = TestContext::start().await;
+    let request = root_request(&mut ctx, ETH_CHAIN_ID, &[latest_root_call()]).await;
+    // Repeat the per chain query after the version, nonce and number of queries.
+    let mut two_requests = request[..5].to_vec();
+    two_requests.push(2);
+    two_requests.extend_from_slice(&request[6..]);
+    two_requests.extend_from_slice(&request[6..]);
+    let response = eth_call(&ctx, ETH_CHAIN_ID, vec![ROOT.to_vec()]);
+    let response = ctx
+        .proxy
+        .respond(&two_requests, &[response.clone(), response]);
+    assert_error(
+        ctx.update_root_with_query(&response, ROOT, MOCK_GUARDIAN_SET_INDEX)
+            .await,
+        SolanaWorldIDProgramError::InvalidNumberOfRequests,
+    );
+}
+
+#[tokio::test]
+pub async fn rejects_disabled_verification_type() {
+    let mut ctx = TestContext::start().await;
+    let ix = instructions::set_verification_type_enabled(
+        ctx.owner.pubkey(),
+        QUERY_VERIFICATION_TYPE,
+        false,
+    );
+    ctx.send_as_owner(&[ix]).await.unwrap();
+    let response = ctx.mock_root_response(ROOT).await;
+    assert_error(
+        ctx.update_root_with_query(&response, ROOT, MOCK_GUARDIAN_SET_INDEX)
+            .await,
+        SolanaWorldIDProgramError::VerificationTypeDisabled,
+    );
+}
+
+#[tokio::test]
+pub async fn rejects_vaa_verification_type() {
+    let mut ctx = TestContext::start().await;
+    ctx.register_vaa_verification_type().await;
+    let response = ctx.mock_root_response(ROOT).await;
+    let guardian_signatures = ctx.post_signatures(response.signatures).await;
+    let ix = instructions::update_root_with_query(
+        ctx.payer(),
+        false,
+        guardian_signatures.pubkey(),
+        ctx.payer(),
+        response.bytes,
+        ROOT,
+        VAA_VERIFICATION_TYPE,
+        MOCK_GUARDIAN_SET_INDEX,
+    );
+    assert_error(
+        ctx.send(&[ix], &[]).await,
+        SolanaWorldIDProgramError::InvalidRootSource,
+    );
+}
+
+#[tokio::test]
+pub async fn rejects_invalid_request_chain_id() {
+    let mut ctx = TestContext::start().await;
+    let response = respond_to_calls(&mut ctx, OTHER_CHAIN_ID, &[latest_root_call()]).await;
+    assert_error(
+        ctx.update_root_with_query(&response, ROOT, MOCK_GUARDIAN_SET_INDEX)
+            .await,
+        SolanaWorldIDProgramError::InvalidRequestChainId,
+    );
+}
+
+#[tokio::test]
+pub async fn rejects_invalid_request_type() {
+    let mut ctx = TestContext::start().await;
+    let block_number = ctx.next_block(ROOT).await;
+    // An EthCallWithFinality query, which has a finality after the block id of an EthCall query.
+    let block_id = block_tag(block_number);
+    let finality = "finalized";
+    let call = latest_root_call();
+    let mut query = Vec::new();
+    query.extend_from_slice(&(block_id.len() as u32).to_be_bytes());
+    query.extend_from_slice(block_id.as_bytes());
+    query.extend_from_slice(&(finality.len() as u32).to_be_bytes());
+    query.extend_from_slice(finality.as_bytes());
+    query.push(1);
+    query.extend_from_slice(&call.to);
+    query.extend_from_slice(&(call.data.len() as u32).to_be_bytes());
+    query.extend_from_slice(&call.data);
+    let mut request = vec![QUERY_REQUEST_VERSION];
+    request.extend_from_slice(&ctx.next_nonce().to_be_bytes());
+    request.push(1);
+    request.extend_from_slice(&ETH_CHAIN_ID.to_be_bytes());
+    request.push(ETH_CALL_WITH_FINALITY_QUERY_TYPE);
+    request.extend_from_slice(&(query.len() as u32).to_be_bytes());
+    request.extend_from_slice(&query);
+    // EthCallWithFinality responses are serialized like EthCall responses.
+    let response = PerChainResponse {
+        query_type: ETH_CALL_WITH_FINALITY_QUERY_TYPE,
+        ..eth_call(&ctx, ETH_CHAIN_ID, vec![ROOT.to_vec()])
+    };
+    let response = ctx.proxy.respond(&request, &[response]);
+    assert_error(
+        ctx.update_root_with_query(&response, ROOT, MOCK_GUARDIAN_SET_INDEX)
+            .await,
+        SolanaWorldIDProgramError::InvalidRequestType,
+    );
+}
+
+#[tokio::test]
+pub async fn rejects_invalid_request_call_data_length() {
+    let mut ctx = TestContext::start().await;
+    let response = respond_to_calls(
+        &mut ctx,
+        ETH_CHAIN_ID,
+        &[latest_root_call(), latest_root_call()],
+    )
+    .await;
+    assert_error(
+        ctx.update_root_with_query(&response, ROOT, MOCK_GUARDIAN_SET_INDEX)
+            .await,
+        SolanaWorldIDProgramError::InvalidRequestCallDataLength,
+    );
+}
+
+#[tokio::test]
+pub async fn rejects_invalid_request_contract() {
+    let mut ctx = TestContext::start().await;
+    let call = EthCallData {
+        to: [0xaa; 20],
+        ..latest_root_call()
+    };
+    let response = respond_to_calls(&mut ctx, ETH_CHAIN_ID, &[call]).await;
+    assert_error(
+        ctx.update_root_with_query(&response, ROOT, MOCK_GUARDIAN_SET_INDEX)
+            .await,
+        SolanaWorldIDProgramError::InvalidRequestContract,
+    );
+}
+
+#[tokio::test]
+pub async fn rejects_invalid_request_signature() {
+    let mut ctx = TestContext::start().await;
+    let call = EthCallData {
+        data: vec![0xde, 0xad, 0xbe, 0xef],
+        ..latest_root_call()
+    };
+    let response = respond_to_calls(&mut ctx, ETH_CHAIN_ID, &[call]).await;
+    assert_error(
+        ctx.update_root_with_query(&response, ROOT, MOCK_GUARDIAN_SET_INDEX)
+            .await,
+        SolanaWorldIDProgramError::InvalidRequestSignature,
+    );
+}
+
+#[tokio::test]
+pub async fn rejects_invalid_number_of_responses() {
+    let mut ctx = TestContext::start().await;
+    let response = respond_to_root_request(&mut ctx, |ctx| {
+        let response = eth_call(ctx, ETH_CHAIN_ID, vec![ROOT.to_vec()]);
+        vec![response.clone(), response]
+    })
+    .await;
+    assert_error(
+        ctx.update_root_with_query(&response, ROOT, MOCK_GUARDIAN_SET_INDEX)
+            .await,
+        SolanaWorldIDProgramError::InvalidNumberOfResponses,
+    );
+}
+
+#[tokio::test]
+pub async fn rejects_invalid_response_chain_id() {
+    let mut ctx = TestContext::start().await;
+    let response = respond_to_root_request(&mut ctx, |ctx| {
+        vec![eth_call(ctx, OTHER_CHAIN_ID, vec![ROOT.to_vec()])]
+    })
+    .await;
+    assert_error(
+        ctx.update_root_with_query(&response, ROOT, MOCK_GUARDIAN_SET_INDEX)
+            .await,
+        SolanaWorldIDProgramError::InvalidResponseChainId,
+    );
+}
+
+#[tokio::test]
+pub async fn rejects_invalid_response_type() {
+    let mut ctx = TestContext::start().await;
+    let response = respond_to_root_request(&mut ctx, |ctx| {
+        vec![PerChainResponse {
+            query_type: ETH_CALL_WITH_FINALITY_QUERY_TYPE,
+            ..eth_call(ctx, ETH_CHAIN_ID, vec![ROOT.to_vec()])
+        }]
+    })
+    .await;
+    assert_error(
+        ctx.update_root_with_query(&response, ROOT, MOCK_GUARDIAN_SET_INDEX)
+            .await,
+        SolanaWorldIDProgramError::InvalidResponseType,
+    );
+}
+
+#[tokio::test]
+pub async fn rejects_stale_block_number() {
+    let mut ctx = TestContext::start().await;
+    let response = ctx.mock_root_response(ROOT).await;
+    ctx.update_root_with_query(&response, ROOT, MOCK_GUARDIAN_SET_INDEX)
+        .await
+        .unwrap();
+    // Same block, but a new root account.
+    assert_error(
+        ctx.update_root_with_query(&response, OTHER_ROOT, MOCK_GUARDIAN_SET_INDEX)
+            .await,
+        SolanaWorldIDProgramError::StaleBlockNum,
+    );
+}
+
+#[tokio::test]
+pub async fn rejects_stale_block_time() {
+    let mut ctx = TestContext::start().await;
+    let response = ctx.mock_root_response(ROOT).await;
+    ctx.warp(ALLOWED_UPDATE_STALENESS + 1).await;
+    assert_error(
+        ctx.update_root_with_query(&response, ROOT, MOCK_GUARDIAN_SET_INDEX)
+            .await,
+        SolanaWorldIDProgramError::StaleBlockTime,
+    );
+}
+
+#[tokio::test]
+pub async fn rejects_invalid_response_results_length() {
+    let mut ctx = TestContext::start().await;
+    let response = respond_to_root_request(&mut ctx, |ctx| {
+        vec![eth_call(
+            ctx,
+            ETH_CHAIN_ID,
+            vec![ROOT.to_vec(), ROOT.to_vec()],
+        )]
+    })
+    .await;
+    assert_error(
+        ctx.update_root_with_query(&response, ROOT, MOCK_GUARDIAN_SET_INDEX)
+            .await,
+        SolanaWorldIDProgramError::InvalidResponseResultsLength,
+    );
+}
+
+#[tokio::test]
+pub async fn rejects_invalid_response_result_length() {
+    let mut ctx = TestContext::start().await;
+    let response = respond_to_root_request(&mut ctx, |ctx| {
+        vec![eth_call(ctx, ETH_CHAIN_ID, vec![ROOT[..31].to_vec()])]
+    })
+    .await;
+    assert_error(
+        ctx.update_root_with_query(&response, ROOT, MOCK_GUARDIAN_SET_INDEX)
+            .await,
+        SolanaWorldIDProgramError::InvalidResponseResultLength,
+    );
+}
+
+#[tokio::test]
+pub async fn rejects_root_hash_mismatch() {
+    let mut ctx = TestContext::start().await;
+    let response = ctx.mock_root_response(ROOT).await;
+    assert_error(
+        ctx.update_root_with_query(&response, OTHER_ROOT, MOCK_GUARDIAN_SET_INDEX)
+            .await,
+        SolanaWorldIDProgramError::RootHashMismatch,
+    );
+}
@@ -0,0 +1,130 @@
+use solana_sdk::instruction::Instruction;
+use solana_world_id_client::{error::SolanaWorldIDProgramError, instructions};
+use solana_world_id_program_tests::{secp256k1::secp256k1_instruction, *};
+use wormhole_query_mock::{MockGuardianSet, MockQueryResponse};
+
+const ROOT: [u8; 32] = [1; 32];
+
+/// The instruction which updates the root with `response`, at index 1 after its secp256k1
+/// instruction.
+fn update_root(
+    ctx: &TestContext,
+    response: &MockQueryResponse,
+    guardian_set_index: u32,
+) -> Instruction {
+    instructions::update_root_with_query_precompile(
+        ctx.payer(),
+        false,
+        response.bytes.clone(),
+        ROOT,
+        QUERY_VERIFICATION_TYPE,
+        guardian_set_index,
+    )
+}
+
+/// Verifies the signatures of `response` by `guardians` over the query message of `bytes`.
+fn verify_signatures(
+    guardians: &MockGuardianSet,
+    response: &MockQueryResponse,
+    bytes: &[u8],
+) -> Instruction {
+    secp256k1_instruction(&response.signatures, &guardians.addresses(), bytes, 0)
+}
+
+#[tokio::test]
+pub async fn updates_root() {
+    let mut ctx = TestContext::start().await;
+    let response = ctx.mock_root_response(ROOT).await;
+    let secp256k1_ix = verify_signatures(&ctx.proxy.guardians, &response, &response.bytes);
+    let ix = update_root(&ctx, &response, MOCK_GUARDIAN_SET_INDEX);
+    ctx.send(&[secp256k1_ix, ix], &[]).await.unwrap();
+}
+
+#[tokio::test]
+pub async fn rejects_expired_guardian_set() {
+    let mut ctx = TestContext::start().await;
+    let response = ctx.mock_root_response(ROOT).await;
+    let secp256k1_ix = verify_signatures(&ctx.proxy.guardians, &response, &response.bytes);
+    let ix = update_root(&ctx, &response, EXPIRED_MOCK_GUARDIAN_SET_INDEX);
+    assert_error(
+        ctx.send(&[secp256k1_ix, ix], &[]).await,
+        SolanaWorldIDProgramError::GuardianSetExpired,
+    );
+}
+
+#[tokio::test]
+pub async fn rejects_no_quorum() {
+    let mut ctx = TestContext::start().await;
+    let response = ctx.mock_root_response(ROOT).await;
+    let secp256k1_ix = verify_signatures(&ctx.proxy.guardians, &response, &response.bytes);
+    let ix = update_root(&ctx, &response, NO_QUORUM_MOCK_GUARDIAN_SET_INDEX);
+    assert_error(
+        ctx.send(&[secp256k1_ix, ix], &[]).await,
+        SolanaWorldIDProgramError::NoQuorum,
+    );
+}
+
+#[tokio::test]
+pub async fn rejects_missing_secp256k1_instruction() {
+    let mut ctx = TestContext::start().await;
+    let response = ctx.mock_root_response(ROOT).await;
+    let ix = update_root(&ctx, &response, MOCK_GUARDIAN_SET_INDEX);
+    assert_error(
+        ctx.send(&[ix], &[]).await,
+        SolanaWorldIDProgramError::InvalidSecp256k1Instruction,
+    );
+}
+
+#[tokio::test]
+pub async fn rejects_signatures_from_another_instruction() {
+    let mut ctx = TestContext::start().await;
+    let response = ctx.mock_root_response(ROOT).await;
+    let secp256k1_ix = verify_signatures(&ctx.proxy.guardians, &response, &response.bytes);
+    // The introspected instruction at index 1 verifies the data of the one at index 0.
+    let ix = update_root(&ctx, &response, MOCK_GUARDIAN_SET_INDEX);
+    assert_error(
+        ctx.send(&[secp256k1_ix.clone(), secp256k1_ix, ix], &[])
+            .await,
+        SolanaWorldIDProgramError::InvalidSecp256k1InstructionIndex,
+    );
+}
+
+#[tokio::test]
+pub async fn rejects_signatures_of_another_message() {
+    let mut ctx = TestContext::start().await;
+    let response = ctx.mock_root_response(ROOT).await;
+    let other = ctx.mock_root_response(ROOT).await;
+    let secp256k1_ix = verify_signatures(&ctx.proxy.guardians, &other, &other.bytes);
+    let ix = update_root(&ctx, &response, MOCK_GUARDIAN_SET_INDEX);
+    assert_error(
+        ctx.send(&[secp256k1_ix, ix], &[]).await,
+        SolanaWorldIDProgramError::InvalidSecp256k1Message,
+    );
+}
+
+#[tokio::test]
+pub async fn rejects_unknown_guardian_key() {
+    let mut ctx = TestContext::start().await;
+    let mut response = ctx.mock_root_response(ROOT).await;
+    let guardians = MockGuardianSet::deterministic(1);
+    response.signatures = guardians.sign_quorum(&response.bytes);
+    let secp256k1_ix = verify_signatures(&guardians, &response, &response.bytes);
+    let ix = update_root(&ctx, &response, MOCK_GUARDIAN_SET_INDEX);
+    assert_error(
+        ctx.send(&[secp256k1_ix, ix], &[]).await,
+        SolanaWorldIDProgramError::UnknownGuardianKey,
+    );
+}
+
+#[tokio::test]
+pub async fn rejects_duplicate_signatures() {
+    let mut ctx = TestContext::start().await;
+    let mut response = ctx.mock_root_response(ROOT).await;
+    response.signatures = ctx.proxy.guardians.sign(&response.bytes, &[0, 0]);
+    let secp256k1_ix = verify_signatures(&ctx.proxy.guardians, &response, &response.bytes);
+    let ix = update_root(&ctx, &response, MOCK_GUARDIAN_SET_INDEX);
+    assert_error(
+        ctx.send(&[secp256k1_ix, ix], &[]).await,
+        SolanaWorldIDProgramError::InvalidGuardianIndexNonIncreasing,
+    );
+}
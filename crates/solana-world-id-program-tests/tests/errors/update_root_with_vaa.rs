@@ -0,0 +1,112 @@
+use solana_world_id_client::{
+    error::SolanaWorldIDProgramError, instructions, pda::derive_latest_root_key, state::LatestRoot,
+};
+use solana_world_id_program_tests::*;
+
+async fn start() -> TestContext {
+    let mut ctx = TestContext::start().await;
+    ctx.register_vaa_verification_type().await;
+    ctx
+}
+
+#[tokio::test]
+pub async fn updates_root() {
+    let mut ctx = start().await;
+    let root = ctx.posted_vaa_root(POSTED_VAA_ROOT_0).await;
+    ctx.update_root_with_vaa(POSTED_VAA_ROOT_0, root)
+        .await
+        .unwrap();
+    let latest_root: LatestRoot = ctx
+        .account(derive_latest_root_key(VAA_VERIFICATION_TYPE))
+        .await;
+    assert_eq!(latest_root.root, root);
+}
+
+#[tokio::test]
+pub async fn rejects_query_verification_type() {
+    let mut ctx = start().await;
+    let root = ctx.posted_vaa_root(POSTED_VAA_ROOT_0).await;
+    let ix = instructions::update_root_with_vaa(
+        ctx.payer(),
+        POSTED_VAA_ROOT_0,
+        root,
+        QUERY_VERIFICATION_TYPE,
+    );
+    assert_error(
+        ctx.send(&[ix], &[]).await,
+        SolanaWorldIDProgramError::InvalidRootSource,
+    );
+}
+
+#[tokio::test]
+pub async fn rejects_invalid_emitter_chain() {
+    let mut ctx = start().await;
+    let root = ctx.posted_vaa_root(POSTED_VAA_INVALID_EMITTER_CHAIN).await;
+    assert_error(
+        ctx.update_root_with_vaa(POSTED_VAA_INVALID_EMITTER_CHAIN, root)
+            .await,
+        SolanaWorldIDProgramError::InvalidVaaEmitterChain,
+    );
+}
+
+#[tokio::test]
+pub async fn rejects_invalid_emitter_address() {
+    let mut ctx = start().await;
+    let root = ctx
+        .posted_vaa_root(POSTED_VAA_INVALID_EMITTER_ADDRESS)
+        .await;
+    assert_error(
+        ctx.update_root_with_vaa(POSTED_VAA_INVALID_EMITTER_ADDRESS, root)
+            .await,
+        SolanaWorldIDProgramError::InvalidVaaEmitterAddress,
+    );
+}
+
+#[tokio::test]
+pub async fn rejects_stale_sequence() {
+    let mut ctx = start().await;
+    let root = ctx.posted_vaa_root(POSTED_VAA_ROOT_0).await;
+    ctx.update_root_with_vaa(POSTED_VAA_ROOT_0, root)
+        .await
+        .unwrap();
+    // Same sequence, but a new root.
+    let root = ctx.posted_vaa_root(POSTED_VAA_STALE_SEQUENCE).await;
+    assert_error(
+        ctx.update_root_with_vaa(POSTED_VAA_STALE_SEQUENCE, root)
+            .await,
+        SolanaWorldIDProgramError::StaleVaaSequence,
+    );
+}
+
+#[tokio::test]
+pub async fn rejects_stale_block_time() {
+    let mut ctx = start().await;
+    ctx.set_time(i64::from(u32::MAX) + ALLOWED_UPDATE_STALENESS as i64 + 1)
+        .await;
+    let root = ctx.posted_vaa_root(POSTED_VAA_ROOT_0).await;
+    assert_error(
+        ctx.update_root_with_vaa(POSTED_VAA_ROOT_0, root).await,
+        SolanaWorldIDProgramError::StaleBlockTime,
+    );
+}
+
+#[tokio::test]
+pub async fn rejects_invalid_payload() {
+    let mut ctx = start().await;
+    let root = ctx.posted_vaa_root(POSTED_VAA_INVALID_PAYLOAD).await;
+    assert_error(
+        ctx.update_root_with_vaa(POSTED_VAA_INVALID_PAYLOAD, root)
+            .await,
+        SolanaWorldIDProgramError::InvalidVaaPayload,
+    );
+}
+
+#[tokio::test]
+pub async fn rejects_root_hash_mismatch() {
+    let mut ctx = start().await;
+    let root = ctx.posted_vaa_root(POSTED_VAA_ROOT_1).await;
+    assert_error(
+        ctx.update_root_with_vaa(POSTED_VAA_ROOT_0, root).await,
+        SolanaWorldIDProgramError::RootHashMismatch,
+    );
+}
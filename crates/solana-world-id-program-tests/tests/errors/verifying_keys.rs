@@ -0,0 +1,159 @@
+use solana_sdk::signature::Signer;
+use solana_world_id_client::{error::SolanaWorldIDProgramError, instructions};
+use solana_world_id_program_tests::{proof::valid_proof, *};
+
+/// Starts with the root of [`valid_proof`] as the latest root.
+async fn start() -> TestContext {
+    let mut ctx = TestContext::start().await;
+    ctx.update_root(valid_proof().root_hash).await;
+    ctx
+}
+
+#[tokio::test]
+pub async fn verifies_proof_with_registered_key() {
+    let mut ctx = start().await;
+    ctx.register_verifying_key().await;
+    let ix = instructions::verify_groth16_proof(
+        QUERY_VERIFICATION_TYPE,
+        Some(VERIFYING_KEY_ID),
+        valid_proof(),
+    );
+    ctx.send(&[ix], &[]).await.unwrap();
+}
+
+#[tokio::test]
+pub async fn rejects_missing_verifying_key() {
+    let mut ctx = start().await;
+    ctx.register_verifying_key().await;
+    let ix = instructions::verify_groth16_proof(QUERY_VERIFICATION_TYPE, None, valid_proof());
+    assert_error(
+        ctx.send(&[ix], &[]).await,
+        SolanaWorldIDProgramError::VerifyingKeyNotProvided,
+    );
+}
+
+#[tokio::test]
+pub async fn rejects_key_of_another_verification_type() {
+    let mut ctx = start().await;
+    ctx.register_verifying_key().await;
+    // Switch back to the built-in key, leaving the registered one unused.
+    ctx.rotate_verifying_key(None, 0).await;
+    let ix = instructions::verify_groth16_proof(
+        QUERY_VERIFICATION_TYPE,
+        Some(VERIFYING_KEY_ID),
+        valid_proof(),
+    );
+    assert_error(
+        ctx.send(&[ix], &[]).await,
+        SolanaWorldIDProgramError::VerifyingKeyMismatch,
+    );
+}
+
+#[tokio::test]
+pub async fn rejects_retired_verifying_key() {
+    let mut ctx = start().await;
+    ctx.register_verifying_key().await;
+    let ix = instructions::retire_verifying_key(ctx.owner.pubkey(), VERIFYING_KEY_ID);
+    ctx.send_as_owner(&[ix]).await.unwrap();
+    let ix = instructions::verify_groth16_proof(
+        QUERY_VERIFICATION_TYPE,
+        Some(VERIFYING_KEY_ID),
+        valid_proof(),
+    );
+    assert_error(
+        ctx.send(&[ix], &[]).await,
+        SolanaWorldIDProgramError::RetiredVerifyingKey,
+    );
+}
+
+#[tokio::test]
+pub async fn rejects_invalid_verifying_key() {
+    let mut ctx = start().await;
+    let mut args = built_in_verifying_key();
+    // Not on the curve, as y^2 != x^3 + 3.
+    args.alpha_g1 = [0; 64];
+    args.alpha_g1[31] = 1;
+    args.alpha_g1[63] = 3;
+    let ix = instructions::register_verifying_key(
+        ctx.payer(),
+        ctx.owner.pubkey(),
+        VERIFYING_KEY_ID,
+        args,
+    );
+    assert_error(
+        ctx.send_as_owner(&[ix]).await,
+        SolanaWorldIDProgramError::InvalidVerifyingKey,
+    );
+}
+
+#[tokio::test]
+pub async fn rejects_activation_without_pending_key() {
+    let mut ctx = start().await;
+    let ix =
+        instructions::activate_verifying_key(ctx.owner.pubkey(), QUERY_VERIFICATION_TYPE, None);
+    assert_error(
+        ctx.send_as_owner(&[ix]).await,
+        SolanaWorldIDProgramError::NoPendingVerifyingKey,
+    );
+}
+
+#[tokio::test]
+pub async fn rejects_activation_before_delay() {
+    let mut ctx = start().await;
+    let ix = instructions::register_verifying_key(
+        ctx.payer(),
+        ctx.owner.pubkey(),
+        VERIFYING_KEY_ID,
+        built_in_verifying_key(),
+    );
+    ctx.send_as_owner(&[ix]).await.unwrap();
+    let ix = instructions::propose_verifying_key(
+        ctx.owner.pubkey(),
+        QUERY_VERIFICATION_TYPE,
+        Some(VERIFYING_KEY_ID),
+        0,
+    );
+    ctx.send_as_owner(&[ix]).await.unwrap();
+    ctx.warp(VERIFYING_KEY_ACTIVATION_DELAY - 1).await;
+    let ix = instructions::activate_verifying_key(
+        ctx.owner.pubkey(),
+        QUERY_VERIFICATION_TYPE,
+        Some(VERIFYING_KEY_ID),
+    );
+    assert_error(
+        ctx.send_as_owner(&[ix]).await,
+        SolanaWorldIDProgramError::VerifyingKeyActivationDelayNotElapsed,
+    );
+}
+
+#[tokio::test]
+pub async fn verifies_proof_with_previous_key_during_grace_period() {
+    let mut ctx = start().await;
+    ctx.register_verifying_key().await;
+    ctx.rotate_verifying_key(None, 60).await;
+    for verifying_key_id in [Some(VERIFYING_KEY_ID), None] {
+        let ix = instructions::verify_groth16_proof(
+            QUERY_VERIFICATION_TYPE,
+            verifying_key_id,
+            valid_proof(),
+        );
+        ctx.send(&[ix], &[]).await.unwrap();
+    }
+}
+
+#[tokio::test]
+pub async fn rejects_previous_key_after_grace_period() {
+    let mut ctx = start().await;
+    ctx.register_verifying_key().await;
+    ctx.rotate_verifying_key(None, 60).await;
+    ctx.warp(61).await;
+    let ix = instructions::verify_groth16_proof(
+        QUERY_VERIFICATION_TYPE,
+        Some(VERIFYING_KEY_ID),
+        valid_proof(),
+    );
+    assert_error(
+        ctx.send(&[ix], &[]).await,
+        SolanaWorldIDProgramError::VerifyingKeyGracePeriodEnded,
+    );
+}
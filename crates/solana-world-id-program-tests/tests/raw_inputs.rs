@@ -0,0 +1,45 @@
+//! verify_groth16_proof_with_raw_inputs derives the same hashes as the client's helpers.
+
+use anchor_lang::AnchorDeserialize;
+use solana_sdk::instruction::Instruction;
+use solana_world_id_client::{error::SolanaWorldIDProgramError, instructions, VerificationResult};
+use solana_world_id_program_tests::{
+    proof::{valid_proof, ACTION, APP_ID, SIGNAL},
+    *,
+};
+
+fn verify_with_raw_inputs(action: &str) -> Instruction {
+    let args = valid_proof();
+    instructions::verify_groth16_proof_with_raw_inputs(
+        QUERY_VERIFICATION_TYPE,
+        None,
+        args.root_hash,
+        APP_ID.to_string(),
+        action.to_string(),
+        hex::decode(SIGNAL).unwrap(),
+        args.nullifier_hash,
+        args.proof,
+    )
+}
+
+#[tokio::test]
+async fn verifies_proof_with_raw_inputs() {
+    let mut ctx = TestContext::start().await;
+    ctx.update_root(valid_proof().root_hash).await;
+    let ix = verify_with_raw_inputs(ACTION);
+    let data = ctx.simulate_return_data(&[ix]).await.unwrap();
+    let result = VerificationResult::try_from_slice(&data).unwrap();
+    assert_eq!(result.root, valid_proof().root_hash);
+    assert!(result.is_latest_root);
+}
+
+#[tokio::test]
+async fn rejects_proof_of_another_action() {
+    let mut ctx = TestContext::start().await;
+    ctx.update_root(valid_proof().root_hash).await;
+    let ix = verify_with_raw_inputs("garbage");
+    assert_error(
+        ctx.send(&[ix], &[]).await,
+        SolanaWorldIDProgramError::Groth16ProofVerificationFailed,
+    );
+}
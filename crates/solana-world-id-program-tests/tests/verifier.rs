@@ -0,0 +1,95 @@
+//! The off-chain verifier of solana-world-id-client agrees with verify_groth16_proof, both on the
+//! returned VerificationResult and on the error of a rejected proof.
+
+use anchor_lang::{prelude::ProgramError, AnchorDeserialize};
+use solana_sdk::{instruction::InstructionError, signature::Signer, transaction::TransactionError};
+use solana_world_id_client::{
+    instructions,
+    pda::{derive_latest_root_key, derive_root_key, derive_verification_type_config_key},
+    verifier, Groth16ProofArgs, VerificationResult,
+};
+use solana_world_id_program_tests::{proof::valid_proof, *};
+
+const OTHER_ROOT: [u8; 32] = [2; 32];
+
+/// Verifies `args` off-chain against the fetched accounts and on-chain, at the same time.
+async fn assert_same_outcome(ctx: &mut TestContext, args: Groth16ProofArgs) {
+    let root = ctx
+        .account(derive_root_key(args.root_hash, QUERY_VERIFICATION_TYPE))
+        .await;
+    let latest_root = ctx
+        .account(derive_latest_root_key(QUERY_VERIFICATION_TYPE))
+        .await;
+    let config = ctx
+        .account(derive_verification_type_config_key(QUERY_VERIFICATION_TYPE))
+        .await;
+    let now = ctx.now().await as u64;
+    let off_chain = verifier::verify_groth16_proof(&args, &root, &latest_root, &config, None, now);
+
+    let ix = instructions::verify_groth16_proof(QUERY_VERIFICATION_TYPE, None, args);
+    let on_chain = ctx.simulate_return_data(&[ix]).await;
+    match (off_chain, on_chain) {
+        (Ok(expected), Ok(data)) => {
+            assert_eq!(VerificationResult::try_from_slice(&data).unwrap(), expected)
+        }
+        (Err(expected), Err(error)) => match (ProgramError::from(expected), error.unwrap()) {
+            (
+                ProgramError::Custom(expected),
+                TransactionError::InstructionError(_, InstructionError::Custom(code)),
+            ) => assert_eq!(code, expected),
+            other => panic!("expected the same error, got {other:?}"),
+        },
+        other => panic!("expected the same outcome, got {other:?}"),
+    }
+}
+
+/// Starts with the root of [`valid_proof`] as the latest root.
+async fn start() -> TestContext {
+    let mut ctx = TestContext::start().await;
+    ctx.update_root(valid_proof().root_hash).await;
+    ctx
+}
+
+#[tokio::test]
+async fn agrees_on_latest_root() {
+    let mut ctx = start().await;
+    assert_same_outcome(&mut ctx, valid_proof()).await;
+}
+
+#[tokio::test]
+async fn agrees_on_older_root() {
+    let mut ctx = start().await;
+    ctx.update_root(OTHER_ROOT).await;
+    ctx.warp(ROOT_EXPIRY / 2).await;
+    assert_same_outcome(&mut ctx, valid_proof()).await;
+}
+
+#[tokio::test]
+async fn agrees_on_expired_root() {
+    let mut ctx = start().await;
+    ctx.update_root(OTHER_ROOT).await;
+    ctx.warp(ROOT_EXPIRY + 1).await;
+    assert_same_outcome(&mut ctx, valid_proof()).await;
+}
+
+#[tokio::test]
+async fn agrees_on_invalid_proof() {
+    let mut ctx = start().await;
+    let args = Groth16ProofArgs {
+        signal_hash: [0; 32],
+        ..valid_proof()
+    };
+    assert_same_outcome(&mut ctx, args).await;
+}
+
+#[tokio::test]
+async fn agrees_on_disabled_verification_type() {
+    let mut ctx = start().await;
+    let ix = instructions::set_verification_type_enabled(
+        ctx.owner.pubkey(),
+        QUERY_VERIFICATION_TYPE,
+        false,
+    );
+    ctx.send_as_owner(&[ix]).await.unwrap();
+    assert_same_outcome(&mut ctx, valid_proof()).await;
+}
@@ -0,0 +1,257 @@
+//! Reads the source chain over JSON-RPC.
+
+use std::collections::HashMap;
+
+use anyhow::{anyhow, Context};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use wormhole_query_mock::{EthCallQuery, EthCallResponse};
+
+/// Root returned by the source contract and the block at which it was read.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SourceRoot {
+    pub root: [u8; 32],
+    pub block_number: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcResponse {
+    id: u64,
+    result: Option<Value>,
+    error: Option<RpcError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcError {
+    message: String,
+}
+
+pub struct EthereumClient {
+    url: String,
+    http: reqwest::Client,
+}
+
+impl EthereumClient {
+    pub fn new(url: String) -> Self {
+        Self {
+            url,
+            http: reqwest::Client::new(),
+        }
+    }
+
+    /// Calls `selector` on `contract` alongside `eth_getBlockByNumber` in a single batch, so that
+    /// the returned block number is at least that of the call.
+    pub async fn latest_root(
+        &self,
+        contract: &[u8; 20],
+        selector: &[u8; 4],
+    ) -> anyhow::Result<SourceRoot> {
+        let responses: Vec<RpcResponse> = self
+            .http
+            .post(&self.url)
+            .json(&json!([
+                {
+                    "jsonrpc": "2.0",
+                    "id": 0,
+                    "method": "eth_call",
+                    "params": [
+                        {
+                            "to": format!("0x{}", hex::encode(contract)),
+                            "data": format!("0x{}", hex::encode(selector)),
+                        },
+                        "latest",
+                    ],
+                },
+                {
+                    "jsonrpc": "2.0",
+                    "id": 1,
+                    "method": "eth_getBlockByNumber",
+                    "params": ["latest", false],
+                },
+            ]))
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        parse_latest_root(responses)
+    }
+
+    /// Reads the block and calls of an EthCall query in a single batch, as a guardian would.
+    /// Used to answer queries locally in mock mode.
+    pub async fn eth_call_query(&self, query: &EthCallQuery) -> anyhow::Result<EthCallResponse> {
+        let mut batch = vec![json!({
+            "jsonrpc": "2.0",
+            "id": 0,
+            "method": "eth_getBlockByNumber",
+            "params": [query.block_tag, false],
+        })];
+        for (i, call) in query.call_data.iter().enumerate() {
+            batch.push(json!({
+                "jsonrpc": "2.0",
+                "id": i + 1,
+                "method": "eth_call",
+                "params": [
+                    {
+                        "to": format!("0x{}", hex::encode(call.to)),
+                        "data": format!("0x{}", hex::encode(&call.data)),
+                    },
+                    query.block_tag,
+                ],
+            }));
+        }
+        let responses: Vec<RpcResponse> = self
+            .http
+            .post(&self.url)
+            .json(&batch)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        parse_eth_call_query(responses)
+    }
+}
+
+/// An [`EthereumClient`] per Wormhole chain id, as roots may be read from an L2 or another chain.
+pub struct EthereumClients(HashMap<u16, EthereumClient>);
+
+impl EthereumClients {
+    pub fn new(urls: HashMap<u16, String>) -> Self {
+        Self(
+            urls.into_iter()
+                .map(|(chain_id, url)| (chain_id, EthereumClient::new(url)))
+                .collect(),
+        )
+    }
+
+    pub fn get(&self, chain_id: u16) -> anyhow::Result<&EthereumClient> {
+        self.0.get(&chain_id).with_context(|| {
+            format!("no RPC URL configured for chain {chain_id}, set --rpc-url {chain_id}=<url>")
+        })
+    }
+}
+
+fn parse_latest_root(responses: Vec<RpcResponse>) -> anyhow::Result<SourceRoot> {
+    let call_result = result(&responses, 0)?;
+    let root = decode_hex(call_result.as_str().context("invalid eth_call result")?)?
+        .try_into()
+        .map_err(|_| anyhow!("eth_call result is not 32 bytes"))?;
+    let block_number = decode_quantity(&result(&responses, 1)?["number"])?;
+    Ok(SourceRoot { root, block_number })
+}
+
+/// Parses the block of an EthCall query (id 0) and its calls (ids 1 onwards).
+fn parse_eth_call_query(responses: Vec<RpcResponse>) -> anyhow::Result<EthCallResponse> {
+    let block = result(&responses, 0)?;
+    let block_hash = decode_hex(block["hash"].as_str().context("invalid block hash")?)?
+        .try_into()
+        .map_err(|_| anyhow!("block hash is not 32 bytes"))?;
+    let results = (1..responses.len() as u64)
+        .map(|id| {
+            decode_hex(
+                result(&responses, id)?
+                    .as_str()
+                    .context("invalid eth_call result")?,
+            )
+        })
+        .collect::<anyhow::Result<_>>()?;
+    Ok(EthCallResponse {
+        block_number: decode_quantity(&block["number"])?,
+        block_hash,
+        block_time: decode_quantity(&block["timestamp"])? * 1_000_000,
+        results,
+    })
+}
+
+fn result(responses: &[RpcResponse], id: u64) -> anyhow::Result<Value> {
+    let response = responses
+        .iter()
+        .find(|r| r.id == id)
+        .ok_or_else(|| anyhow!("missing response {id}"))?;
+    match (&response.result, &response.error) {
+        (Some(result), _) => Ok(result.clone()),
+        (None, Some(error)) => Err(anyhow!("RPC error: {}", error.message)),
+        (None, None) => Err(anyhow!("RPC error: unknown error")),
+    }
+}
+
+fn decode_hex(s: &str) -> anyhow::Result<Vec<u8>> {
+    Ok(hex::decode(s.trim_start_matches("0x"))?)
+}
+
+fn decode_quantity(value: &Value) -> anyhow::Result<u64> {
+    let quantity = value.as_str().context("invalid quantity")?;
+    u64::from_str_radix(quantity.trim_start_matches("0x"), 16).context("invalid quantity")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_latest_root() {
+        let responses = serde_json::from_value(json!([
+            { "jsonrpc": "2.0", "id": 1, "result": { "number": "0x5f4b2a" } },
+            { "jsonrpc": "2.0", "id": 0, "result": format!("0x{}", "ab".repeat(32)) },
+        ]))
+        .unwrap();
+        assert_eq!(
+            parse_latest_root(responses).unwrap(),
+            SourceRoot {
+                root: [0xab; 32],
+                block_number: 0x5f4b2a,
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_eth_call_query() {
+        let responses = serde_json::from_value(json!([
+            {
+                "jsonrpc": "2.0",
+                "id": 0,
+                "result": {
+                    "number": "0x5f4b2a",
+                    "hash": format!("0x{}", "cd".repeat(32)),
+                    "timestamp": "0x665a1d80",
+                },
+            },
+            { "jsonrpc": "2.0", "id": 1, "result": format!("0x{}", "ab".repeat(32)) },
+        ]))
+        .unwrap();
+        assert_eq!(
+            parse_eth_call_query(responses).unwrap(),
+            EthCallResponse {
+                block_number: 0x5f4b2a,
+                block_hash: [0xcd; 32],
+                block_time: 0x665a1d80 * 1_000_000,
+                results: vec![vec![0xab; 32]],
+            }
+        );
+    }
+
+    #[test]
+    fn test_ethereum_clients() {
+        let clients = EthereumClients::new(HashMap::from([(2, "http://eth".to_string())]));
+        assert_eq!(clients.get(2).unwrap().url, "http://eth");
+        assert!(clients
+            .get(10002)
+            .unwrap_err()
+            .to_string()
+            .contains("--rpc-url 10002=<url>"));
+    }
+
+    #[test]
+    fn test_parse_latest_root_error() {
+        let responses = serde_json::from_value(json!([
+            { "jsonrpc": "2.0", "id": 0, "error": { "code": -32000, "message": "execution reverted" } },
+            { "jsonrpc": "2.0", "id": 1, "result": { "number": "0x5f4b2a" } },
+        ]))
+        .unwrap();
+        assert!(parse_latest_root(responses)
+            .unwrap_err()
+            .to_string()
+            .contains("execution reverted"));
+    }
+}
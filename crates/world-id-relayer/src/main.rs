@@ -0,0 +1,265 @@
+//! Relays the latest World ID root of a verification type to the Solana World ID Program.
+//!
+//! Runs once by default. When `SLEEP` is set, runs as a service which syncs the root every `SLEEP`
+//! seconds and, when `CLEANUP` is also set, closes expired roots every `CLEANUP` seconds.
+
+mod ethereum;
+mod query_proxy;
+mod relayer;
+
+use std::{collections::HashMap, future::Future, path::PathBuf, time::Duration};
+
+use anyhow::{bail, Context};
+use clap::{Parser, ValueEnum};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    signature::{read_keypair_file, Keypair},
+};
+use solana_world_id_client::program::ETH_CHAIN_ID;
+use tracing::{error, info, warn};
+use wormhole_query_mock::MockGuardianSet;
+
+use crate::{
+    ethereum::EthereumClients,
+    query_proxy::{MockQueryProvider, QueryProvider, QueryProxyClient},
+    relayer::Relayer,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum Network {
+    Localnet,
+    Testnet,
+    Mainnet,
+}
+
+#[derive(Debug, Parser)]
+#[command(version, about)]
+struct Args {
+    #[arg(long, env = "NETWORK", value_enum, default_value_t = Network::Mainnet)]
+    network: Network,
+
+    /// Answers queries locally with the devnet guardian key instead of using a Query proxy.
+    /// Always set on localnet.
+    #[arg(long, env = "MOCK")]
+    mock: bool,
+
+    /// Defaults to the Query proxy of the network.
+    #[arg(long, env = "QUERY_URL")]
+    query_url: Option<String>,
+
+    #[arg(long, env = "QUERY_API_KEY")]
+    query_api_key: Option<String>,
+
+    /// Seconds between root syncs. Runs once when 0.
+    #[arg(long, env = "SLEEP", default_value_t = 0)]
+    sleep: u64,
+
+    /// Seconds between root cleanups. Only used when SLEEP is set, never cleans up when 0.
+    #[arg(long, env = "CLEANUP", default_value_t = 0)]
+    cleanup: u64,
+
+    /// RPC of Ethereum. Defaults to a public RPC of the network.
+    #[arg(long, env = "ETH_RPC_URL")]
+    eth_rpc_url: Option<String>,
+
+    /// RPC of another chain which roots are read from, as `<wormhole chain id>=<url>`.
+    /// May be repeated, or comma-separated in RPC_URLS.
+    #[arg(
+        long = "rpc-url",
+        env = "RPC_URLS",
+        value_delimiter = ',',
+        value_parser = parse_rpc_url
+    )]
+    rpc_urls: Vec<(u16, String)>,
+
+    /// Defaults to a public RPC of the network.
+    #[arg(long, env = "SOLANA_RPC_URL")]
+    solana_rpc_url: Option<String>,
+
+    /// Keypair file of the payer.
+    #[arg(long, env = "WALLET")]
+    wallet: PathBuf,
+
+    #[arg(long, env = "VERIFICATION_TYPE", default_value_t = 0)]
+    verification_type: u8,
+
+    /// Defaults to the current guardian set index of the Core Bridge, or 5 in mock mode.
+    #[arg(long, env = "GUARDIAN_SET_INDEX")]
+    guardian_set_index: Option<u32>,
+
+    /// Number of guardian signatures posted per post_signatures transaction.
+    #[arg(long, env = "SIGNATURES_PER_TX", default_value_t = 13)]
+    signatures_per_tx: usize,
+}
+
+impl Args {
+    fn query_url(&self) -> String {
+        self.query_url.clone().unwrap_or_else(|| {
+            match self.network {
+                Network::Testnet => "https://testnet.query.wormhole.com/v1/query",
+                _ => "https://query.wormhole.com/v1/query",
+            }
+            .to_string()
+        })
+    }
+
+    fn eth_rpc_url(&self) -> String {
+        self.eth_rpc_url.clone().unwrap_or_else(|| {
+            match self.network {
+                Network::Testnet => "https://ethereum-sepolia-rpc.publicnode.com",
+                _ => "https://ethereum-rpc.publicnode.com",
+            }
+            .to_string()
+        })
+    }
+
+    /// RPC URL of each chain by Wormhole chain id, including Ethereum.
+    fn rpc_urls(&self) -> HashMap<u16, String> {
+        let mut rpc_urls = HashMap::from([(ETH_CHAIN_ID, self.eth_rpc_url())]);
+        rpc_urls.extend(self.rpc_urls.iter().cloned());
+        rpc_urls
+    }
+
+    fn solana_rpc_url(&self) -> String {
+        self.solana_rpc_url.clone().unwrap_or_else(|| {
+            match self.network {
+                Network::Localnet => "http://127.0.0.1:8899",
+                Network::Testnet => "https://api.devnet.solana.com",
+                Network::Mainnet => "https://api.mainnet-beta.solana.com",
+            }
+            .to_string()
+        })
+    }
+}
+
+fn parse_rpc_url(s: &str) -> Result<(u16, String), String> {
+    let (chain_id, url) = s
+        .split_once('=')
+        .ok_or_else(|| format!("expected <chain id>=<url>, got {s}"))?;
+    let chain_id = chain_id
+        .parse()
+        .map_err(|_| format!("invalid chain id {chain_id}"))?;
+    Ok((chain_id, url.to_string()))
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    tracing_subscriber::fmt()
+        .with_env_filter(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info")),
+        )
+        .init();
+
+    let args = Args::parse();
+    // The Core Bridge address is fixed at compile time.
+    if (args.network == Network::Testnet) != cfg!(feature = "testnet") {
+        bail!(
+            "NETWORK {:?} requires building with the matching network feature",
+            args.network
+        );
+    }
+    if args.signatures_per_tx == 0 {
+        bail!("SIGNATURES_PER_TX must be greater than 0");
+    }
+    let mock = args.mock || args.network == Network::Localnet;
+    let payer = read_keypair_file(&args.wallet)
+        .map_err(|e| anyhow::anyhow!("{e}"))
+        .with_context(|| format!("failed to read WALLET {}", args.wallet.display()))?;
+    info!(network = ?args.network, mock, verification_type = args.verification_type, "starting");
+
+    if mock {
+        // Signs with the devnet guardian of the tests' mock guardian set.
+        let query_provider = MockQueryProvider {
+            ethereum: EthereumClients::new(args.rpc_urls()),
+            guardians: MockGuardianSet::devnet(1),
+        };
+        run(&args, relayer(&args, payer, query_provider, Some(5))).await
+    } else {
+        let Some(query_api_key) = args.query_api_key.clone() else {
+            bail!("QUERY_API_KEY is required when MOCK is not set");
+        };
+        let query_provider = QueryProxyClient::new(args.query_url(), query_api_key);
+        run(&args, relayer(&args, payer, query_provider, None)).await
+    }
+}
+
+fn relayer<Q: QueryProvider>(
+    args: &Args,
+    payer: Keypair,
+    query_provider: Q,
+    default_guardian_set_index: Option<u32>,
+) -> Relayer<Q> {
+    Relayer {
+        rpc: RpcClient::new_with_commitment(args.solana_rpc_url(), CommitmentConfig::confirmed()),
+        ethereum: EthereumClients::new(args.rpc_urls()),
+        query_provider,
+        payer,
+        verification_type: [args.verification_type],
+        guardian_set_index: args.guardian_set_index.or(default_guardian_set_index),
+        signatures_per_tx: args.signatures_per_tx,
+        compute_unit_limit: match args.network {
+            Network::Mainnet => Some(420_000),
+            _ => None,
+        },
+    }
+}
+
+async fn run<Q: QueryProvider>(args: &Args, relayer: Relayer<Q>) -> anyhow::Result<()> {
+    if args.sleep == 0 {
+        return relayer.sync_root().await;
+    }
+    info!("SLEEP is set. Running as a service.");
+    let interval = Duration::from_secs(args.sleep);
+    if args.cleanup == 0 {
+        run_with_retry("sync", interval, || relayer.sync_root()).await;
+    } else {
+        info!("CLEANUP is set. Running intermittent cleanup.");
+        let cleanup_interval = Duration::from_secs(args.cleanup);
+        tokio::join!(
+            run_with_retry("sync", interval, || relayer.sync_root()),
+            run_with_retry("cleanup", cleanup_interval, || relayer.clean_up_roots()),
+        );
+    }
+    Ok(())
+}
+
+/// Runs `f` every `interval`, backing off exponentially while it fails.
+async fn run_with_retry<F, Fut>(source: &str, interval: Duration, mut f: F)
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = anyhow::Result<()>>,
+{
+    let mut retry = 0;
+    loop {
+        match f().await {
+            Ok(()) => {
+                retry = 0;
+                tokio::time::sleep(interval).await;
+            }
+            Err(e) => {
+                retry = (retry + 1).min(10);
+                error!(source, "{e:#}");
+                let backoff = interval * 2u32.pow(retry);
+                warn!(source, "backing off for {backoff:?}");
+                tokio::time::sleep(backoff).await;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_rpc_url() {
+        assert_eq!(
+            parse_rpc_url("10002=https://rpc.example/?key=a").unwrap(),
+            (10002, "https://rpc.example/?key=a".to_string())
+        );
+        assert!(parse_rpc_url("https://rpc.example").is_err());
+        assert!(parse_rpc_url("sepolia=https://rpc.example").is_err());
+    }
+}
@@ -0,0 +1,134 @@
+//! Requests signed Query responses from a Wormhole Query proxy, or answers them locally in mock mode.
+//!
+//! See https://docs.wormhole.com/wormhole/queries/use-queries for the proxy API.
+
+use anyhow::{anyhow, Context};
+use serde::{Deserialize, Serialize};
+use wormhole_query_mock::{
+    parse_query_request, serialize_query_response, MockGuardianSet, PerChainResponse,
+};
+
+use crate::ethereum::EthereumClients;
+
+/// A QueryResponse and the guardian signatures over it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SignedQueryResponse {
+    pub bytes: Vec<u8>,
+    /// Signatures in the form expected by post_signatures: guardian index followed by r, s and v.
+    pub signatures: Vec<[u8; 66]>,
+}
+
+/// Source of signed Query responses. Implemented by [`QueryProxyClient`] and by mocks which sign
+/// responses locally.
+pub trait QueryProvider {
+    async fn query(&self, request: &[u8]) -> anyhow::Result<SignedQueryResponse>;
+}
+
+#[derive(Serialize)]
+struct ProxyRequest {
+    bytes: String,
+}
+
+#[derive(Deserialize)]
+struct ProxyResponse {
+    bytes: String,
+    signatures: Vec<String>,
+}
+
+pub struct QueryProxyClient {
+    url: String,
+    api_key: String,
+    http: reqwest::Client,
+}
+
+impl QueryProxyClient {
+    pub fn new(url: String, api_key: String) -> Self {
+        Self {
+            url,
+            api_key,
+            http: reqwest::Client::new(),
+        }
+    }
+}
+
+impl QueryProvider for QueryProxyClient {
+    async fn query(&self, request: &[u8]) -> anyhow::Result<SignedQueryResponse> {
+        let response: ProxyResponse = self
+            .http
+            .post(&self.url)
+            .header("X-API-Key", &self.api_key)
+            .json(&ProxyRequest {
+                bytes: hex::encode(request),
+            })
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        Ok(SignedQueryResponse {
+            bytes: hex::decode(response.bytes.trim_start_matches("0x"))
+                .context("invalid response bytes")?,
+            signatures: response
+                .signatures
+                .iter()
+                .map(|s| to_solana_signature(s))
+                .collect::<anyhow::Result<_>>()?,
+        })
+    }
+}
+
+/// Answers queries like [`QueryProxyClient`] without a proxy: reads each queried chain over
+/// JSON-RPC and signs with local guardian keys. Same as `QueryProxyMock` of the TypeScript query SDK.
+pub struct MockQueryProvider {
+    pub ethereum: EthereumClients,
+    pub guardians: MockGuardianSet,
+}
+
+impl QueryProvider for MockQueryProvider {
+    async fn query(&self, request: &[u8]) -> anyhow::Result<SignedQueryResponse> {
+        let mut responses = Vec::new();
+        for query in parse_query_request(request)?.queries {
+            let response = self
+                .ethereum
+                .get(query.chain_id)?
+                .eth_call_query(&query)
+                .await?;
+            responses.push(PerChainResponse::eth_call(query.chain_id, response));
+        }
+        let bytes = serialize_query_response(request, &responses);
+        let signatures = self.guardians.sign_quorum(&bytes);
+        Ok(SignedQueryResponse { bytes, signatures })
+    }
+}
+
+/// Converts a proxy signature (r, s and v followed by the guardian index) to the form expected by
+/// post_signatures. Same as `signaturesToSolanaArray` of the TypeScript query SDK.
+pub fn to_solana_signature(signature: &str) -> anyhow::Result<[u8; 66]> {
+    let bytes = hex::decode(signature.trim_start_matches("0x")).context("invalid signature")?;
+    if bytes.len() != 66 {
+        return Err(anyhow!("signature is {} bytes, expected 66", bytes.len()));
+    }
+    let mut solana_signature = [0; 66];
+    solana_signature[0] = bytes[65];
+    solana_signature[1..].copy_from_slice(&bytes[..65]);
+    Ok(solana_signature)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_solana_signature() {
+        let signature = format!("{}{}{}", "11".repeat(64), "01", "0c");
+        let solana_signature = to_solana_signature(&signature).unwrap();
+        assert_eq!(solana_signature[0], 12);
+        assert_eq!(solana_signature[1..65], [0x11; 64]);
+        assert_eq!(solana_signature[65], 1);
+    }
+
+    #[test]
+    fn test_to_solana_signature_invalid_length() {
+        assert!(to_solana_signature(&"11".repeat(65)).is_err());
+    }
+}
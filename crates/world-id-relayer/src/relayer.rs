@@ -0,0 +1,353 @@
+//! Syncs roots to and cleans up roots from the Solana World ID Program.
+
+use anchor_lang::{AccountDeserialize, Discriminator};
+use anyhow::{anyhow, bail, Context};
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::{
+    nonblocking::rpc_client::RpcClient,
+    rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
+    rpc_filter::{Memcmp, RpcFilterType},
+};
+use solana_sdk::{
+    compute_budget::ComputeBudgetInstruction,
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{Keypair, Signature, Signer},
+    transaction::Transaction,
+};
+use solana_world_id_client::{
+    instructions, pda,
+    query::latest_root_query_request,
+    state::{Config, LatestRoot, Root, RootSource, VerificationTypeConfig},
+    ID,
+};
+use tracing::{debug, info, warn};
+use wormhole_query_sdk::structs::{ChainSpecificResponse, QueryResponse};
+
+use crate::{
+    ethereum::EthereumClients,
+    query_proxy::{QueryProvider, SignedQueryResponse},
+};
+
+/// Nonce of every QueryRequest, as in the TypeScript bridge.
+const QUERY_NONCE: u32 = 42;
+
+pub struct Relayer<Q> {
+    pub rpc: RpcClient,
+    pub ethereum: EthereumClients,
+    pub query_provider: Q,
+    pub payer: Keypair,
+    pub verification_type: [u8; 1],
+    /// Read from the Core Bridge when `None`.
+    pub guardian_set_index: Option<u32>,
+    pub signatures_per_tx: usize,
+    pub compute_unit_limit: Option<u32>,
+}
+
+impl<Q: QueryProvider> Relayer<Q> {
+    /// Queries the latest root of the verification type's source and, when it is newer than the
+    /// latest root on Solana, posts it with update_root_with_query.
+    pub async fn sync_root(&self) -> anyhow::Result<()> {
+        let config: VerificationTypeConfig = self
+            .fetch(&pda::derive_verification_type_config_key(
+                self.verification_type,
+            ))
+            .await?;
+        let (chain_id, contract, selector) = match config.source {
+            RootSource::Query {
+                chain_id,
+                contract,
+                selector,
+            } => (chain_id, contract, selector),
+            _ => bail!("verification type is not updated with Queries"),
+        };
+        let source_root = self
+            .ethereum
+            .get(chain_id)?
+            .latest_root(&contract, &selector)
+            .await?;
+        let latest_root: LatestRoot = self
+            .fetch(&pda::derive_latest_root_key(self.verification_type))
+            .await?;
+        info!(
+            block_number = source_root.block_number,
+            root = hex::encode(source_root.root),
+            "source root"
+        );
+        info!(
+            block_number = latest_root.read_block_number,
+            root = hex::encode(latest_root.root),
+            "solana root"
+        );
+        if source_root.root == latest_root.root
+            || source_root.block_number <= latest_root.read_block_number
+        {
+            debug!("Roots match, nothing to update.");
+            return Ok(());
+        }
+
+        debug!("Source root is newer, querying...");
+        let request =
+            latest_root_query_request(QUERY_NONCE, &config.source, source_root.block_number)
+                .context("verification type is not updated with Queries")?;
+        let response = self.query_provider.query(&request).await?;
+        let queried_root = queried_root(&response.bytes)?;
+        if queried_root != source_root.root {
+            warn!(
+                ours = hex::encode(source_root.root),
+                theirs = hex::encode(queried_root),
+                "Queried root mismatch!"
+            );
+            return Ok(());
+        }
+
+        debug!("Query successful! Updating...");
+        let signature = self.update_root_with_query(response, queried_root).await?;
+        info!(%signature, "Successfully updated root on Solana");
+        Ok(())
+    }
+
+    /// Posts the guardian signatures in chunks and updates the root. Closes the signatures account
+    /// if the update fails, so that its rent is not left behind.
+    async fn update_root_with_query(
+        &self,
+        response: SignedQueryResponse,
+        root_hash: [u8; 32],
+    ) -> anyhow::Result<Signature> {
+        let guardian_set_index = match self.guardian_set_index {
+            Some(index) => index,
+            None => self.current_guardian_set_index().await?,
+        };
+        // The payer must be on the relayer allowlist while it is enabled.
+        let config: Config = self.fetch(&pda::derive_config_key()).await?;
+        let guardian_signatures = Keypair::new();
+        let total_signatures =
+            u8::try_from(response.signatures.len()).map_err(|_| anyhow!("too many signatures"))?;
+
+        let result = async {
+            for (i, chunk) in response
+                .signatures
+                .chunks(self.signatures_per_tx)
+                .enumerate()
+            {
+                let ix = instructions::post_signatures(
+                    self.payer.pubkey(),
+                    guardian_signatures.pubkey(),
+                    chunk.to_vec(),
+                    total_signatures,
+                );
+                // Only the first chunk creates the account.
+                if i == 0 {
+                    self.send(vec![ix], &[&guardian_signatures]).await?;
+                } else {
+                    self.send(vec![ix], &[]).await?;
+                }
+            }
+            let mut ixs = Vec::new();
+            if let Some(units) = self.compute_unit_limit {
+                ixs.push(ComputeBudgetInstruction::set_compute_unit_limit(units));
+            }
+            ixs.push(instructions::update_root_with_query(
+                self.payer.pubkey(),
+                config.relayer_allowlist_enabled,
+                guardian_signatures.pubkey(),
+                self.payer.pubkey(),
+                response.bytes,
+                root_hash,
+                self.verification_type,
+                guardian_set_index,
+            ));
+            self.send(ixs, &[]).await
+        }
+        .await;
+
+        if result.is_err() {
+            self.close_signatures(&guardian_signatures.pubkey()).await;
+        }
+        result
+    }
+
+    /// Closes a leftover GuardianSignatures account, if it was created.
+    async fn close_signatures(&self, guardian_signatures: &Pubkey) {
+        match self.rpc.get_account(guardian_signatures).await {
+            Ok(account) if account.owner == ID => {}
+            _ => return,
+        }
+        let ix = instructions::close_signatures(*guardian_signatures, self.payer.pubkey());
+        match self.send(vec![ix], &[]).await {
+            Ok(signature) => info!(%guardian_signatures, %signature, "Closed signatures"),
+            Err(e) => warn!(%guardian_signatures, "Error closing signatures: {e:#}"),
+        }
+    }
+
+    /// Closes every expired root which is not the latest root of its verification type.
+    pub async fn clean_up_roots(&self) -> anyhow::Result<()> {
+        info!("Cleaning up roots...");
+        let configs: Vec<VerificationTypeConfig> = self
+            .fetch_all(VerificationTypeConfig::DISCRIMINATOR)
+            .await?;
+        let slot = self.rpc.get_slot().await?;
+        let block_time = u64::try_from(self.rpc.get_block_time(slot).await?)?;
+        let latest_roots: Vec<LatestRoot> = self.fetch_all(LatestRoot::DISCRIMINATOR).await?;
+        let roots: Vec<Root> = self.fetch_all(Root::DISCRIMINATOR).await?;
+        debug!("Found {} root(s)", roots.len());
+        for root in roots {
+            let root_hex = hex::encode(root.root);
+            let Some(config) = configs
+                .iter()
+                .find(|c| c.verification_type == root.verification_type)
+            else {
+                warn!(
+                    root = root_hex,
+                    "Skipping root of unknown verification type {}", root.verification_type[0]
+                );
+                continue;
+            };
+            if latest_roots
+                .iter()
+                .any(|l| l.verification_type == root.verification_type && l.root == root.root)
+            {
+                debug!(root = root_hex, "Skipping latest root");
+                continue;
+            }
+            if root.is_active(&block_time, &config.root_expiry) {
+                debug!(
+                    root = root_hex,
+                    "Skipping active root, expires in {}s",
+                    root.expiry_time(&config.root_expiry) - block_time
+                );
+                continue;
+            }
+            let ix = instructions::clean_up_root(
+                root.root,
+                root.verification_type,
+                root.refund_recipient,
+            );
+            match self.send(vec![ix], &[]).await {
+                Ok(signature) => info!(root = root_hex, %signature, "Cleaned up root"),
+                Err(e) => warn!(root = root_hex, "Error cleaning up root: {e:#}"),
+            }
+        }
+        info!("Done.");
+        Ok(())
+    }
+
+    async fn current_guardian_set_index(&self) -> anyhow::Result<u32> {
+        let data = self
+            .rpc
+            .get_account_data(&pda::derive_core_bridge_config_key())
+            .await?;
+        // The guardian set index is the first field of the Core Bridge config.
+        let index = data.get(..4).context("invalid Core Bridge config")?;
+        Ok(u32::from_le_bytes(index.try_into()?))
+    }
+
+    async fn fetch<T: AccountDeserialize>(&self, address: &Pubkey) -> anyhow::Result<T> {
+        let data = self
+            .rpc
+            .get_account_data(address)
+            .await
+            .with_context(|| format!("failed to fetch {address}"))?;
+        Ok(T::try_deserialize(&mut data.as_slice())?)
+    }
+
+    async fn fetch_all<T: AccountDeserialize>(
+        &self,
+        discriminator: [u8; 8],
+    ) -> anyhow::Result<Vec<T>> {
+        let accounts = self
+            .rpc
+            .get_program_accounts_with_config(
+                &ID,
+                RpcProgramAccountsConfig {
+                    filters: Some(vec![RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+                        0,
+                        discriminator.to_vec(),
+                    ))]),
+                    account_config: RpcAccountInfoConfig {
+                        encoding: Some(UiAccountEncoding::Base64),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+            )
+            .await?;
+        accounts
+            .into_iter()
+            .map(|(_, account)| Ok(T::try_deserialize(&mut account.data.as_slice())?))
+            .collect()
+    }
+
+    async fn send(&self, ixs: Vec<Instruction>, signers: &[&Keypair]) -> anyhow::Result<Signature> {
+        let blockhash = self.rpc.get_latest_blockhash().await?;
+        let mut all_signers = vec![&self.payer];
+        all_signers.extend_from_slice(signers);
+        let tx = Transaction::new_signed_with_payer(
+            &ixs,
+            Some(&self.payer.pubkey()),
+            &all_signers,
+            blockhash,
+        );
+        Ok(self.rpc.send_and_confirm_transaction(&tx).await?)
+    }
+}
+
+/// Root returned by the single `eth_call` of a QueryResponse.
+pub fn queried_root(bytes: &[u8]) -> anyhow::Result<[u8; 32]> {
+    let response =
+        QueryResponse::deserialize(bytes).map_err(|_| anyhow!("failed to parse response"))?;
+    let result = response
+        .responses
+        .first()
+        .and_then(|r| match &r.response {
+            ChainSpecificResponse::EthCallQueryResponse(r) => r.results.first(),
+            _ => None,
+        })
+        .context("response has no eth_call result")?;
+    result
+        .as_slice()
+        .try_into()
+        .map_err(|_| anyhow!("eth_call result is not 32 bytes"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_world_id_client::query::{serialize_eth_call_query_request, EthCallData};
+    use wormhole_query_mock::{EthCallResponse, MockGuardianSet, PerChainResponse, QueryProxyMock};
+
+    fn response(results: Vec<Vec<u8>>) -> Vec<u8> {
+        let request = serialize_eth_call_query_request(
+            42,
+            2,
+            "0x1",
+            &[EthCallData {
+                to: [0xaa; 20],
+                data: vec![0xd7, 0xb0, 0xfe, 0xf1],
+            }],
+        );
+        QueryProxyMock::new(MockGuardianSet::devnet(1), Default::default())
+            .respond(
+                &request,
+                &[PerChainResponse::eth_call(
+                    2,
+                    EthCallResponse {
+                        block_number: 1,
+                        block_hash: [0; 32],
+                        block_time: 0,
+                        results,
+                    },
+                )],
+            )
+            .bytes
+    }
+
+    #[test]
+    fn test_queried_root() {
+        assert_eq!(
+            queried_root(&response(vec![vec![0xab; 32]])).unwrap(),
+            [0xab; 32]
+        );
+        assert!(queried_root(&response(vec![vec![0xab; 31]])).is_err());
+    }
+}
@@ -0,0 +1,177 @@
+//! Guardian keys which sign QueryResponses.
+
+use anchor_lang::solana_program::keccak;
+use libsecp256k1::{Message, PublicKey, SecretKey};
+use wormhole_query_sdk::MESSAGE_PREFIX;
+
+/// Private keys of the first devnet guardians.
+/// https://github.com/wormhole-foundation/wormhole/blob/main/scripts/devnet-consts.json#L320
+pub const DEVNET_GUARDIAN_KEYS: [&str; 13] = [
+    "cfb12303a19cde580bb4dd771639b0d26bc68353645571a8cff516ab2ee113a0",
+    "c3b2e45c422a1602333a64078aeb42637370b0f48fe385f9cfa6ad54a8e0c47e",
+    "9f790d3f08bc4b5cd910d4278f3deb406e57bb5e924906ccd52052bb078ccd47",
+    "b20cc49d6f2c82a5e6519015fc18aa3e562867f85f872c58f1277cfbd2a0c8e4",
+    "eded5a2fdcb5bbbfa5b07f2a91393813420e7ac30a72fc935b6df36f8294b855",
+    "00d39587c3556f289677a837c7f3c0817cb7541ce6e38a243a4bdc761d534c5e",
+    "da534d61a8da77b232f3a2cee55c0125e2b3e33a5cd8247f3fe9e72379445c3b",
+    "cdbabfc2118eb00bc62c88845f3bbd03cb67a9e18a055101588ca9b36387006c",
+    "c83d36423820e7350428dc4abe645cb2904459b7d7128adefe16472fdac397ba",
+    "1cbf4e1388b81c9020500fefc83a7a81f707091bb899074db1bfce4537428112",
+    "17646a6ba14a541957fc7112cc973c0b3f04fce59484a92c09bb45a0b57eb740",
+    "eb94ff04accbfc8195d44b45e7c7da4c6993b2fbbfc4ef166a7675a905df9891",
+    "053a6527124b309d914a47f5257a995e9b0ad17f14659f90ed42af5e6e262b6a",
+];
+
+/// Digest signed by the guardians, `keccak256(MESSAGE_PREFIX || keccak256(bytes))`.
+pub fn query_message_hash(bytes: &[u8]) -> [u8; 32] {
+    keccak::hashv(&[MESSAGE_PREFIX, &keccak::hash(bytes).0]).0
+}
+
+/// Ethereum address of a guardian key, as stored in a Core Bridge GuardianSet.
+pub fn guardian_address(secret_key: &SecretKey) -> [u8; 20] {
+    let public_key = PublicKey::from_secret_key(secret_key).serialize();
+    let hash = keccak::hash(&public_key[1..]).0;
+    hash[12..].try_into().unwrap()
+}
+
+/// An ordered set of guardian keys. A guardian's index is its position in the set.
+#[derive(Clone, Debug)]
+pub struct MockGuardianSet {
+    secret_keys: Vec<SecretKey>,
+}
+
+impl MockGuardianSet {
+    pub fn new(secret_keys: Vec<SecretKey>) -> Self {
+        Self { secret_keys }
+    }
+
+    /// The first `n` devnet guardians. `devnet(1)` matches the single guardian of the tests' mock
+    /// guardian set 5.
+    pub fn devnet(n: usize) -> Self {
+        assert!(n <= DEVNET_GUARDIAN_KEYS.len(), "only 13 devnet keys");
+        Self::new(
+            DEVNET_GUARDIAN_KEYS[..n]
+                .iter()
+                .map(|key| {
+                    let mut bytes = [0; 32];
+                    for (i, byte) in bytes.iter_mut().enumerate() {
+                        *byte = u8::from_str_radix(&key[2 * i..2 * i + 2], 16).unwrap();
+                    }
+                    SecretKey::parse(&bytes).unwrap()
+                })
+                .collect(),
+        )
+    }
+
+    /// `n` guardians with keys derived from their index, for guardian sets of any size.
+    pub fn deterministic(n: usize) -> Self {
+        Self::new(
+            (0..n)
+                .map(|i| {
+                    let seed = keccak::hashv(&[b"mock_guardian", &(i as u64).to_be_bytes()]);
+                    SecretKey::parse(&seed.0).expect("invalid secret key")
+                })
+                .collect(),
+        )
+    }
+
+    pub fn len(&self) -> usize {
+        self.secret_keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.secret_keys.is_empty()
+    }
+
+    /// Minimum number of signatures accepted by the Core Bridge, same as
+    /// `wormhole_raw_vaas::utils::quorum`.
+    pub fn quorum(&self) -> usize {
+        (self.len() * 2) / 3 + 1
+    }
+
+    pub fn addresses(&self) -> Vec<[u8; 20]> {
+        self.secret_keys.iter().map(guardian_address).collect()
+    }
+
+    /// Data of a Core Bridge GuardianSet account (without a discriminator) holding these guardians,
+    /// e.g. to load into a test validator.
+    pub fn guardian_set_data(
+        &self,
+        index: u32,
+        creation_time: u32,
+        expiration_time: u32,
+    ) -> Vec<u8> {
+        let mut data = Vec::with_capacity(16 + 20 * self.len());
+        data.extend_from_slice(&index.to_le_bytes());
+        data.extend_from_slice(&(self.len() as u32).to_le_bytes());
+        for address in self.addresses() {
+            data.extend_from_slice(&address);
+        }
+        data.extend_from_slice(&creation_time.to_le_bytes());
+        data.extend_from_slice(&expiration_time.to_le_bytes());
+        data
+    }
+
+    /// Signs `bytes` with the guardians at `indices`, in that order, returning signatures in the
+    /// form expected by post_signatures: guardian index followed by r, s and v.
+    pub fn sign(&self, bytes: &[u8], indices: &[u8]) -> Vec<[u8; 66]> {
+        let message = Message::parse(&query_message_hash(bytes));
+        indices
+            .iter()
+            .map(|&index| {
+                let (signature, recovery_id) =
+                    libsecp256k1::sign(&message, &self.secret_keys[usize::from(index)]);
+                let mut guardian_signature = [0; 66];
+                guardian_signature[0] = index;
+                guardian_signature[1..65].copy_from_slice(&signature.serialize());
+                guardian_signature[65] = recovery_id.serialize();
+                guardian_signature
+            })
+            .collect()
+    }
+
+    /// Signs `bytes` with the first [`quorum`](Self::quorum) guardians.
+    pub fn sign_quorum(&self, bytes: &[u8]) -> Vec<[u8; 66]> {
+        let indices: Vec<u8> = (0..self.quorum() as u8).collect();
+        self.sign(bytes, &indices)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use libsecp256k1::{recover, RecoveryId, Signature};
+
+    #[test]
+    fn test_devnet_guardian_address() {
+        // Guardian of tests/accounts/core_bridge_mainnet/guardian_set_5_mock.json
+        assert_eq!(
+            MockGuardianSet::devnet(1).addresses()[0],
+            [
+                0xbe, 0xfa, 0x42, 0x9d, 0x57, 0xcd, 0x18, 0xb7, 0xf8, 0xa4, 0xd9, 0x1a, 0x2d, 0xa9,
+                0xab, 0x4a, 0xf0, 0x5d, 0x0f, 0xbe
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sign_recovers_guardian_address() {
+        let guardians = MockGuardianSet::deterministic(19);
+        assert_eq!(guardians.quorum(), 13);
+        let signatures = guardians.sign_quorum(b"bytes");
+        assert_eq!(signatures.len(), 13);
+
+        let message = Message::parse(&query_message_hash(b"bytes"));
+        let addresses = guardians.addresses();
+        for signature in signatures {
+            let public_key = recover(
+                &message,
+                &Signature::parse_standard_slice(&signature[1..65]).unwrap(),
+                &RecoveryId::parse(signature[65]).unwrap(),
+            )
+            .unwrap();
+            let hash = keccak::hash(&public_key.serialize()[1..]).0;
+            assert_eq!(hash[12..], addresses[usize::from(signature[0])]);
+        }
+    }
+}
@@ -0,0 +1,301 @@
+//! Offline mock of a Wormhole Query proxy.
+//!
+//! [`QueryProxyMock`] answers EthCall QueryRequests from a fake EVM state ([`MockEvmState`]) and
+//! signs the QueryResponse with local guardian keys ([`MockGuardianSet`]), producing the bytes and
+//! 66-byte signatures accepted by update_root_with_query. This is the Rust counterpart of the
+//! TypeScript query SDK's `QueryProxyMock`, except that it never calls an RPC.
+//!
+//! Malformed responses can be built with [`serialize_query_response`] and signed with
+//! [`MockGuardianSet::sign`] or [`QueryProxyMock::respond`].
+
+pub mod guardians;
+pub mod request;
+pub mod response;
+
+use std::{collections::HashMap, fmt};
+
+use anchor_lang::solana_program::keccak;
+
+pub use guardians::{query_message_hash, MockGuardianSet};
+pub use request::{parse_query_request, EthCallQuery, MockQueryRequest};
+pub use response::{serialize_query_response, EthCallResponse, PerChainResponse};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MockQueryError {
+    InvalidRequest(&'static str),
+    UnknownChain(u16),
+    UnknownBlock { chain_id: u16, block_tag: String },
+    UnknownCall { chain_id: u16, to: [u8; 20] },
+}
+
+impl fmt::Display for MockQueryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidRequest(reason) => write!(f, "invalid request: {reason}"),
+            Self::UnknownChain(chain_id) => write!(f, "unknown chain {chain_id}"),
+            Self::UnknownBlock {
+                chain_id,
+                block_tag,
+            } => write!(f, "unknown block {block_tag} on chain {chain_id}"),
+            Self::UnknownCall { chain_id, to } => {
+                write!(f, "unknown call to {to:02x?} on chain {chain_id}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for MockQueryError {}
+
+/// Fake state of an EVM chain: its latest block and the results of `eth_call`s at that block.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MockChain {
+    pub block_number: u64,
+    pub block_hash: [u8; 32],
+    /// In microseconds.
+    pub block_time: u64,
+    calls: HashMap<([u8; 20], Vec<u8>), Vec<u8>>,
+}
+
+impl MockChain {
+    /// A chain at `block_number` with a block hash derived from the block number.
+    pub fn new(block_number: u64, block_time: u64) -> Self {
+        Self {
+            block_number,
+            block_hash: keccak::hash(&block_number.to_be_bytes()).0,
+            block_time,
+            calls: HashMap::new(),
+        }
+    }
+
+    /// Sets the result of calling `to` with `data`.
+    pub fn set_call(&mut self, to: [u8; 20], data: Vec<u8>, result: Vec<u8>) -> &mut Self {
+        self.calls.insert((to, data), result);
+        self
+    }
+
+    /// Advances the chain by `blocks` blocks of `block_interval` microseconds each.
+    pub fn advance(&mut self, blocks: u64, block_interval: u64) -> &mut Self {
+        let block_time = self.block_time + blocks * block_interval;
+        let calls = std::mem::take(&mut self.calls);
+        *self = Self {
+            calls,
+            ..Self::new(self.block_number + blocks, block_time)
+        };
+        self
+    }
+
+    fn call(&self, to: &[u8; 20], data: &[u8]) -> Option<&Vec<u8>> {
+        self.calls.get(&(*to, data.to_vec()))
+    }
+}
+
+/// Fake state of EVM chains, keyed by Wormhole chain id.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MockEvmState {
+    pub chains: HashMap<u16, MockChain>,
+}
+
+impl MockEvmState {
+    pub fn with_chain(mut self, chain_id: u16, chain: MockChain) -> Self {
+        self.chains.insert(chain_id, chain);
+        self
+    }
+
+    pub fn chain_mut(&mut self, chain_id: u16) -> Option<&mut MockChain> {
+        self.chains.get_mut(&chain_id)
+    }
+
+    /// Answers an EthCall query. Only the chain's latest block can be queried, by number or as
+    /// `latest`.
+    pub fn eth_call(&self, query: &EthCallQuery) -> Result<EthCallResponse, MockQueryError> {
+        let chain = self
+            .chains
+            .get(&query.chain_id)
+            .ok_or(MockQueryError::UnknownChain(query.chain_id))?;
+        let block_number = match query.block_tag.as_str() {
+            "latest" => Some(chain.block_number),
+            tag => tag
+                .strip_prefix("0x")
+                .and_then(|n| u64::from_str_radix(n, 16).ok()),
+        };
+        if block_number != Some(chain.block_number) {
+            return Err(MockQueryError::UnknownBlock {
+                chain_id: query.chain_id,
+                block_tag: query.block_tag.clone(),
+            });
+        }
+        let results = query
+            .call_data
+            .iter()
+            .map(|call| {
+                chain
+                    .call(&call.to, &call.data)
+                    .cloned()
+                    .ok_or(MockQueryError::UnknownCall {
+                        chain_id: query.chain_id,
+                        to: call.to,
+                    })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(EthCallResponse {
+            block_number: chain.block_number,
+            block_hash: chain.block_hash,
+            block_time: chain.block_time,
+            results,
+        })
+    }
+}
+
+/// A QueryResponse and the guardian signatures over it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MockQueryResponse {
+    pub bytes: Vec<u8>,
+    /// Signatures in the form expected by post_signatures: guardian index followed by r, s and v.
+    pub signatures: Vec<[u8; 66]>,
+}
+
+#[derive(Clone, Debug)]
+pub struct QueryProxyMock {
+    pub guardians: MockGuardianSet,
+    pub state: MockEvmState,
+    /// Indices of the guardians which sign, in order. Defaults to the first quorum of guardians.
+    pub signers: Option<Vec<u8>>,
+}
+
+impl QueryProxyMock {
+    pub fn new(guardians: MockGuardianSet, state: MockEvmState) -> Self {
+        Self {
+            guardians,
+            state,
+            signers: None,
+        }
+    }
+
+    /// Signs with the guardians at `indices` instead of the first quorum, e.g. to sign with fewer
+    /// than a quorum or out of order.
+    pub fn with_signers(mut self, indices: Vec<u8>) -> Self {
+        self.signers = Some(indices);
+        self
+    }
+
+    /// Answers a serialized QueryRequest from the fake EVM state.
+    pub fn mock(&self, request: &[u8]) -> Result<MockQueryResponse, MockQueryError> {
+        let responses = parse_query_request(request)?
+            .queries
+            .iter()
+            .map(|query| {
+                Ok(PerChainResponse::eth_call(
+                    query.chain_id,
+                    self.state.eth_call(query)?,
+                ))
+            })
+            .collect::<Result<Vec<_>, MockQueryError>>()?;
+        Ok(self.respond(request, &responses))
+    }
+
+    /// Serializes and signs arbitrary, possibly malformed, `responses` to `request`.
+    pub fn respond(&self, request: &[u8], responses: &[PerChainResponse]) -> MockQueryResponse {
+        let bytes = serialize_query_response(request, responses);
+        let signatures = match &self.signers {
+            Some(indices) => self.guardians.sign(&bytes, indices),
+            None => self.guardians.sign_quorum(&bytes),
+        };
+        MockQueryResponse { bytes, signatures }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_world_id_client::{query::latest_root_query_request, state::RootSource};
+    use wormhole_query_sdk::structs::{ChainSpecificQuery, ChainSpecificResponse, QueryResponse};
+
+    const IDENTITY_MANAGER: [u8; 20] = [0xaa; 20];
+    const LATEST_ROOT_SELECTOR: [u8; 4] = [0xd7, 0xb0, 0xfe, 0xf1];
+
+    fn source() -> RootSource {
+        RootSource::Query {
+            chain_id: 2,
+            contract: IDENTITY_MANAGER,
+            selector: LATEST_ROOT_SELECTOR,
+        }
+    }
+
+    fn proxy() -> QueryProxyMock {
+        let mut chain = MockChain::new(6_245_162, 1_717_000_000_000_000);
+        chain.set_call(
+            IDENTITY_MANAGER,
+            LATEST_ROOT_SELECTOR.to_vec(),
+            vec![0xab; 32],
+        );
+        QueryProxyMock::new(
+            MockGuardianSet::deterministic(19),
+            MockEvmState::default().with_chain(2, chain),
+        )
+    }
+
+    #[test]
+    fn test_mock_is_parsed_by_query_sdk() {
+        let request = latest_root_query_request(42, &source(), 6_245_162).unwrap();
+        let response = proxy().mock(&request).unwrap();
+        assert_eq!(response.signatures.len(), 13);
+
+        let parsed = QueryResponse::deserialize(&response.bytes).unwrap();
+        assert_eq!(parsed.request.requests.len(), 1);
+        assert_eq!(parsed.request.requests[0].chain_id, 2);
+        match &parsed.request.requests[0].query {
+            ChainSpecificQuery::EthCallQueryRequest(q) => {
+                assert_eq!(q.call_data[0].to, IDENTITY_MANAGER);
+                assert_eq!(q.call_data[0].data, LATEST_ROOT_SELECTOR);
+            }
+            _ => panic!("expected an EthCall request"),
+        }
+        assert_eq!(parsed.responses.len(), 1);
+        assert_eq!(parsed.responses[0].chain_id, 2);
+        match &parsed.responses[0].response {
+            ChainSpecificResponse::EthCallQueryResponse(r) => {
+                assert_eq!(r.block_number, 6_245_162);
+                assert_eq!(r.block_time, 1_717_000_000_000_000);
+                assert_eq!(r.results, vec![vec![0xab; 32]]);
+            }
+            _ => panic!("expected an EthCall response"),
+        }
+    }
+
+    #[test]
+    fn test_mock_rejects_unknown_block() {
+        let request = latest_root_query_request(42, &source(), 6_245_161).unwrap();
+        assert!(matches!(
+            proxy().mock(&request),
+            Err(MockQueryError::UnknownBlock { chain_id: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn test_mock_signs_with_configured_signers() {
+        let request = latest_root_query_request(42, &source(), 6_245_162).unwrap();
+        let response = proxy().with_signers(vec![3, 1]).mock(&request).unwrap();
+        assert_eq!(response.signatures.len(), 2);
+        assert_eq!(response.signatures[0][0], 3);
+        assert_eq!(response.signatures[1][0], 1);
+    }
+
+    #[test]
+    fn test_advance() {
+        let mut proxy = proxy();
+        proxy.state.chain_mut(2).unwrap().advance(10, 12_000_000);
+        let request = latest_root_query_request(42, &source(), 6_245_172).unwrap();
+        let response = proxy.mock(&request).unwrap();
+        match &QueryResponse::deserialize(&response.bytes)
+            .unwrap()
+            .responses[0]
+            .response
+        {
+            ChainSpecificResponse::EthCallQueryResponse(r) => {
+                assert_eq!(r.block_time, 1_717_000_120_000_000);
+                assert_eq!(r.results, vec![vec![0xab; 32]]);
+            }
+            _ => panic!("expected an EthCall response"),
+        }
+    }
+}
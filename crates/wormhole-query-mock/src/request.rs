@@ -0,0 +1,113 @@
+//! Parses the QueryRequests answered by the mock, i.e. EthCall queries.
+
+use solana_world_id_client::query::{EthCallData, ETH_CALL_QUERY_TYPE, QUERY_REQUEST_VERSION};
+
+use crate::MockQueryError;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EthCallQuery {
+    pub chain_id: u16,
+    pub block_tag: String,
+    pub call_data: Vec<EthCallData>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MockQueryRequest {
+    pub nonce: u32,
+    pub queries: Vec<EthCallQuery>,
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], MockQueryError> {
+        if self.0.len() < len {
+            return Err(MockQueryError::InvalidRequest("unexpected end"));
+        }
+        let (taken, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Ok(taken)
+    }
+
+    fn u8(&mut self) -> Result<u8, MockQueryError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, MockQueryError> {
+        Ok(u16::from_be_bytes(self.take(2)?.try_into().unwrap()))
+    }
+
+    fn u32(&mut self) -> Result<u32, MockQueryError> {
+        Ok(u32::from_be_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn bytes(&mut self) -> Result<&'a [u8], MockQueryError> {
+        let len = self.u32()? as usize;
+        self.take(len)
+    }
+}
+
+pub fn parse_query_request(bytes: &[u8]) -> Result<MockQueryRequest, MockQueryError> {
+    let mut reader = Reader(bytes);
+    if reader.u8()? != QUERY_REQUEST_VERSION {
+        return Err(MockQueryError::InvalidRequest("unsupported version"));
+    }
+    let nonce = reader.u32()?;
+    let num_queries = reader.u8()?;
+    let mut queries = Vec::with_capacity(usize::from(num_queries));
+    for _ in 0..num_queries {
+        let chain_id = reader.u16()?;
+        if reader.u8()? != ETH_CALL_QUERY_TYPE {
+            return Err(MockQueryError::InvalidRequest("unsupported query type"));
+        }
+        let mut query = Reader(reader.bytes()?);
+        let block_tag = String::from_utf8(query.bytes()?.to_vec())
+            .map_err(|_| MockQueryError::InvalidRequest("invalid block tag"))?;
+        let num_calls = query.u8()?;
+        let mut call_data = Vec::with_capacity(usize::from(num_calls));
+        for _ in 0..num_calls {
+            let to = query.take(20)?.try_into().unwrap();
+            let data = query.bytes()?.to_vec();
+            call_data.push(EthCallData { to, data });
+        }
+        if !query.0.is_empty() {
+            return Err(MockQueryError::InvalidRequest("trailing query bytes"));
+        }
+        queries.push(EthCallQuery {
+            chain_id,
+            block_tag,
+            call_data,
+        });
+    }
+    if !reader.0.is_empty() {
+        return Err(MockQueryError::InvalidRequest("trailing request bytes"));
+    }
+    Ok(MockQueryRequest { nonce, queries })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_world_id_client::query::serialize_eth_call_query_request;
+
+    #[test]
+    fn test_parse_query_request() {
+        let call_data = vec![EthCallData {
+            to: [0xaa; 20],
+            data: vec![0xd7, 0xb0, 0xfe, 0xf1],
+        }];
+        let bytes = serialize_eth_call_query_request(42, 2, "0x5f4b2a", &call_data);
+        assert_eq!(
+            parse_query_request(&bytes).unwrap(),
+            MockQueryRequest {
+                nonce: 42,
+                queries: vec![EthCallQuery {
+                    chain_id: 2,
+                    block_tag: "0x5f4b2a".to_string(),
+                    call_data,
+                }],
+            }
+        );
+        assert!(parse_query_request(&bytes[..bytes.len() - 1]).is_err());
+    }
+}
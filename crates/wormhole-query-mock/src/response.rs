@@ -0,0 +1,71 @@
+//! Serializes QueryResponses. Responses are built field by field so that tests can produce
+//! malformed ones, e.g. with extra results or a response from another chain.
+
+use solana_world_id_client::query::ETH_CALL_QUERY_TYPE;
+
+pub const QUERY_RESPONSE_VERSION: u8 = 1;
+
+/// Request chain id of off-chain requests, i.e. those sent to a Query proxy.
+pub const OFF_CHAIN_REQUEST_CHAIN_ID: u16 = 0;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EthCallResponse {
+    pub block_number: u64,
+    pub block_hash: [u8; 32],
+    /// In microseconds.
+    pub block_time: u64,
+    pub results: Vec<Vec<u8>>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PerChainResponse {
+    pub chain_id: u16,
+    /// [`ETH_CALL_QUERY_TYPE`] unless deliberately malformed.
+    pub query_type: u8,
+    pub response: EthCallResponse,
+}
+
+impl PerChainResponse {
+    pub fn eth_call(chain_id: u16, response: EthCallResponse) -> Self {
+        Self {
+            chain_id,
+            query_type: ETH_CALL_QUERY_TYPE,
+            response,
+        }
+    }
+}
+
+impl EthCallResponse {
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&self.block_number.to_be_bytes());
+        bytes.extend_from_slice(&self.block_hash);
+        bytes.extend_from_slice(&self.block_time.to_be_bytes());
+        bytes.push(self.results.len() as u8);
+        for result in &self.results {
+            bytes.extend_from_slice(&(result.len() as u32).to_be_bytes());
+            bytes.extend_from_slice(result);
+        }
+        bytes
+    }
+}
+
+/// Serializes the response to an off-chain `request`. The request signature is left empty, as it
+/// is not checked by the program.
+pub fn serialize_query_response(request: &[u8], responses: &[PerChainResponse]) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    bytes.push(QUERY_RESPONSE_VERSION);
+    bytes.extend_from_slice(&OFF_CHAIN_REQUEST_CHAIN_ID.to_be_bytes());
+    bytes.extend_from_slice(&[0; 65]);
+    bytes.extend_from_slice(&(request.len() as u32).to_be_bytes());
+    bytes.extend_from_slice(request);
+    bytes.push(responses.len() as u8);
+    for response in responses {
+        let data = response.response.serialize();
+        bytes.extend_from_slice(&response.chain_id.to_be_bytes());
+        bytes.push(response.query_type);
+        bytes.extend_from_slice(&(data.len() as u32).to_be_bytes());
+        bytes.extend_from_slice(&data);
+    }
+    bytes
+}
@@ -82,6 +82,8 @@ pub enum SolanaWorldIDProgramError {
     #[msg("RootHashMismatch")]
     RootHashMismatch = 0x124,
 
+    /// Deprecated: no longer returned. Root expiry is now changed through
+    /// `propose_config_change`. Kept so the following error codes don't shift.
     #[msg("NoopExpiryUpdate")]
     NoopExpiryUpdate = 0x125,
 
@@ -91,6 +93,48 @@ pub enum SolanaWorldIDProgramError {
     #[msg("RootIsLatest")]
     RootIsLatest = 0x127,
 
+    #[msg("VerificationTypeDisabled")]
+    VerificationTypeDisabled = 0x128,
+
+    #[msg("InvalidRootSource")]
+    InvalidRootSource = 0x129,
+
+    #[msg("InvalidVaaEmitterChain")]
+    InvalidVaaEmitterChain = 0x12a,
+
+    #[msg("InvalidVaaEmitterAddress")]
+    InvalidVaaEmitterAddress = 0x12b,
+
+    #[msg("InvalidVaaPayload")]
+    InvalidVaaPayload = 0x12c,
+
+    #[msg("StaleVaaSequence")]
+    StaleVaaSequence = 0x12d,
+
+    #[msg("InvalidSecp256k1Instruction")]
+    InvalidSecp256k1Instruction = 0x12e,
+
+    #[msg("InvalidSecp256k1InstructionIndex")]
+    InvalidSecp256k1InstructionIndex = 0x12f,
+
+    #[msg("InvalidSecp256k1Message")]
+    InvalidSecp256k1Message = 0x130,
+
+    #[msg("UnknownGuardianKey")]
+    UnknownGuardianKey = 0x131,
+
+    #[msg("RootUpdatesPaused")]
+    RootUpdatesPaused = 0x132,
+
+    #[msg("CleanupPaused")]
+    CleanupPaused = 0x133,
+
+    #[msg("RelayerAllowlistNotProvided")]
+    RelayerAllowlistNotProvided = 0x134,
+
+    #[msg("RelayerNotAllowed")]
+    RelayerNotAllowed = 0x135,
+
     #[msg("RootExpired")]
     RootExpired = 0x200,
 
@@ -100,6 +144,137 @@ pub enum SolanaWorldIDProgramError {
     #[msg("Groth16ProofVerificationFailed")]
     Groth16ProofVerificationFailed = 0x202,
 
+    #[msg("NullifierAlreadyConsumed")]
+    NullifierAlreadyConsumed = 0x203,
+
+    #[msg("EmptyProofBatch")]
+    EmptyProofBatch = 0x204,
+
+    #[msg("RootAccountNotProvided")]
+    RootAccountNotProvided = 0x205,
+
+    #[msg("RootVerificationTypeMismatch")]
+    RootVerificationTypeMismatch = 0x206,
+
+    #[msg("DuplicateRootAccount")]
+    DuplicateRootAccount = 0x207,
+
+    #[msg("RootHashOutOfRange")]
+    RootHashOutOfRange = 0x208,
+
+    #[msg("SignalHashOutOfRange")]
+    SignalHashOutOfRange = 0x209,
+
+    #[msg("NullifierHashOutOfRange")]
+    NullifierHashOutOfRange = 0x20a,
+
+    #[msg("ExternalNullifierHashOutOfRange")]
+    ExternalNullifierHashOutOfRange = 0x20b,
+
+    #[msg("InvalidProofA")]
+    InvalidProofA = 0x20c,
+
+    #[msg("InvalidProofB")]
+    InvalidProofB = 0x20d,
+
+    #[msg("InvalidProofC")]
+    InvalidProofC = 0x20e,
+
+    #[msg("VerifyingKeyNotProvided")]
+    VerifyingKeyNotProvided = 0x20f,
+
+    #[msg("VerifyingKeyMismatch")]
+    VerifyingKeyMismatch = 0x210,
+
+    #[msg("RetiredVerifyingKey")]
+    RetiredVerifyingKey = 0x211,
+
+    #[msg("VerifyingKeyGracePeriodEnded")]
+    VerifyingKeyGracePeriodEnded = 0x212,
+
+    #[msg("ProofVerificationPaused")]
+    ProofVerificationPaused = 0x213,
+
+    #[msg("RootRevoked")]
+    RootRevoked = 0x214,
+
     #[msg("InvalidPendingOwner")]
     InvalidPendingOwner = 0x1000,
+
+    #[msg("InvalidVerifyingKey")]
+    InvalidVerifyingKey = 0x1001,
+
+    #[msg("NoPendingVerifyingKey")]
+    NoPendingVerifyingKey = 0x1002,
+
+    #[msg("VerifyingKeyActivationDelayNotElapsed")]
+    VerifyingKeyActivationDelayNotElapsed = 0x1003,
+
+    #[msg("InvalidPauser")]
+    InvalidPauser = 0x1004,
+
+    #[msg("InvalidPauseFlags")]
+    InvalidPauseFlags = 0x1005,
+
+    /// Deprecated: no longer returned. revoke_root leaves the revoked root as the latest root when
+    /// no previous root is passed. Kept so the following error codes don't shift.
+    #[msg("PreviousRootNotProvided")]
+    PreviousRootNotProvided = 0x1006,
+
+    #[msg("InvalidPreviousRoot")]
+    InvalidPreviousRoot = 0x1007,
+
+    #[msg("ConfigValueOutOfBounds")]
+    ConfigValueOutOfBounds = 0x1008,
+
+    #[msg("ConfigChangeDelayNotElapsed")]
+    ConfigChangeDelayNotElapsed = 0x1009,
+
+    #[msg("VerificationTypeConfigNotProvided")]
+    VerificationTypeConfigNotProvided = 0x100a,
+
+    #[msg("VerificationTypeConfigMismatch")]
+    VerificationTypeConfigMismatch = 0x100b,
+
+    #[msg("InvalidAdminSet")]
+    InvalidAdminSet = 0x100c,
+
+    #[msg("AdminSetThresholdNotMet")]
+    AdminSetThresholdNotMet = 0x100d,
+
+    #[msg("AdminSetMemberNotFound")]
+    AdminSetMemberNotFound = 0x100e,
+
+    #[msg("InvalidConfigManager")]
+    InvalidConfigManager = 0x100f,
+
+    #[msg("InvalidRootRevoker")]
+    InvalidRootRevoker = 0x1010,
+
+    #[msg("InvalidRelayerAllowlist")]
+    InvalidRelayerAllowlist = 0x1011,
+
+    #[msg("RelayerNotFound")]
+    RelayerNotFound = 0x1012,
+
+    #[msg("PreviousRootNotOlder")]
+    PreviousRootNotOlder = 0x1013,
+
+    #[msg("PreviousRootExpired")]
+    PreviousRootExpired = 0x1014,
+
+    #[msg("ConfigAlreadyMigrated")]
+    ConfigAlreadyMigrated = 0x1015,
+
+    #[msg("InvalidLegacyConfigOwner")]
+    InvalidLegacyConfigOwner = 0x1016,
+
+    #[msg("RootAlreadyMigrated")]
+    RootAlreadyMigrated = 0x1017,
+
+    #[msg("RootSourceChainMismatch")]
+    RootSourceChainMismatch = 0x1018,
+
+    #[msg("OwnerOnlyConfigChange")]
+    OwnerOnlyConfigChange = 0x1019,
 }
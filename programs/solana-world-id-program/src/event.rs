@@ -0,0 +1,251 @@
+//! Events emitted by the Solana World ID Program.
+//!
+//! These are emitted with `emit_cpi!`, i.e. as instruction data of a self-CPI, so that indexers
+//! can read them from the transaction's inner instructions even when the logs are truncated.
+
+use anchor_lang::prelude::*;
+
+use crate::state::{ConfigChange, Role, RootSource};
+
+/// Emitted by update_root_with_query, update_root_with_query_precompile and update_root_with_vaa.
+#[event]
+#[derive(Debug)]
+pub struct RootUpdated {
+    pub root: [u8; 32],
+    pub verification_type: [u8; 1],
+    /// Block number (or VAA sequence) from which the root was read.
+    pub read_block_number: u64,
+    /// Block time (in microseconds) from which the root was read.
+    pub read_block_time: u64,
+    /// Guardian set which signed the Query response. `None` for roots from VAAs.
+    pub guardian_set_index: Option<u32>,
+    pub payer: Pubkey,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct RootCleanedUp {
+    pub root: [u8; 32],
+    pub verification_type: [u8; 1],
+    pub refund_recipient: Pubkey,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct SignaturesClosed {
+    pub guardian_signatures: Pubkey,
+    pub refund_recipient: Pubkey,
+}
+
+/// Emitted for every valid proof by verify_groth16_proof, verify_groth16_proof_with_raw_inputs and
+/// verify_and_consume_nullifier.
+#[event]
+#[derive(Debug)]
+pub struct ProofVerified {
+    pub root: [u8; 32],
+    pub verification_type: [u8; 1],
+    pub nullifier_hash: [u8; 32],
+    pub external_nullifier_hash: [u8; 32],
+    pub signal_hash: [u8; 32],
+    /// Program of the top-level instruction when invoked via CPI, `None` when invoked directly.
+    pub caller_program: Option<Pubkey>,
+}
+
+/// A valid proof of a [`ProofsVerified`] batch.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct VerifiedProof {
+    pub root: [u8; 32],
+    pub nullifier_hash: [u8; 32],
+    pub external_nullifier_hash: [u8; 32],
+    pub signal_hash: [u8; 32],
+}
+
+/// Emitted once by verify_groth16_proofs for all valid proofs of the batch, so that a batch costs
+/// a single self-CPI. Not emitted when no proof is valid.
+#[event]
+#[derive(Debug)]
+pub struct ProofsVerified {
+    pub verification_type: [u8; 1],
+    /// Valid proofs, in the order of the batch.
+    pub proofs: Vec<VerifiedProof>,
+    /// Program of the top-level instruction when invoked via CPI, `None` when invoked directly.
+    pub caller_program: Option<Pubkey>,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct OwnershipTransferStarted {
+    pub owner: Pubkey,
+    pub pending_owner: Pubkey,
+}
+
+/// Emitted by claim_ownership. `previous_owner` and `owner` are equal when the transfer was cancelled.
+#[event]
+#[derive(Debug)]
+pub struct OwnershipTransferred {
+    pub previous_owner: Pubkey,
+    pub owner: Pubkey,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct VerificationTypeRegistered {
+    pub verification_type: [u8; 1],
+    pub root_expiry: u64,
+    pub allowed_update_staleness: u64,
+    pub source: RootSource,
+}
+
+/// Emitted by execute_config_change.
+#[event]
+#[derive(Debug)]
+pub struct RootExpiryUpdated {
+    pub verification_type: [u8; 1],
+    pub root_expiry: u64,
+}
+
+/// Emitted by execute_config_change.
+#[event]
+#[derive(Debug)]
+pub struct AllowedUpdateStalenessUpdated {
+    pub verification_type: [u8; 1],
+    pub allowed_update_staleness: u64,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct RootSourceUpdated {
+    pub verification_type: [u8; 1],
+    pub source: RootSource,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct VerificationTypeEnabledUpdated {
+    pub verification_type: [u8; 1],
+    pub enabled: bool,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct VerifyingKeyRegistered {
+    pub key_id: [u8; 1],
+}
+
+#[event]
+#[derive(Debug)]
+pub struct VerifyingKeyRetired {
+    pub key_id: [u8; 1],
+}
+
+/// Emitted by propose_verifying_key. The key may be activated from `activation_time` (in seconds).
+#[event]
+#[derive(Debug)]
+pub struct VerifyingKeyProposed {
+    pub verification_type: [u8; 1],
+    /// `None` for the built-in key.
+    pub verifying_key_id: Option<[u8; 1]>,
+    pub activation_time: u64,
+    pub grace_period: u64,
+}
+
+/// Emitted by activate_verifying_key. Key ids are `None` for the built-in key.
+#[event]
+#[derive(Debug)]
+pub struct VerifyingKeyActivated {
+    pub verification_type: [u8; 1],
+    pub verifying_key_id: Option<[u8; 1]>,
+    pub previous_verifying_key_id: Option<[u8; 1]>,
+    /// Last time (in seconds) at which proofs are verified with the previous key.
+    pub previous_accepted_until: Option<u64>,
+}
+
+/// Emitted by execute_config_change.
+#[event]
+#[derive(Debug)]
+pub struct VerifyingKeyActivationDelayUpdated {
+    pub verifying_key_activation_delay: u64,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct RoleGranted {
+    pub role: Role,
+    pub grantee: Pubkey,
+}
+
+/// Emitted by revoke_role with the account which held the role, if any.
+#[event]
+#[derive(Debug)]
+pub struct RoleRevoked {
+    pub role: Role,
+    pub previous_grantee: Option<Pubkey>,
+}
+
+/// Emitted by pause and unpause with the resulting bitfield of paused instructions.
+#[event]
+#[derive(Debug)]
+pub struct PausedUpdated {
+    pub paused: u8,
+}
+
+/// Emitted by revoke_root. `latest_root` is the root which the latest root was rolled back to, if
+/// any.
+#[event]
+#[derive(Debug)]
+pub struct RootRevoked {
+    pub root: [u8; 32],
+    pub verification_type: [u8; 1],
+    pub latest_root: Option<[u8; 32]>,
+}
+
+/// Emitted by propose_config_change. The change may be executed from `executable_at` (in seconds).
+#[event]
+#[derive(Debug)]
+pub struct ConfigChangeProposed {
+    pub change: ConfigChange,
+    pub executable_at: u64,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct ConfigChangeCancelled {
+    pub change: ConfigChange,
+}
+
+/// Emitted by execute_config_change.
+#[event]
+#[derive(Debug)]
+pub struct ConfigChangeDelayUpdated {
+    pub config_change_delay: u64,
+}
+
+/// Emitted by initialize_admin_set, add_admin_set_member, remove_admin_set_member and
+/// set_admin_set_threshold.
+#[event]
+#[derive(Debug)]
+pub struct AdminSetUpdated {
+    pub members: Vec<Pubkey>,
+    pub threshold: u8,
+}
+
+/// Emitted by execute_as_admin_set with the members which approved the invoked instruction.
+#[event]
+#[derive(Debug)]
+pub struct AdminSetExecuted {
+    pub program: Pubkey,
+    pub signers: Vec<Pubkey>,
+}
+
+/// Emitted by initialize_relayer_allowlist, add_relayer and remove_relayer.
+#[event]
+#[derive(Debug)]
+pub struct RelayerAllowlistUpdated {
+    pub relayers: Vec<Pubkey>,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct RelayerAllowlistEnabledUpdated {
+    pub enabled: bool,
+}
@@ -0,0 +1,31 @@
+//! World ID's `hashToField`, which maps arbitrary bytes into the scalar field of the proof's public
+//! inputs, and the signal and external nullifier hashes derived with it.
+//!
+//! Same as `hashToField` of World ID's `ByteHasher` library. Proofs only verify against public
+//! inputs computed this way, so integrators should use these rather than plain keccak hashes.
+
+use anchor_lang::{prelude::Pubkey, solana_program::keccak};
+
+/// `uint256(keccak256(bytes)) >> 8`, which is always less than the scalar field modulus.
+pub fn hash_to_field(bytes: &[u8]) -> [u8; 32] {
+    let hash = keccak::hash(bytes).0;
+    let mut field = [0; 32];
+    field[1..].copy_from_slice(&hash[..31]);
+    field
+}
+
+/// Hash of an arbitrary signal, e.g. the ABI-packed bytes of an Ethereum address.
+pub fn signal_hash(signal: &[u8]) -> [u8; 32] {
+    hash_to_field(signal)
+}
+
+/// Hash of a Solana account used as the signal, e.g. the wallet a proof is bound to.
+pub fn pubkey_signal_hash(signal: &Pubkey) -> [u8; 32] {
+    hash_to_field(signal.as_ref())
+}
+
+/// External nullifier hash of an action of an app created via https://developer.worldcoin.org:
+/// `hashToField(abi.encodePacked(hashToField(abi.encodePacked(app_id)), action))`.
+pub fn external_nullifier_hash(app_id: &str, action: &str) -> [u8; 32] {
+    hash_to_field(&[&hash_to_field(app_id.as_bytes())[..], action.as_bytes()].concat())
+}
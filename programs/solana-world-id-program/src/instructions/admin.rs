@@ -1,7 +1,28 @@
-use anchor_lang::prelude::*;
+use anchor_lang::{
+    prelude::*,
+    solana_program::{instruction::Instruction, program::invoke_signed},
+};
 use wormhole_solana_utils::cpi::bpf_loader_upgradeable::{self, BpfLoaderUpgradeable};
 
-use crate::{error::SolanaWorldIDProgramError, state::Config};
+use crate::{
+    error::SolanaWorldIDProgramError,
+    event::{
+        AdminSetExecuted, AdminSetUpdated, AllowedUpdateStalenessUpdated, ConfigChangeCancelled,
+        ConfigChangeDelayUpdated, ConfigChangeProposed, OwnershipTransferStarted,
+        OwnershipTransferred, PausedUpdated, RelayerAllowlistEnabledUpdated,
+        RelayerAllowlistUpdated, RoleGranted, RoleRevoked, RootExpiryUpdated, RootRevoked,
+        RootSourceUpdated, VerificationTypeEnabledUpdated, VerificationTypeRegistered,
+        VerifyingKeyActivated, VerifyingKeyActivationDelayUpdated, VerifyingKeyProposed,
+        VerifyingKeyRegistered, VerifyingKeyRetired,
+    },
+    state::{
+        AdminSet, Config, ConfigChange, LatestRoot, PendingConfigChange, PendingVerifyingKey,
+        PreviousVerifyingKey, RelayerAllowlist, Role, Root, RootSource, VerificationTypeConfig,
+        VerifyingKey,
+    },
+};
+
+use super::verify_groth16_proof::{are_g2_points, is_g1_point};
 
 // * Transfer ownership
 // Adapted from https://github.com/wormhole-foundation/example-native-token-transfers/blob/7a5b86ff8c0c36f9b609175d67e5f3651a62d740/solana/programs/example-native-token-transfers/src/instructions/admin.rs
@@ -13,6 +34,7 @@ use crate::{error::SolanaWorldIDProgramError, state::Config};
 ///
 /// The transfer can be cancelled by the existing owner invoking the [`claim_ownership`]
 /// instruction.
+#[event_cpi]
 #[derive(Accounts)]
 pub struct TransferOwnership<'info> {
     #[account(
@@ -49,6 +71,11 @@ pub struct TransferOwnership<'info> {
 pub fn transfer_ownership(ctx: Context<TransferOwnership>) -> Result<()> {
     ctx.accounts.config.pending_owner = Some(ctx.accounts.new_owner.key());
 
+    emit_cpi!(OwnershipTransferStarted {
+        owner: ctx.accounts.owner.key(),
+        pending_owner: ctx.accounts.new_owner.key(),
+    });
+
     bpf_loader_upgradeable::set_upgrade_authority_checked(
         CpiContext::new_with_signer(
             ctx.accounts
@@ -67,6 +94,7 @@ pub fn transfer_ownership(ctx: Context<TransferOwnership>) -> Result<()> {
 
 // * Claim ownership
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct ClaimOwnership<'info> {
     #[account(
@@ -101,9 +129,15 @@ pub struct ClaimOwnership<'info> {
 }
 
 pub fn claim_ownership(ctx: Context<ClaimOwnership>) -> Result<()> {
+    let previous_owner = ctx.accounts.config.owner;
     ctx.accounts.config.pending_owner = None;
     ctx.accounts.config.owner = ctx.accounts.new_owner.key();
 
+    emit_cpi!(OwnershipTransferred {
+        previous_owner,
+        owner: ctx.accounts.new_owner.key(),
+    });
+
     bpf_loader_upgradeable::set_upgrade_authority_checked(
         CpiContext::new_with_signer(
             ctx.accounts
@@ -120,28 +154,1139 @@ pub fn claim_ownership(ctx: Context<ClaimOwnership>) -> Result<()> {
     )
 }
 
-// * Set Root Expiry
+// * Register Verification Type
+
+/// Registers a new verification type with its own LatestRoot.
+///
+/// NOTE: LatestRoot orders roots by block number or VAA sequence, so a different chain or kind of
+/// source must be registered under a new verification type. A change of the source of an existing
+/// one may only replace the contract, selector or emitter address.
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(verification_type: [u8; 1])]
+pub struct RegisterVerificationType<'info> {
+    #[account(mut)]
+    payer: Signer<'info>,
+
+    pub owner: Signer<'info>,
+
+    #[account(
+        has_one = owner,
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + VerificationTypeConfig::INIT_SPACE,
+        seeds = [
+            VerificationTypeConfig::SEED_PREFIX,
+            &verification_type,
+        ],
+        bump
+    )]
+    verification_type_config: Account<'info, VerificationTypeConfig>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + LatestRoot::INIT_SPACE,
+        seeds = [
+            LatestRoot::SEED_PREFIX,
+            &verification_type,
+        ],
+        bump
+    )]
+    latest_root: Account<'info, LatestRoot>,
+
+    system_program: Program<'info, System>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct RegisterVerificationTypeArgs {
+    pub root_expiry: u64,
+    pub allowed_update_staleness: u64,
+    pub source: RootSource,
+}
+
+pub fn register_verification_type(
+    ctx: Context<RegisterVerificationType>,
+    verification_type: [u8; 1],
+    args: RegisterVerificationTypeArgs,
+) -> Result<()> {
+    // Bounded like later changes of these values, see ConfigChange::check_bounds.
+    ConfigChange::RootExpiry {
+        verification_type,
+        root_expiry: args.root_expiry,
+    }
+    .check_bounds()?;
+    ConfigChange::AllowedUpdateStaleness {
+        verification_type,
+        allowed_update_staleness: args.allowed_update_staleness,
+    }
+    .check_bounds()?;
+
+    ctx.accounts
+        .verification_type_config
+        .set_inner(VerificationTypeConfig {
+            bump: ctx.bumps.verification_type_config,
+            verification_type,
+            enabled: true,
+            root_expiry: args.root_expiry,
+            allowed_update_staleness: args.allowed_update_staleness,
+            source: args.source,
+            verifying_key_id: None,
+            pending_verifying_key: None,
+            previous_verifying_key: None,
+        });
+
+    ctx.accounts.latest_root.bump = ctx.bumps.latest_root;
+    ctx.accounts.latest_root.verification_type = verification_type;
+
+    emit_cpi!(VerificationTypeRegistered {
+        verification_type,
+        root_expiry: args.root_expiry,
+        allowed_update_staleness: args.allowed_update_staleness,
+        source: args.source,
+    });
+
+    Ok(())
+}
+
+// * Set Verification Type Enabled
+
+/// A disabled verification type accepts neither root updates nor proofs.
+/// Existing roots may still be cleaned up once expired.
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(verification_type: [u8; 1])]
+pub struct SetVerificationTypeEnabled<'info> {
+    /// The owner or the config manager.
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+        constraint = config.has_role(Role::ConfigManager, &authority.key())
+            @ SolanaWorldIDProgramError::InvalidConfigManager
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [
+            VerificationTypeConfig::SEED_PREFIX,
+            &verification_type,
+        ],
+        bump = verification_type_config.bump
+    )]
+    pub verification_type_config: Account<'info, VerificationTypeConfig>,
+}
+
+pub fn set_verification_type_enabled(
+    ctx: Context<SetVerificationTypeEnabled>,
+    verification_type: [u8; 1],
+    enabled: bool,
+) -> Result<()> {
+    ctx.accounts.verification_type_config.enabled = enabled;
+
+    emit_cpi!(VerificationTypeEnabledUpdated {
+        verification_type,
+        enabled,
+    });
+
+    Ok(())
+}
+
+// * Register Verifying Key
+
+/// Registers a Groth16 verifying key, e.g. for a World ID circuit of another tree depth, which
+/// verification types can then be switched to with [`propose_verifying_key`].
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(key_id: [u8; 1])]
+pub struct RegisterVerifyingKey<'info> {
+    #[account(mut)]
+    payer: Signer<'info>,
+
+    pub owner: Signer<'info>,
+
+    #[account(
+        has_one = owner,
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + VerifyingKey::INIT_SPACE,
+        seeds = [
+            VerifyingKey::SEED_PREFIX,
+            &key_id,
+        ],
+        bump
+    )]
+    verifying_key: Box<Account<'info, VerifyingKey>>,
+
+    system_program: Program<'info, System>,
+}
+
+/// The points of a verifying key, see [`VerifyingKey`].
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct RegisterVerifyingKeyArgs {
+    pub alpha_g1: [u8; 64],
+    pub beta_g2: [u8; 128],
+    pub gamma_g2: [u8; 128],
+    pub delta_g2: [u8; 128],
+    pub ic: [[u8; 64]; 5],
+}
+
+pub fn register_verifying_key(
+    ctx: Context<RegisterVerifyingKey>,
+    key_id: [u8; 1],
+    args: RegisterVerifyingKeyArgs,
+) -> Result<()> {
+    // Otherwise every proof would fail, as if it were invalid.
+    require!(
+        is_g1_point(&args.alpha_g1)
+            && args.ic.iter().all(is_g1_point)
+            && are_g2_points(&[args.beta_g2, args.gamma_g2, args.delta_g2]),
+        SolanaWorldIDProgramError::InvalidVerifyingKey
+    );
+
+    ctx.accounts.verifying_key.set_inner(VerifyingKey {
+        bump: ctx.bumps.verifying_key,
+        key_id,
+        retired: false,
+        alpha_g1: args.alpha_g1,
+        beta_g2: args.beta_g2,
+        gamma_g2: args.gamma_g2,
+        delta_g2: args.delta_g2,
+        ic: args.ic,
+    });
+
+    emit_cpi!(VerifyingKeyRegistered { key_id });
+
+    Ok(())
+}
+
+// * Retire Verifying Key
+
+/// No proof is verified with a retired key, including those of verification types which still use
+/// it, until they are switched to another key. Retirement is permanent, so that a key id always
+/// refers to the same key.
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(key_id: [u8; 1])]
+pub struct RetireVerifyingKey<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        has_one = owner,
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [
+            VerifyingKey::SEED_PREFIX,
+            &key_id,
+        ],
+        bump = verifying_key.bump
+    )]
+    pub verifying_key: Box<Account<'info, VerifyingKey>>,
+}
+
+pub fn retire_verifying_key(ctx: Context<RetireVerifyingKey>, key_id: [u8; 1]) -> Result<()> {
+    ctx.accounts.verifying_key.retired = true;
+
+    emit_cpi!(VerifyingKeyRetired { key_id });
+
+    Ok(())
+}
+
+// * Propose Verifying Key
+
+/// Proposes the key which proofs of a verification type are verified with: the given registered
+/// key, or the built-in key when the `verifying_key` account is omitted. It can be activated with
+/// [`activate_verifying_key`] once the configured activation delay has passed, so that integrators
+/// can prepare for it. A new proposal replaces the pending one and restarts the delay.
+///
+/// For `grace_period` seconds after the activation, proofs are also verified with the key which
+/// was replaced, so that proofs generated before the rotation remain valid.
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(verification_type: [u8; 1])]
+pub struct ProposeVerifyingKey<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        has_one = owner,
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [
+            VerificationTypeConfig::SEED_PREFIX,
+            &verification_type,
+        ],
+        bump = verification_type_config.bump
+    )]
+    pub verification_type_config: Account<'info, VerificationTypeConfig>,
+
+    #[account(
+        constraint = !verifying_key.retired @ SolanaWorldIDProgramError::RetiredVerifyingKey
+    )]
+    pub verifying_key: Option<Box<Account<'info, VerifyingKey>>>,
+}
+
+pub fn propose_verifying_key(
+    ctx: Context<ProposeVerifyingKey>,
+    verification_type: [u8; 1],
+    grace_period: u64,
+) -> Result<()> {
+    let verifying_key_id = ctx.accounts.verifying_key.as_ref().map(|key| key.key_id);
+    let current_timestamp: u64 = Clock::get()?
+        .unix_timestamp
+        .try_into()
+        .expect("timestamp underflow");
+    let activation_time =
+        current_timestamp.saturating_add(ctx.accounts.config.verifying_key_activation_delay);
+    ctx.accounts.verification_type_config.pending_verifying_key = Some(PendingVerifyingKey {
+        key_id: verifying_key_id,
+        activation_time,
+        grace_period,
+    });
+
+    emit_cpi!(VerifyingKeyProposed {
+        verification_type,
+        verifying_key_id,
+        activation_time,
+        grace_period,
+    });
+
+    Ok(())
+}
+
+// * Activate Verifying Key
+
+/// Replaces the key of a verification type with its pending key once the activation delay has
+/// passed. The `verifying_key` account is the pending key, omitted for the built-in key.
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(verification_type: [u8; 1])]
+pub struct ActivateVerifyingKey<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        has_one = owner,
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [
+            VerificationTypeConfig::SEED_PREFIX,
+            &verification_type,
+        ],
+        bump = verification_type_config.bump
+    )]
+    pub verification_type_config: Account<'info, VerificationTypeConfig>,
+
+    /// The pending key may have been retired since it was proposed.
+    #[account(
+        constraint = !verifying_key.retired @ SolanaWorldIDProgramError::RetiredVerifyingKey
+    )]
+    pub verifying_key: Option<Box<Account<'info, VerifyingKey>>>,
+}
+
+pub fn activate_verifying_key(
+    ctx: Context<ActivateVerifyingKey>,
+    verification_type: [u8; 1],
+) -> Result<()> {
+    let verification_type_config = &mut ctx.accounts.verification_type_config;
+    let pending = verification_type_config
+        .pending_verifying_key
+        .take()
+        .ok_or(SolanaWorldIDProgramError::NoPendingVerifyingKey)?;
+    let current_timestamp: u64 = Clock::get()?
+        .unix_timestamp
+        .try_into()
+        .expect("timestamp underflow");
+    require!(
+        current_timestamp >= pending.activation_time,
+        SolanaWorldIDProgramError::VerifyingKeyActivationDelayNotElapsed
+    );
+    let verifying_key_id = ctx.accounts.verifying_key.as_ref().map(|key| key.key_id);
+    if verifying_key_id != pending.key_id {
+        return match verifying_key_id {
+            None => err!(SolanaWorldIDProgramError::VerifyingKeyNotProvided),
+            Some(_) => err!(SolanaWorldIDProgramError::VerifyingKeyMismatch),
+        };
+    }
+
+    let previous_verifying_key_id = verification_type_config.verifying_key_id;
+    let previous_accepted_until =
+        (pending.grace_period > 0).then(|| current_timestamp.saturating_add(pending.grace_period));
+    verification_type_config.verifying_key_id = pending.key_id;
+    verification_type_config.previous_verifying_key =
+        previous_accepted_until.map(|accepted_until| PreviousVerifyingKey {
+            key_id: previous_verifying_key_id,
+            accepted_until,
+        });
+
+    emit_cpi!(VerifyingKeyActivated {
+        verification_type,
+        verifying_key_id,
+        previous_verifying_key_id,
+        previous_accepted_until,
+    });
+
+    Ok(())
+}
+
+// * Grant Role
+
+/// Grants `role` to `grantee`, replacing its current holder, so that it may perform the role's
+/// instructions in addition to the owner.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct GrantRole<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = owner,
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+}
+
+pub fn grant_role(ctx: Context<GrantRole>, role: Role, grantee: Pubkey) -> Result<()> {
+    *ctx.accounts.config.role_mut(role) = Some(grantee);
+
+    emit_cpi!(RoleGranted { role, grantee });
+
+    Ok(())
+}
+
+// * Revoke Role
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RevokeRole<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = owner,
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+}
+
+pub fn revoke_role(ctx: Context<RevokeRole>, role: Role) -> Result<()> {
+    let previous_grantee = ctx.accounts.config.role_mut(role).take();
+
+    emit_cpi!(RoleRevoked {
+        role,
+        previous_grantee,
+    });
+
+    Ok(())
+}
+
+// * Pause
+
+/// Pauses the instructions of `flags`, see [`Config::PAUSE_ROOT_UPDATES`] and the like, in case
+/// e.g. a guardian set is compromised or a bad root has been posted. Paused instructions fail with
+/// their own error until unpaused by the owner.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct Pause<'info> {
+    /// The owner or the pauser.
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+        constraint = config.has_role(Role::Pauser, &authority.key())
+            @ SolanaWorldIDProgramError::InvalidPauser
+    )]
+    pub config: Account<'info, Config>,
+}
+
+pub fn pause(ctx: Context<Pause>, flags: u8) -> Result<()> {
+    require!(
+        flags != 0 && flags & !Config::PAUSE_ALL == 0,
+        SolanaWorldIDProgramError::InvalidPauseFlags
+    );
+    let config = &mut ctx.accounts.config;
+    config.paused |= flags;
+
+    emit_cpi!(PausedUpdated {
+        paused: config.paused,
+    });
+
+    Ok(())
+}
+
+// * Unpause
+#[event_cpi]
+#[derive(Accounts)]
+pub struct Unpause<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = owner,
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+}
+
+pub fn unpause(ctx: Context<Unpause>, flags: u8) -> Result<()> {
+    require!(
+        flags != 0 && flags & !Config::PAUSE_ALL == 0,
+        SolanaWorldIDProgramError::InvalidPauseFlags
+    );
+    let config = &mut ctx.accounts.config;
+    config.paused &= !flags;
+
+    emit_cpi!(PausedUpdated {
+        paused: config.paused,
+    });
+
+    Ok(())
+}
+
+// * Revoke Root
+
+/// Revokes a root, e.g. one announced as erroneous, so that proofs are no longer verified against
+/// it. If it is the latest root and `previous_root` is passed, the latest root is rolled back to
+/// it, which must be an unrevoked, unexpired root of the same verification type read from an older
+/// block. Otherwise the revoked root stays the latest root until the next update.
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(root_hash: [u8; 32], verification_type: [u8; 1])]
+pub struct RevokeRoot<'info> {
+    /// The owner or the root revoker.
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+        constraint = config.has_role(Role::RootRevoker, &authority.key())
+            @ SolanaWorldIDProgramError::InvalidRootRevoker
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [
+            Root::SEED_PREFIX,
+            &root_hash,
+            &verification_type,
+        ],
+        bump = root.bump
+    )]
+    pub root: Account<'info, Root>,
+
+    #[account(
+        mut,
+        seeds = [
+            LatestRoot::SEED_PREFIX,
+            &verification_type,
+        ],
+        bump = latest_root.bump
+    )]
+    pub latest_root: Account<'info, LatestRoot>,
+
+    #[account(
+        seeds = [
+            VerificationTypeConfig::SEED_PREFIX,
+            &verification_type,
+        ],
+        bump = verification_type_config.bump
+    )]
+    pub verification_type_config: Account<'info, VerificationTypeConfig>,
+
+    /// Root which the latest root is rolled back to, if `root` is the latest root.
+    #[account(
+        constraint = previous_root.verification_type == verification_type
+            && previous_root.root != root_hash
+            && !previous_root.revoked @ SolanaWorldIDProgramError::InvalidPreviousRoot
+    )]
+    pub previous_root: Option<Account<'info, Root>>,
+}
+
+pub fn revoke_root(
+    ctx: Context<RevokeRoot>,
+    root_hash: [u8; 32],
+    verification_type: [u8; 1],
+) -> Result<()> {
+    ctx.accounts.root.revoked = true;
+
+    let latest_root = &mut ctx.accounts.latest_root;
+    // Rolling back is optional, so that the latest root can be revoked even when no older root is
+    // still valid. check_root rejects revoked roots, including the latest one.
+    let rolled_back_to = match &ctx.accounts.previous_root {
+        Some(previous_root) if latest_root.root == root_hash => {
+            // Roll back only to an older root which would still be accepted if it weren't the
+            // latest, so that revoking cannot reinstate a stale root indefinitely.
+            require!(
+                previous_root.read_block_number < latest_root.read_block_number,
+                SolanaWorldIDProgramError::PreviousRootNotOlder
+            );
+            let current_timestamp = Clock::get()?
+                .unix_timestamp
+                .try_into()
+                .expect("timestamp underflow");
+            require!(
+                previous_root.is_active(
+                    &current_timestamp,
+                    &ctx.accounts.verification_type_config.root_expiry
+                ),
+                SolanaWorldIDProgramError::PreviousRootExpired
+            );
+
+            latest_root.read_block_number = previous_root.read_block_number;
+            latest_root.read_block_hash = previous_root.read_block_hash;
+            latest_root.read_block_time = previous_root.read_block_time;
+            latest_root.root = previous_root.root;
+            Some(previous_root.root)
+        }
+        _ => None,
+    };
+
+    emit_cpi!(RootRevoked {
+        root: root_hash,
+        verification_type,
+        latest_root: rolled_back_to,
+    });
+
+    Ok(())
+}
+
+// * Propose Config Change
+
+/// Proposes a change of a configured value, which takes effect with [`execute_config_change`] once
+/// the config change delay has passed, so that integrators can react to it. Values outside of
+/// their bounds are rejected. A pending change must be executed or cancelled before the same value
+/// can be changed again.
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(change: ConfigChange)]
+pub struct ProposeConfigChange<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The owner, or the config manager unless the change is owner-only.
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+        constraint = config.has_role(Role::ConfigManager, &authority.key())
+            @ SolanaWorldIDProgramError::InvalidConfigManager,
+        constraint = !change.is_owner_only() || authority.key() == config.owner
+            @ SolanaWorldIDProgramError::OwnerOnlyConfigChange
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + PendingConfigChange::INIT_SPACE,
+        seeds = [
+            PendingConfigChange::SEED_PREFIX,
+            &change.seed(),
+        ],
+        bump
+    )]
+    pub pending_config_change: Account<'info, PendingConfigChange>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn propose_config_change(
+    ctx: Context<ProposeConfigChange>,
+    change: ConfigChange,
+) -> Result<()> {
+    change.check_bounds()?;
+    let current_timestamp: u64 = Clock::get()?
+        .unix_timestamp
+        .try_into()
+        .expect("timestamp underflow");
+    let executable_at = current_timestamp.saturating_add(ctx.accounts.config.config_change_delay);
+    ctx.accounts
+        .pending_config_change
+        .set_inner(PendingConfigChange {
+            bump: ctx.bumps.pending_config_change,
+            change,
+            executable_at,
+            refund_recipient: ctx.accounts.payer.key(),
+        });
+
+    emit_cpi!(ConfigChangeProposed {
+        change,
+        executable_at,
+    });
+
+    Ok(())
+}
+
+// * Execute Config Change
+#[event_cpi]
 #[derive(Accounts)]
-pub struct SetRootExpiry<'info> {
+pub struct ExecuteConfigChange<'info> {
+    /// The owner, or the config manager unless the change is owner-only.
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+        constraint = config.has_role(Role::ConfigManager, &authority.key())
+            @ SolanaWorldIDProgramError::InvalidConfigManager
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        has_one = refund_recipient,
+        close = refund_recipient,
+        constraint = !pending_config_change.change.is_owner_only()
+            || authority.key() == config.owner
+            @ SolanaWorldIDProgramError::OwnerOnlyConfigChange
+    )]
+    pub pending_config_change: Account<'info, PendingConfigChange>,
+
+    /// Config of the changed verification type. Omitted for changes of the program config.
+    #[account(mut)]
+    pub verification_type_config: Option<Account<'info, VerificationTypeConfig>>,
+
+    /// CHECK: This account is the refund recipient for the above pending config change.
+    #[account(mut, address = pending_config_change.refund_recipient)]
+    pub refund_recipient: AccountInfo<'info>,
+}
+
+pub fn execute_config_change(ctx: Context<ExecuteConfigChange>) -> Result<()> {
+    let pending_config_change = &ctx.accounts.pending_config_change;
+    let current_timestamp: u64 = Clock::get()?
+        .unix_timestamp
+        .try_into()
+        .expect("timestamp underflow");
+    require!(
+        current_timestamp >= pending_config_change.executable_at,
+        SolanaWorldIDProgramError::ConfigChangeDelayNotElapsed
+    );
+
+    let change = pending_config_change.change;
+    if let Some(verification_type) = change.verification_type() {
+        let verification_type_config = ctx
+            .accounts
+            .verification_type_config
+            .as_mut()
+            .ok_or(SolanaWorldIDProgramError::VerificationTypeConfigNotProvided)?;
+        require!(
+            verification_type_config.verification_type == verification_type,
+            SolanaWorldIDProgramError::VerificationTypeConfigMismatch
+        );
+        match change {
+            ConfigChange::RootExpiry { root_expiry, .. } => {
+                verification_type_config.root_expiry = root_expiry;
+                emit_cpi!(RootExpiryUpdated {
+                    verification_type,
+                    root_expiry,
+                });
+            }
+            ConfigChange::AllowedUpdateStaleness {
+                allowed_update_staleness,
+                ..
+            } => {
+                verification_type_config.allowed_update_staleness = allowed_update_staleness;
+                emit_cpi!(AllowedUpdateStalenessUpdated {
+                    verification_type,
+                    allowed_update_staleness,
+                });
+            }
+            ConfigChange::RootSource { source, .. } => {
+                // LatestRoot keeps the block number or VAA sequence of the current source, which
+                // those of another chain or kind of source cannot be compared with.
+                require!(
+                    verification_type_config
+                        .source
+                        .has_same_chain_and_kind(&source),
+                    SolanaWorldIDProgramError::RootSourceChainMismatch
+                );
+                verification_type_config.source = source;
+                emit_cpi!(RootSourceUpdated {
+                    verification_type,
+                    source,
+                });
+            }
+            _ => unreachable!("change of the program config"),
+        }
+    } else {
+        let config = &mut ctx.accounts.config;
+        match change {
+            ConfigChange::VerifyingKeyActivationDelay {
+                verifying_key_activation_delay,
+            } => {
+                config.verifying_key_activation_delay = verifying_key_activation_delay;
+                emit_cpi!(VerifyingKeyActivationDelayUpdated {
+                    verifying_key_activation_delay,
+                });
+            }
+            ConfigChange::ConfigChangeDelay {
+                config_change_delay,
+            } => {
+                config.config_change_delay = config_change_delay;
+                emit_cpi!(ConfigChangeDelayUpdated {
+                    config_change_delay,
+                });
+            }
+            _ => unreachable!("change of a verification type"),
+        }
+    }
+
+    Ok(())
+}
+
+// * Cancel Config Change
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CancelConfigChange<'info> {
+    /// The owner, or the config manager unless the change is owner-only.
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+        constraint = config.has_role(Role::ConfigManager, &authority.key())
+            @ SolanaWorldIDProgramError::InvalidConfigManager
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        has_one = refund_recipient,
+        close = refund_recipient,
+        constraint = !pending_config_change.change.is_owner_only()
+            || authority.key() == config.owner
+            @ SolanaWorldIDProgramError::OwnerOnlyConfigChange
+    )]
+    pub pending_config_change: Account<'info, PendingConfigChange>,
+
+    /// CHECK: This account is the refund recipient for the above pending config change.
+    #[account(mut, address = pending_config_change.refund_recipient)]
+    pub refund_recipient: AccountInfo<'info>,
+}
+
+pub fn cancel_config_change(ctx: Context<CancelConfigChange>) -> Result<()> {
+    emit_cpi!(ConfigChangeCancelled {
+        change: ctx.accounts.pending_config_change.change,
+    });
+
+    Ok(())
+}
+
+// * Initialize Admin Set
+
+/// Creates the [`AdminSet`], whose PDA becomes the owner when ownership is transferred to it with
+/// [`transfer_ownership`] and claimed with [`execute_as_admin_set`]. From then on, every owner
+/// instruction requires `threshold` distinct members to sign.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct InitializeAdminSet<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
     pub owner: Signer<'info>,
 
+    #[account(
+        has_one = owner,
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + AdminSet::INIT_SPACE,
+        seeds = [AdminSet::SEED_PREFIX],
+        bump
+    )]
+    pub admin_set: Account<'info, AdminSet>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_admin_set(
+    ctx: Context<InitializeAdminSet>,
+    members: Vec<Pubkey>,
+    threshold: u8,
+) -> Result<()> {
+    let admin_set = &mut ctx.accounts.admin_set;
+    admin_set.set_inner(AdminSet {
+        bump: ctx.bumps.admin_set,
+        threshold,
+        members,
+    });
+    admin_set.check()?;
+
+    emit_cpi!(AdminSetUpdated {
+        members: admin_set.members.clone(),
+        threshold: admin_set.threshold,
+    });
+
+    Ok(())
+}
+
+// * Update Admin Set
+
+/// The admin set is only updated with its own signature, i.e. with [`execute_as_admin_set`].
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateAdminSet<'info> {
     #[account(
         mut,
+        signer,
+        seeds = [AdminSet::SEED_PREFIX],
+        bump = admin_set.bump
+    )]
+    pub admin_set: Account<'info, AdminSet>,
+}
+
+pub fn add_admin_set_member(ctx: Context<UpdateAdminSet>, member: Pubkey) -> Result<()> {
+    let admin_set = &mut ctx.accounts.admin_set;
+    admin_set.members.push(member);
+    admin_set.check()?;
+
+    emit_cpi!(AdminSetUpdated {
+        members: admin_set.members.clone(),
+        threshold: admin_set.threshold,
+    });
+
+    Ok(())
+}
+
+/// Fails if the threshold could no longer be met, in which case it must be lowered first.
+pub fn remove_admin_set_member(ctx: Context<UpdateAdminSet>, member: Pubkey) -> Result<()> {
+    let admin_set = &mut ctx.accounts.admin_set;
+    let index = admin_set
+        .members
+        .iter()
+        .position(|m| *m == member)
+        .ok_or(SolanaWorldIDProgramError::AdminSetMemberNotFound)?;
+    admin_set.members.remove(index);
+    admin_set.check()?;
+
+    emit_cpi!(AdminSetUpdated {
+        members: admin_set.members.clone(),
+        threshold: admin_set.threshold,
+    });
+
+    Ok(())
+}
+
+pub fn set_admin_set_threshold(ctx: Context<UpdateAdminSet>, threshold: u8) -> Result<()> {
+    let admin_set = &mut ctx.accounts.admin_set;
+    admin_set.threshold = threshold;
+    admin_set.check()?;
+
+    emit_cpi!(AdminSetUpdated {
+        members: admin_set.members.clone(),
+        threshold,
+    });
+
+    Ok(())
+}
+
+// * Execute As Admin Set
+
+/// Invokes an instruction of `target_program` with the signature of the admin set PDA, once
+/// `threshold` distinct members have signed this transaction.
+///
+/// The remaining accounts are the accounts of the invoked instruction, followed by the signing
+/// members. The admin set PDA is marked as a signer wherever it appears.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ExecuteAsAdminSet<'info> {
+    // Not mutable here, so that changes made by the invoked instruction are not overwritten.
+    #[account(
+        seeds = [AdminSet::SEED_PREFIX],
+        bump = admin_set.bump
+    )]
+    pub admin_set: Account<'info, AdminSet>,
+
+    /// CHECK: Any program, e.g. this program or the BPF loader for upgrades.
+    #[account(executable)]
+    pub target_program: AccountInfo<'info>,
+}
+
+pub fn execute_as_admin_set<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ExecuteAsAdminSet<'info>>,
+    data: Vec<u8>,
+) -> Result<()> {
+    let admin_set = &ctx.accounts.admin_set;
+    let signers = admin_set.signers(ctx.remaining_accounts);
+    require!(
+        signers.len() >= usize::from(admin_set.threshold),
+        SolanaWorldIDProgramError::AdminSetThresholdNotMet
+    );
+
+    let admin_set_key = admin_set.key();
+    let instruction = Instruction {
+        program_id: ctx.accounts.target_program.key(),
+        accounts: ctx
+            .remaining_accounts
+            .iter()
+            .map(|account| AccountMeta {
+                pubkey: account.key(),
+                is_signer: account.is_signer || account.key() == admin_set_key,
+                is_writable: account.is_writable,
+            })
+            .collect(),
+        data,
+    };
+    let mut account_infos = ctx.remaining_accounts.to_vec();
+    account_infos.push(ctx.accounts.target_program.clone());
+    invoke_signed(
+        &instruction,
+        &account_infos,
+        &[&[AdminSet::SEED_PREFIX, &[admin_set.bump]]],
+    )?;
+
+    emit_cpi!(AdminSetExecuted {
+        program: instruction.program_id,
+        signers,
+    });
+
+    Ok(())
+}
+
+// * Initialize Relayer Allowlist
+
+/// Creates the [`RelayerAllowlist`], which only restricts who may update roots with a
+/// QueryResponse once enabled with [`set_relayer_allowlist_enabled`].
+#[event_cpi]
+#[derive(Accounts)]
+pub struct InitializeRelayerAllowlist<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub owner: Signer<'info>,
+
+    #[account(
+        has_one = owner,
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + RelayerAllowlist::INIT_SPACE,
+        seeds = [RelayerAllowlist::SEED_PREFIX],
+        bump
+    )]
+    pub relayer_allowlist: Account<'info, RelayerAllowlist>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_relayer_allowlist(
+    ctx: Context<InitializeRelayerAllowlist>,
+    relayers: Vec<Pubkey>,
+) -> Result<()> {
+    let relayer_allowlist = &mut ctx.accounts.relayer_allowlist;
+    relayer_allowlist.set_inner(RelayerAllowlist {
+        bump: ctx.bumps.relayer_allowlist,
+        relayers,
+    });
+    relayer_allowlist.check()?;
+
+    emit_cpi!(RelayerAllowlistUpdated {
+        relayers: relayer_allowlist.relayers.clone(),
+    });
+
+    Ok(())
+}
+
+// * Update Relayer Allowlist
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateRelayerAllowlist<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
         has_one = owner,
         seeds = [Config::SEED_PREFIX],
         bump = config.bump
     )]
     pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [RelayerAllowlist::SEED_PREFIX],
+        bump = relayer_allowlist.bump
+    )]
+    pub relayer_allowlist: Account<'info, RelayerAllowlist>,
 }
 
-pub fn set_root_expiry(ctx: Context<SetRootExpiry>, root_expiry: u64) -> Result<()> {
-    ctx.accounts.config.root_expiry = root_expiry;
+pub fn add_relayer(ctx: Context<UpdateRelayerAllowlist>, relayer: Pubkey) -> Result<()> {
+    let relayer_allowlist = &mut ctx.accounts.relayer_allowlist;
+    relayer_allowlist.relayers.push(relayer);
+    relayer_allowlist.check()?;
+
+    emit_cpi!(RelayerAllowlistUpdated {
+        relayers: relayer_allowlist.relayers.clone(),
+    });
+
+    Ok(())
+}
+
+/// Roots already relayed by a removed relayer are unaffected.
+pub fn remove_relayer(ctx: Context<UpdateRelayerAllowlist>, relayer: Pubkey) -> Result<()> {
+    let relayer_allowlist = &mut ctx.accounts.relayer_allowlist;
+    let index = relayer_allowlist
+        .relayers
+        .iter()
+        .position(|r| *r == relayer)
+        .ok_or(SolanaWorldIDProgramError::RelayerNotFound)?;
+    relayer_allowlist.relayers.remove(index);
+
+    emit_cpi!(RelayerAllowlistUpdated {
+        relayers: relayer_allowlist.relayers.clone(),
+    });
+
     Ok(())
 }
 
-// * Set Allowed Update Staleness
+// * Set Relayer Allowlist Enabled
+
+/// Requires the relayer allowlist, so that it is initialized before it is enabled.
+#[event_cpi]
 #[derive(Accounts)]
-pub struct SetAllowedUpdateStaleness<'info> {
+pub struct SetRelayerAllowlistEnabled<'info> {
     pub owner: Signer<'info>,
 
     #[account(
@@ -151,12 +1296,21 @@ pub struct SetAllowedUpdateStaleness<'info> {
         bump = config.bump
     )]
     pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [RelayerAllowlist::SEED_PREFIX],
+        bump = relayer_allowlist.bump
+    )]
+    pub relayer_allowlist: Account<'info, RelayerAllowlist>,
 }
 
-pub fn set_allowed_update_staleness(
-    ctx: Context<SetAllowedUpdateStaleness>,
-    allowed_update_staleness: u64,
+pub fn set_relayer_allowlist_enabled(
+    ctx: Context<SetRelayerAllowlistEnabled>,
+    enabled: bool,
 ) -> Result<()> {
-    ctx.accounts.config.allowed_update_staleness = allowed_update_staleness;
+    ctx.accounts.config.relayer_allowlist_enabled = enabled;
+
+    emit_cpi!(RelayerAllowlistEnabledUpdated { enabled });
+
     Ok(())
 }
@@ -1,9 +1,11 @@
 use crate::{
     error::SolanaWorldIDProgramError,
-    state::{Config, LatestRoot, Root},
+    event::RootCleanedUp,
+    state::{Config, LatestRoot, Root, VerificationTypeConfig},
 };
 use anchor_lang::prelude::*;
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct CleanUpRoot<'info> {
     /// This can be any expired root account.
@@ -28,10 +30,21 @@ pub struct CleanUpRoot<'info> {
 
     #[account(
         seeds = [Config::SEED_PREFIX],
-        bump = config.bump
+        bump = config.bump,
+        constraint = !config.is_paused(Config::PAUSE_CLEANUP) @ SolanaWorldIDProgramError::CleanupPaused
     )]
     config: Account<'info, Config>,
 
+    /// Config of the matching verification type
+    #[account(
+        seeds = [
+            VerificationTypeConfig::SEED_PREFIX,
+            &root.verification_type,
+        ],
+        bump = verification_type_config.bump
+    )]
+    verification_type_config: Account<'info, VerificationTypeConfig>,
+
     /// CHECK: This account is the refund recipient for the above root.
     #[account(mut, address = root.refund_recipient)]
     refund_recipient: AccountInfo<'info>,
@@ -41,7 +54,7 @@ impl<'info> CleanUpRoot<'info> {
     pub fn constraints(ctx: &Context<Self>) -> Result<()> {
         let root = &ctx.accounts.root;
         let latest_root = &ctx.accounts.latest_root;
-        let config = &ctx.accounts.config;
+        let verification_type_config = &ctx.accounts.verification_type_config;
 
         // The latest root cannot be cleaned up, as it is always considered valid
         require!(
@@ -49,13 +62,14 @@ impl<'info> CleanUpRoot<'info> {
             SolanaWorldIDProgramError::RootIsLatest
         );
 
-        // Check that the root has expired.
+        // Check that the root has expired, unless it was revoked.
         let current_timestamp = Clock::get()?
             .unix_timestamp
             .try_into()
             .expect("timestamp underflow");
         require!(
-            !root.is_active(&current_timestamp, &config.root_expiry),
+            root.revoked
+                || !root.is_active(&current_timestamp, &verification_type_config.root_expiry),
             SolanaWorldIDProgramError::RootUnexpired
         );
 
@@ -65,5 +79,11 @@ impl<'info> CleanUpRoot<'info> {
 
 #[access_control(CleanUpRoot::constraints(&ctx))]
 pub fn clean_up_root(ctx: Context<CleanUpRoot>) -> Result<()> {
+    emit_cpi!(RootCleanedUp {
+        root: ctx.accounts.root.root,
+        verification_type: ctx.accounts.root.verification_type,
+        refund_recipient: ctx.accounts.refund_recipient.key(),
+    });
+
     Ok(())
 }
@@ -1,7 +1,8 @@
 use anchor_lang::prelude::*;
 
-use crate::state::GuardianSignatures;
+use crate::{event::SignaturesClosed, state::GuardianSignatures};
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct CloseSignatures<'info> {
     #[account(mut, has_one = refund_recipient, close = refund_recipient)]
@@ -12,6 +13,11 @@ pub struct CloseSignatures<'info> {
 }
 
 /// Allows the initial payer to close the signature account in case the query was invalid.
-pub fn close_signatures(_ctx: Context<CloseSignatures>) -> Result<()> {
+pub fn close_signatures(ctx: Context<CloseSignatures>) -> Result<()> {
+    emit_cpi!(SignaturesClosed {
+        guardian_signatures: ctx.accounts.guardian_signatures.key(),
+        refund_recipient: ctx.accounts.refund_recipient.key(),
+    });
+
     Ok(())
 }
@@ -1,6 +1,14 @@
 use anchor_lang::{prelude::*, solana_program::bpf_loader_upgradeable};
 
-use crate::state::{Config, LatestRoot, Root};
+use crate::state::{Config, ConfigChange, LatestRoot, Root, RootSource, VerificationTypeConfig};
+
+use super::{ETH_CHAIN_ID, ETH_WORLD_ID_IDENTITY_MANAGER, LATEST_ROOT_SIGNATURE};
+
+/// Time (in seconds) after which a proposed verifying key may be activated, unless configured.
+pub const DEFAULT_VERIFYING_KEY_ACTIVATION_DELAY: u64 = 24 * 60 * 60;
+
+/// Time (in seconds) after which a proposed config change may be executed, unless configured.
+pub const DEFAULT_CONFIG_CHANGE_DELAY: u64 = 24 * 60 * 60;
 
 #[derive(Accounts)]
 #[instruction(args: InitializeArgs)]
@@ -39,6 +47,18 @@ pub struct Initialize<'info> {
     )]
     latest_root: Account<'info, LatestRoot>,
 
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + VerificationTypeConfig::INIT_SPACE,
+        seeds = [
+            VerificationTypeConfig::SEED_PREFIX,
+            Root::VERIFICATION_TYPE_QUERY,
+        ],
+        bump
+    )]
+    verification_type_config: Account<'info, VerificationTypeConfig>,
+
     system_program: Program<'info, System>,
 }
 
@@ -46,19 +66,81 @@ pub struct Initialize<'info> {
 pub struct InitializeArgs {
     pub root_expiry: u64,
     pub allowed_update_staleness: u64,
+    /// Defaults to `ETH_CHAIN_ID` for the network feature the program was built with.
+    pub eth_chain_id: Option<u16>,
+    /// Defaults to `ETH_WORLD_ID_IDENTITY_MANAGER` for the network feature the program was built with.
+    pub eth_world_id_identity_manager: Option<[u8; 20]>,
+    /// Defaults to `LATEST_ROOT_SIGNATURE`.
+    pub latest_root_signature: Option<[u8; 4]>,
+    /// Defaults to `DEFAULT_VERIFYING_KEY_ACTIVATION_DELAY`.
+    pub verifying_key_activation_delay: Option<u64>,
+    /// Defaults to `DEFAULT_CONFIG_CHANGE_DELAY`.
+    pub config_change_delay: Option<u64>,
 }
 
 pub fn initialize(ctx: Context<Initialize>, args: InitializeArgs) -> Result<()> {
+    let verifying_key_activation_delay = args
+        .verifying_key_activation_delay
+        .unwrap_or(DEFAULT_VERIFYING_KEY_ACTIVATION_DELAY);
+    let config_change_delay = args
+        .config_change_delay
+        .unwrap_or(DEFAULT_CONFIG_CHANGE_DELAY);
+
+    // Bounded like later changes of these values, see ConfigChange::check_bounds.
+    for change in [
+        ConfigChange::RootExpiry {
+            verification_type: *Root::VERIFICATION_TYPE_QUERY,
+            root_expiry: args.root_expiry,
+        },
+        ConfigChange::AllowedUpdateStaleness {
+            verification_type: *Root::VERIFICATION_TYPE_QUERY,
+            allowed_update_staleness: args.allowed_update_staleness,
+        },
+        ConfigChange::VerifyingKeyActivationDelay {
+            verifying_key_activation_delay,
+        },
+        ConfigChange::ConfigChangeDelay {
+            config_change_delay,
+        },
+    ] {
+        change.check_bounds()?;
+    }
+
     ctx.accounts.config.set_inner(Config {
         bump: ctx.bumps.config,
         owner: ctx.accounts.deployer.key(),
         pending_owner: None,
-        root_expiry: args.root_expiry,
-        allowed_update_staleness: args.allowed_update_staleness,
+        verifying_key_activation_delay,
+        paused: 0,
+        pauser: None,
+        config_change_delay,
+        config_manager: None,
+        root_revoker: None,
+        relayer_allowlist_enabled: false,
     });
 
     ctx.accounts.latest_root.bump = ctx.bumps.latest_root;
     ctx.accounts.latest_root.verification_type = *Root::VERIFICATION_TYPE_QUERY;
 
+    ctx.accounts
+        .verification_type_config
+        .set_inner(VerificationTypeConfig {
+            bump: ctx.bumps.verification_type_config,
+            verification_type: *Root::VERIFICATION_TYPE_QUERY,
+            enabled: true,
+            root_expiry: args.root_expiry,
+            allowed_update_staleness: args.allowed_update_staleness,
+            source: RootSource::Query {
+                chain_id: args.eth_chain_id.unwrap_or(ETH_CHAIN_ID),
+                contract: args
+                    .eth_world_id_identity_manager
+                    .unwrap_or(ETH_WORLD_ID_IDENTITY_MANAGER),
+                selector: args.latest_root_signature.unwrap_or(LATEST_ROOT_SIGNATURE),
+            },
+            verifying_key_id: None,
+            pending_verifying_key: None,
+            previous_verifying_key: None,
+        });
+
     Ok(())
 }
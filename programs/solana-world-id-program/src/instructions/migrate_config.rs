@@ -0,0 +1,135 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{self, Transfer},
+    Discriminator,
+};
+
+use crate::{
+    error::SolanaWorldIDProgramError,
+    state::{Config, LegacyConfig, Root, RootSource, VerificationTypeConfig},
+};
+
+use super::{
+    DEFAULT_CONFIG_CHANGE_DELAY, DEFAULT_VERIFYING_KEY_ACTIVATION_DELAY, ETH_CHAIN_ID,
+    ETH_WORLD_ID_IDENTITY_MANAGER, LATEST_ROOT_SIGNATURE,
+};
+
+/// Migrates a config initialized before verification types were introduced, so that a deployed
+/// program can be upgraded in place. The config is reallocated to the current layout and the
+/// `VerificationTypeConfig` of `Root::VERIFICATION_TYPE_QUERY` is created from its `root_expiry`
+/// and `allowed_update_staleness`. Every other value starts at its default.
+#[derive(Accounts)]
+pub struct MigrateConfig<'info> {
+    #[account(mut)]
+    payer: Signer<'info>,
+
+    /// Owner stored in the legacy config.
+    owner: Signer<'info>,
+
+    /// CHECK: The legacy config cannot be deserialized as a `Config`, so its discriminator, size
+    /// and owner are checked by migrate_config.
+    #[account(
+        mut,
+        seeds = [Config::SEED_PREFIX],
+        bump
+    )]
+    config: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + VerificationTypeConfig::INIT_SPACE,
+        seeds = [
+            VerificationTypeConfig::SEED_PREFIX,
+            Root::VERIFICATION_TYPE_QUERY,
+        ],
+        bump
+    )]
+    verification_type_config: Account<'info, VerificationTypeConfig>,
+
+    system_program: Program<'info, System>,
+}
+
+pub fn migrate_config(ctx: Context<MigrateConfig>) -> Result<()> {
+    let config_info = ctx.accounts.config.to_account_info();
+    let legacy_config = {
+        let data = config_info.try_borrow_data()?;
+        require!(
+            data.len() == 8 + LegacyConfig::INIT_SPACE && data.starts_with(&Config::DISCRIMINATOR),
+            SolanaWorldIDProgramError::ConfigAlreadyMigrated
+        );
+        LegacyConfig::deserialize(&mut &data[8..])?
+    };
+    require_keys_eq!(
+        ctx.accounts.owner.key(),
+        legacy_config.owner,
+        SolanaWorldIDProgramError::InvalidLegacyConfigOwner
+    );
+
+    realloc(
+        &config_info,
+        8 + Config::INIT_SPACE,
+        &ctx.accounts.payer,
+        &ctx.accounts.system_program,
+    )?;
+
+    let config = Config {
+        bump: legacy_config.bump,
+        owner: legacy_config.owner,
+        pending_owner: legacy_config.pending_owner,
+        verifying_key_activation_delay: DEFAULT_VERIFYING_KEY_ACTIVATION_DELAY,
+        paused: 0,
+        pauser: None,
+        config_change_delay: DEFAULT_CONFIG_CHANGE_DELAY,
+        config_manager: None,
+        root_revoker: None,
+        relayer_allowlist_enabled: false,
+    };
+    config.try_serialize(&mut &mut config_info.try_borrow_mut_data()?[..])?;
+
+    ctx.accounts
+        .verification_type_config
+        .set_inner(VerificationTypeConfig {
+            bump: ctx.bumps.verification_type_config,
+            verification_type: *Root::VERIFICATION_TYPE_QUERY,
+            enabled: true,
+            root_expiry: legacy_config.root_expiry,
+            allowed_update_staleness: legacy_config.allowed_update_staleness,
+            source: RootSource::Query {
+                chain_id: ETH_CHAIN_ID,
+                contract: ETH_WORLD_ID_IDENTITY_MANAGER,
+                selector: LATEST_ROOT_SIGNATURE,
+            },
+            verifying_key_id: None,
+            pending_verifying_key: None,
+            previous_verifying_key: None,
+        });
+
+    Ok(())
+}
+
+/// Grows `account` to `space` bytes, zeroing the new bytes, after topping up its rent from `payer`.
+pub(crate) fn realloc<'info>(
+    account: &AccountInfo<'info>,
+    space: usize,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    let lamports = Rent::get()?
+        .minimum_balance(space)
+        .saturating_sub(account.lamports());
+    if lamports > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                Transfer {
+                    from: payer.to_account_info(),
+                    to: account.clone(),
+                },
+            ),
+            lamports,
+        )?;
+    }
+    account.realloc(space, true)?;
+    Ok(())
+}
@@ -0,0 +1,39 @@
+use anchor_lang::{prelude::*, Discriminator};
+
+use crate::{error::SolanaWorldIDProgramError, state::Root};
+
+use super::migrate_config::realloc;
+
+/// Migrates a root created before roots could be revoked, which lacks the trailing `revoked`
+/// flag and cannot be deserialized until it is reallocated. The added byte reads as `false`.
+/// Anyone may pay for the migration.
+#[derive(Accounts)]
+pub struct MigrateRoot<'info> {
+    #[account(mut)]
+    payer: Signer<'info>,
+
+    /// CHECK: The legacy root cannot be deserialized as a `Root`, so its discriminator and size
+    /// are checked by migrate_root. The PDA check is omitted as in clean_up_root.
+    #[account(mut, owner = crate::ID)]
+    root: UncheckedAccount<'info>,
+
+    system_program: Program<'info, System>,
+}
+
+pub fn migrate_root(ctx: Context<MigrateRoot>) -> Result<()> {
+    let root_info = ctx.accounts.root.to_account_info();
+    {
+        let data = root_info.try_borrow_data()?;
+        require!(
+            data.len() == 8 + Root::INIT_SPACE - 1 && data.starts_with(&Root::DISCRIMINATOR),
+            SolanaWorldIDProgramError::RootAlreadyMigrated
+        );
+    }
+
+    realloc(
+        &root_info,
+        8 + Root::INIT_SPACE,
+        &ctx.accounts.payer,
+        &ctx.accounts.system_program,
+    )
+}
@@ -10,11 +10,32 @@ pub use close_signatures::*;
 mod initialize;
 pub use initialize::*;
 
+mod migrate_config;
+pub use migrate_config::*;
+
+mod migrate_root;
+pub use migrate_root::*;
+
 mod post_signatures;
 pub use post_signatures::*;
 
 mod update_root_with_query;
 pub use update_root_with_query::*;
 
+mod update_root_with_query_precompile;
+pub use update_root_with_query_precompile::*;
+
+mod update_root_with_vaa;
+pub use update_root_with_vaa::*;
+
+mod verify_and_consume_nullifier;
+pub use verify_and_consume_nullifier::*;
+
 mod verify_groth16_proof;
 pub use verify_groth16_proof::*;
+
+mod verify_groth16_proof_with_raw_inputs;
+pub use verify_groth16_proof_with_raw_inputs::*;
+
+mod verify_groth16_proofs;
+pub use verify_groth16_proofs::*;
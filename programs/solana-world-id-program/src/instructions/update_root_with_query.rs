@@ -1,6 +1,10 @@
 use crate::{
     error::SolanaWorldIDProgramError,
-    state::{Config, GuardianSignatures, LatestRoot, Root, WormholeGuardianSet},
+    event::RootUpdated,
+    state::{
+        Config, GuardianSignatures, LatestRoot, RelayerAllowlist, Root, RootSource,
+        VerificationTypeConfig, WormholeGuardianSet,
+    },
 };
 use anchor_lang::{
     prelude::*,
@@ -15,13 +19,8 @@ use wormhole_query_sdk::{
 use wormhole_raw_vaas::{utils::quorum, GuardianSetSig};
 use wormhole_solana_consts::CORE_BRIDGE_PROGRAM_ID;
 
-// TODO: move to config acct so these are more easily visible to the public
-// Or consider a view method
-// https://www.anchor-lang.com/docs/cross-program-invocations#reading-return-data-in-the-clients
-// e.g.
-// https://github.com/wormhole-foundation/example-native-token-transfers/blob/c0b0e69c03b3f83ce5a0f8d676d6a82a82443c1a/solana/programs/example-native-token-transfers/src/lib.rs#L80C12-L80C19
-// https://github.com/wormhole-foundation/example-native-token-transfers/blob/c0b0e69c03b3f83ce5a0f8d676d6a82a82443c1a/solana/ts/lib/ntt.ts#L156
-
+// Defaults for the source of Root::VERIFICATION_TYPE_QUERY set during initialize.
+// These can be changed with a ConfigChange::RootSource, e.g. to point at a staging deployment.
 cfg_if::cfg_if! {
     if #[cfg(feature = "mainnet")] {
         // https://docs.wormhole.com/wormhole/reference/constants
@@ -52,8 +51,9 @@ fn test_latest_root_signature() {
     assert_eq!(LATEST_ROOT_SIGNATURE, hash[0..4]);
 }
 
+#[event_cpi]
 #[derive(Accounts)]
-#[instruction(bytes: Vec<u8>, root_hash: [u8; 32], guardian_set_index: u32)]
+#[instruction(bytes: Vec<u8>, root_hash: [u8; 32], verification_type: [u8; 1], guardian_set_index: u32)]
 pub struct UpdateRootWithQuery<'info> {
     #[account(mut)]
     payer: Signer<'info>,
@@ -80,7 +80,7 @@ pub struct UpdateRootWithQuery<'info> {
         seeds = [
             Root::SEED_PREFIX,
             &root_hash,
-            Root::VERIFICATION_TYPE_QUERY,
+            &verification_type,
         ],
         bump
     )]
@@ -90,7 +90,7 @@ pub struct UpdateRootWithQuery<'info> {
         mut,
         seeds = [
             LatestRoot::SEED_PREFIX,
-            Root::VERIFICATION_TYPE_QUERY,
+            &verification_type,
         ],
         bump = latest_root.bump
     )]
@@ -98,10 +98,25 @@ pub struct UpdateRootWithQuery<'info> {
 
     #[account(
         seeds = [Config::SEED_PREFIX],
-        bump = config.bump
+        bump = config.bump,
+        constraint = !config.is_paused(Config::PAUSE_ROOT_UPDATES) @ SolanaWorldIDProgramError::RootUpdatesPaused
     )]
     config: Account<'info, Config>,
 
+    /// Only required while the relayer allowlist is enabled. There is only one, so it is not checked
+    /// against its seeds, which would also make clients derive it when it does not exist.
+    relayer_allowlist: Option<Account<'info, RelayerAllowlist>>,
+
+    /// Source, staleness, and enabled flag for roots of this verification type.
+    #[account(
+        seeds = [
+            VerificationTypeConfig::SEED_PREFIX,
+            &verification_type,
+        ],
+        bump = verification_type_config.bump
+    )]
+    verification_type_config: Account<'info, VerificationTypeConfig>,
+
     /// CHECK: This account is the refund recipient for the above signature_set
     #[account(mut, address = guardian_signatures.refund_recipient)]
     refund_recipient: AccountInfo<'info>,
@@ -111,6 +126,12 @@ pub struct UpdateRootWithQuery<'info> {
 
 impl<'info> UpdateRootWithQuery<'info> {
     pub fn constraints(ctx: &Context<Self>, bytes: &Vec<u8>) -> Result<()> {
+        check_relayer(
+            &ctx.accounts.config,
+            ctx.accounts.relayer_allowlist.as_deref(),
+            &ctx.accounts.payer.key(),
+        )?;
+
         let guardian_set = &ctx.accounts.guardian_set;
 
         // Check that the guardian set is still active.
@@ -123,19 +144,7 @@ impl<'info> UpdateRootWithQuery<'info> {
             SolanaWorldIDProgramError::GuardianSetExpired
         );
 
-        // Compute the message hash.
-        let message_hash = [
-            MESSAGE_PREFIX,
-            &solana_program::keccak::hashv(&[&bytes]).to_bytes(),
-        ]
-        .concat();
-
-        // SECURITY: defense-in-depth, check again that these are the expected length
-        require_eq!(
-            message_hash.len(),
-            QUERY_MESSAGE_LEN,
-            SolanaWorldIDProgramError::InvalidMessageHash
-        );
+        let message_hash = query_message_hash(bytes)?;
 
         let guardian_signatures = &ctx.accounts.guardian_signatures.guardian_signatures;
 
@@ -187,19 +196,124 @@ pub fn update_root_with_query(
     ctx: Context<UpdateRootWithQuery>,
     bytes: Vec<u8>,
     root_hash: [u8; 32],
-    _guardian_set_index: u32,
+    verification_type: [u8; 1],
+    guardian_set_index: u32,
+) -> Result<()> {
+    let block = parse_root_query_response(
+        &bytes,
+        &root_hash,
+        &ctx.accounts.verification_type_config,
+        &ctx.accounts.latest_root,
+    )?;
+
+    ctx.accounts.root.set_inner(Root {
+        bump: ctx.bumps.root,
+        read_block_number: block.number,
+        read_block_hash: block.hash,
+        read_block_time: block.time,
+        refund_recipient: ctx.accounts.payer.key(),
+        root: root_hash,
+        verification_type,
+        revoked: false,
+    });
+
+    ctx.accounts.latest_root.read_block_number = block.number;
+    ctx.accounts.latest_root.read_block_hash = block.hash;
+    ctx.accounts.latest_root.read_block_time = block.time;
+    ctx.accounts.latest_root.root = root_hash;
+
+    emit_cpi!(RootUpdated {
+        root: root_hash,
+        verification_type,
+        read_block_number: block.number,
+        read_block_time: block.time,
+        guardian_set_index: Some(guardian_set_index),
+        payer: ctx.accounts.payer.key(),
+    });
+
+    Ok(())
+}
+
+/// Checks that `payer` may relay roots, i.e. that the relayer allowlist is disabled or lists it.
+/// Shared by every instruction which updates a root with a QueryResponse.
+pub(crate) fn check_relayer(
+    config: &Config,
+    relayer_allowlist: Option<&RelayerAllowlist>,
+    payer: &Pubkey,
 ) -> Result<()> {
+    if !config.relayer_allowlist_enabled {
+        return Ok(());
+    }
+    let relayer_allowlist =
+        relayer_allowlist.ok_or(SolanaWorldIDProgramError::RelayerAllowlistNotProvided)?;
+    require!(
+        relayer_allowlist.relayers.contains(payer),
+        SolanaWorldIDProgramError::RelayerNotAllowed
+    );
+    Ok(())
+}
+
+/// Computes the message hash which guardians sign (after hashing again) for a QueryResponse.
+pub(crate) fn query_message_hash(bytes: &[u8]) -> Result<Vec<u8>> {
+    let message_hash = [
+        MESSAGE_PREFIX,
+        &solana_program::keccak::hashv(&[bytes]).to_bytes(),
+    ]
+    .concat();
+
+    // SECURITY: defense-in-depth, check again that these are the expected length
+    require_eq!(
+        message_hash.len(),
+        QUERY_MESSAGE_LEN,
+        SolanaWorldIDProgramError::InvalidMessageHash
+    );
+
+    Ok(message_hash)
+}
+
+/// The block from which a root was read.
+pub(crate) struct QueriedBlock {
+    pub number: u64,
+    pub hash: [u8; 32],
+    /// In microseconds.
+    pub time: u64,
+}
+
+/// Parses a QueryResponse for the `latestRoot()` of a verification type's source and checks that it
+/// is newer than the latest root, within the allowed update staleness, and returned `root_hash`.
+/// Shared by every instruction which updates a root with a QueryResponse.
+/// Signatures must be verified separately.
+pub(crate) fn parse_root_query_response(
+    bytes: &[u8],
+    root_hash: &[u8; 32],
+    verification_type_config: &VerificationTypeConfig,
+    latest_root: &LatestRoot,
+) -> Result<QueriedBlock> {
     // Parse the Wormhole QueryResponse.
-    let response = QueryResponse::deserialize(&bytes)
+    let response = QueryResponse::deserialize(bytes)
         .map_err(|_| SolanaWorldIDProgramError::FailedToParseResponse)?;
-    // Requests can be batched, expect only one request to Ethereum.
+    // Requests can be batched, expect only one request to the source chain.
     require!(
         response.request.requests.len() == 1,
         SolanaWorldIDProgramError::InvalidNumberOfRequests
     );
+    require!(
+        verification_type_config.enabled,
+        SolanaWorldIDProgramError::VerificationTypeDisabled
+    );
+    // Only Query sources may be updated with a QueryResponse.
+    let source = &verification_type_config.source;
+    let (source_chain_id, source_contract, source_selector) = match source {
+        RootSource::Query {
+            chain_id,
+            contract,
+            selector,
+        } => (*chain_id, *contract, *selector),
+        _ => return err!(SolanaWorldIDProgramError::InvalidRootSource),
+    };
     let request = &response.request.requests[0];
     require!(
-        request.chain_id == ETH_CHAIN_ID,
+        request.chain_id == source_chain_id,
         SolanaWorldIDProgramError::InvalidRequestChainId
     );
     // Ensure this was an EthCall. https://docs.wormhole.com/wormhole/queries/overview#eth_call
@@ -208,27 +322,27 @@ pub fn update_root_with_query(
         _ => None,
     }
     .ok_or(SolanaWorldIDProgramError::InvalidRequestType)?;
-    // Ensure there was one call of `latestRoot()` to the World ID Identity Manager contract.
+    // Ensure there was one call of `latestRoot()` to the World ID contract of this source.
     require!(
         query.call_data.len() == 1,
         SolanaWorldIDProgramError::InvalidRequestCallDataLength
     );
     require!(
-        query.call_data[0].to == ETH_WORLD_ID_IDENTITY_MANAGER,
+        query.call_data[0].to == source_contract,
         SolanaWorldIDProgramError::InvalidRequestContract
     );
     require!(
-        query.call_data[0].data == LATEST_ROOT_SIGNATURE,
+        query.call_data[0].data == source_selector,
         SolanaWorldIDProgramError::InvalidRequestSignature
     );
-    // Sanity checks: expect one EthCall response from Ethereum.
+    // Sanity checks: expect one EthCall response from the source chain.
     require!(
         response.responses.len() == 1,
         SolanaWorldIDProgramError::InvalidNumberOfResponses
     );
     let response = &response.responses[0];
     require!(
-        response.chain_id == ETH_CHAIN_ID,
+        response.chain_id == source_chain_id,
         SolanaWorldIDProgramError::InvalidResponseChainId
     );
     let chain_response = match &response.response {
@@ -237,7 +351,6 @@ pub fn update_root_with_query(
     }
     .ok_or(SolanaWorldIDProgramError::InvalidResponseType)?;
     // Only accept roots from newer blocks.
-    let latest_root = &ctx.accounts.latest_root;
     require!(
         chain_response.block_number > latest_root.read_block_number,
         SolanaWorldIDProgramError::StaleBlockNum
@@ -248,11 +361,11 @@ pub fn update_root_with_query(
         .unix_timestamp
         .try_into()
         .expect("timestamp underflow");
-    let config = &ctx.accounts.config;
-    let min_block_time = if config.allowed_update_staleness >= current_timestamp {
+    let allowed_update_staleness = verification_type_config.allowed_update_staleness;
+    let min_block_time = if allowed_update_staleness >= current_timestamp {
         0
     } else {
-        current_timestamp - config.allowed_update_staleness
+        current_timestamp - allowed_update_staleness
     };
     let read_block_time_in_secs = chain_response.block_time / 1_000_000;
     require!(
@@ -270,26 +383,15 @@ pub fn update_root_with_query(
         SolanaWorldIDProgramError::InvalidResponseResultLength
     );
     require!(
-        result.as_slice() == root_hash,
+        result.as_slice() == root_hash.as_slice(),
         SolanaWorldIDProgramError::RootHashMismatch
     );
 
-    ctx.accounts.root.set_inner(Root {
-        bump: ctx.bumps.root,
-        read_block_number: chain_response.block_number,
-        read_block_hash: chain_response.block_hash,
-        read_block_time: chain_response.block_time,
-        refund_recipient: ctx.accounts.payer.key(),
-        root: root_hash,
-        verification_type: *Root::VERIFICATION_TYPE_QUERY,
-    });
-
-    ctx.accounts.latest_root.read_block_number = chain_response.block_number;
-    ctx.accounts.latest_root.read_block_hash = chain_response.block_hash;
-    ctx.accounts.latest_root.read_block_time = chain_response.block_time;
-    ctx.accounts.latest_root.root = root_hash;
-
-    Ok(())
+    Ok(QueriedBlock {
+        number: chain_response.block_number,
+        hash: chain_response.block_hash,
+        time: chain_response.block_time,
+    })
 }
 
 /**
@@ -0,0 +1,247 @@
+use crate::{
+    error::SolanaWorldIDProgramError,
+    event::RootUpdated,
+    state::{
+        Config, LatestRoot, RelayerAllowlist, Root, VerificationTypeConfig, WormholeGuardianSet,
+    },
+};
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        secp256k1_program,
+        sysvar::instructions::{load_current_index_checked, load_instruction_at_checked},
+    },
+};
+use wormhole_raw_vaas::utils::quorum;
+use wormhole_solana_consts::CORE_BRIDGE_PROGRAM_ID;
+
+use super::update_root_with_query::{check_relayer, parse_root_query_response, query_message_hash};
+
+// https://docs.solanalabs.com/runtime/programs#secp256k1-program
+const SECP256K1_SIGNATURE_OFFSETS_START: usize = 1;
+const SECP256K1_SIGNATURE_OFFSETS_SIZE: usize = 11;
+const SECP256K1_ETH_ADDRESS_SIZE: usize = 20;
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(bytes: Vec<u8>, root_hash: [u8; 32], verification_type: [u8; 1], guardian_set_index: u32)]
+pub struct UpdateRootWithQueryPrecompile<'info> {
+    #[account(mut)]
+    payer: Signer<'info>,
+
+    /// Guardian set used for signature verification.
+    #[account(
+        seeds = [
+            WormholeGuardianSet::SEED_PREFIX,
+            guardian_set_index.to_be_bytes().as_ref()
+        ],
+        bump,
+        seeds::program = CORE_BRIDGE_PROGRAM_ID
+    )]
+    guardian_set: Account<'info, WormholeGuardianSet>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Root::INIT_SPACE,
+        seeds = [
+            Root::SEED_PREFIX,
+            &root_hash,
+            &verification_type,
+        ],
+        bump
+    )]
+    root: Account<'info, Root>,
+
+    #[account(
+        mut,
+        seeds = [
+            LatestRoot::SEED_PREFIX,
+            &verification_type,
+        ],
+        bump = latest_root.bump
+    )]
+    latest_root: Account<'info, LatestRoot>,
+
+    #[account(
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+        constraint = !config.is_paused(Config::PAUSE_ROOT_UPDATES) @ SolanaWorldIDProgramError::RootUpdatesPaused
+    )]
+    config: Account<'info, Config>,
+
+    /// Only required while the relayer allowlist is enabled. There is only one, so it is not checked
+    /// against its seeds, which would also make clients derive it when it does not exist.
+    relayer_allowlist: Option<Account<'info, RelayerAllowlist>>,
+
+    /// Source, staleness, and enabled flag for roots of this verification type.
+    #[account(
+        seeds = [
+            VerificationTypeConfig::SEED_PREFIX,
+            &verification_type,
+        ],
+        bump = verification_type_config.bump
+    )]
+    verification_type_config: Account<'info, VerificationTypeConfig>,
+
+    /// CHECK: Instructions sysvar, used to introspect the secp256k1 program instruction.
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    instructions: UncheckedAccount<'info>,
+
+    system_program: Program<'info, System>,
+}
+
+impl<'info> UpdateRootWithQueryPrecompile<'info> {
+    pub fn constraints(ctx: &Context<Self>, bytes: &Vec<u8>) -> Result<()> {
+        check_relayer(
+            &ctx.accounts.config,
+            ctx.accounts.relayer_allowlist.as_deref(),
+            &ctx.accounts.payer.key(),
+        )?;
+
+        let guardian_set = &ctx.accounts.guardian_set;
+
+        // Check that the guardian set is still active.
+        let timestamp = Clock::get()?
+            .unix_timestamp
+            .try_into()
+            .expect("timestamp overflow");
+        require!(
+            guardian_set.is_active(&timestamp),
+            SolanaWorldIDProgramError::GuardianSetExpired
+        );
+
+        let message_hash = query_message_hash(bytes)?;
+
+        // The secp256k1 program instruction must immediately precede this one.
+        // If it failed to verify any signature, the whole transaction would have failed.
+        let instructions = ctx.accounts.instructions.to_account_info();
+        let current_index = load_current_index_checked(&instructions)?;
+        let secp256k1_index = current_index
+            .checked_sub(1)
+            .ok_or(SolanaWorldIDProgramError::InvalidSecp256k1Instruction)?;
+        let secp256k1_ix =
+            load_instruction_at_checked(usize::from(secp256k1_index), &instructions)?;
+        require_keys_eq!(
+            secp256k1_ix.program_id,
+            secp256k1_program::ID,
+            SolanaWorldIDProgramError::InvalidSecp256k1Instruction
+        );
+        let data = &secp256k1_ix.data;
+
+        // Do we have enough signatures for quorum?
+        let num_signatures = usize::from(
+            *data
+                .first()
+                .ok_or(SolanaWorldIDProgramError::InvalidSecp256k1Instruction)?,
+        );
+        let guardian_keys = &guardian_set.keys;
+        require!(
+            num_signatures >= quorum(guardian_keys.len()),
+            SolanaWorldIDProgramError::NoQuorum
+        );
+
+        // Each verified signature must be over this message hash by a distinct guardian, in order.
+        let mut last_guardian_index = None;
+        for i in 0..num_signatures {
+            let start = SECP256K1_SIGNATURE_OFFSETS_START + i * SECP256K1_SIGNATURE_OFFSETS_SIZE;
+            let offsets = data
+                .get(start..start + SECP256K1_SIGNATURE_OFFSETS_SIZE)
+                .ok_or(SolanaWorldIDProgramError::InvalidSecp256k1Instruction)?;
+            let read_u16 =
+                |at: usize| usize::from(u16::from_le_bytes([offsets[at], offsets[at + 1]]));
+            let signature_instruction_index = offsets[2];
+            let eth_address_offset = read_u16(3);
+            let eth_address_instruction_index = offsets[5];
+            let message_data_offset = read_u16(6);
+            let message_data_size = read_u16(8);
+            let message_instruction_index = offsets[10];
+
+            // SECURITY: the precompile may verify data from any instruction in the transaction,
+            // require that everything was read from the secp256k1 instruction introspected here.
+            require!(
+                u16::from(signature_instruction_index) == secp256k1_index
+                    && u16::from(eth_address_instruction_index) == secp256k1_index
+                    && u16::from(message_instruction_index) == secp256k1_index,
+                SolanaWorldIDProgramError::InvalidSecp256k1InstructionIndex
+            );
+
+            let message = data
+                .get(message_data_offset..message_data_offset + message_data_size)
+                .ok_or(SolanaWorldIDProgramError::InvalidSecp256k1Instruction)?;
+            require!(
+                message == message_hash.as_slice(),
+                SolanaWorldIDProgramError::InvalidSecp256k1Message
+            );
+
+            let eth_address = data
+                .get(eth_address_offset..eth_address_offset + SECP256K1_ETH_ADDRESS_SIZE)
+                .ok_or(SolanaWorldIDProgramError::InvalidSecp256k1Instruction)?;
+            let index = guardian_keys
+                .iter()
+                .position(|key| key.as_slice() == eth_address)
+                .ok_or(SolanaWorldIDProgramError::UnknownGuardianKey)?;
+            // We do not allow for non-increasing guardian signature indices.
+            if let Some(last_index) = last_guardian_index {
+                require!(
+                    index > last_index,
+                    SolanaWorldIDProgramError::InvalidGuardianIndexNonIncreasing
+                );
+            }
+
+            last_guardian_index = Some(index);
+        }
+
+        // Done.
+        Ok(())
+    }
+}
+
+/// Updates a root like update_root_with_query, but without a GuardianSignatures account.
+/// Instead, the guardian signatures are verified by the native secp256k1 program in the preceding
+/// instruction of the same transaction, which is checked via the instructions sysvar.
+///
+/// NOTE: This is only usable when the QueryResponse and a quorum of signatures fit in a single
+/// transaction, e.g. for smaller guardian sets. Otherwise, use post_signatures and update_root_with_query.
+#[access_control(UpdateRootWithQueryPrecompile::constraints(&ctx, &bytes))]
+pub fn update_root_with_query_precompile(
+    ctx: Context<UpdateRootWithQueryPrecompile>,
+    bytes: Vec<u8>,
+    root_hash: [u8; 32],
+    verification_type: [u8; 1],
+    guardian_set_index: u32,
+) -> Result<()> {
+    let block = parse_root_query_response(
+        &bytes,
+        &root_hash,
+        &ctx.accounts.verification_type_config,
+        &ctx.accounts.latest_root,
+    )?;
+
+    ctx.accounts.root.set_inner(Root {
+        bump: ctx.bumps.root,
+        read_block_number: block.number,
+        read_block_hash: block.hash,
+        read_block_time: block.time,
+        refund_recipient: ctx.accounts.payer.key(),
+        root: root_hash,
+        verification_type,
+        revoked: false,
+    });
+
+    ctx.accounts.latest_root.read_block_number = block.number;
+    ctx.accounts.latest_root.read_block_hash = block.hash;
+    ctx.accounts.latest_root.read_block_time = block.time;
+    ctx.accounts.latest_root.root = root_hash;
+
+    emit_cpi!(RootUpdated {
+        root: root_hash,
+        verification_type,
+        read_block_number: block.number,
+        read_block_time: block.time,
+        guardian_set_index: Some(guardian_set_index),
+        payer: ctx.accounts.payer.key(),
+    });
+
+    Ok(())
+}
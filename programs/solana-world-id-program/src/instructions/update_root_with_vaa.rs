@@ -0,0 +1,164 @@
+use crate::{
+    error::SolanaWorldIDProgramError,
+    event::RootUpdated,
+    state::{Config, LatestRoot, Root, RootSource, VerificationTypeConfig, WormholePostedVaa},
+};
+use anchor_lang::prelude::*;
+
+// A root message payload is `ROOT_PAYLOAD_ID (u8) || root (bytes32)`.
+pub const ROOT_PAYLOAD_ID: u8 = 1;
+pub const ROOT_PAYLOAD_LEN: usize = 33;
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(root_hash: [u8; 32], verification_type: [u8; 1])]
+pub struct UpdateRootWithVaa<'info> {
+    #[account(mut)]
+    payer: Signer<'info>,
+
+    /// VAA verified and posted by the Core Bridge.
+    /// The PDA check is omitted since any account owned by the Core Bridge with the posted VAA
+    /// magic has been verified. Replays are prevented by the root and sequence checks below.
+    posted_vaa: Account<'info, WormholePostedVaa>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Root::INIT_SPACE,
+        seeds = [
+            Root::SEED_PREFIX,
+            &root_hash,
+            &verification_type,
+        ],
+        bump
+    )]
+    root: Account<'info, Root>,
+
+    #[account(
+        mut,
+        seeds = [
+            LatestRoot::SEED_PREFIX,
+            &verification_type,
+        ],
+        bump = latest_root.bump
+    )]
+    latest_root: Account<'info, LatestRoot>,
+
+    #[account(
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+        constraint = !config.is_paused(Config::PAUSE_ROOT_UPDATES) @ SolanaWorldIDProgramError::RootUpdatesPaused
+    )]
+    config: Account<'info, Config>,
+
+    /// Source, staleness, and enabled flag for roots of this verification type.
+    #[account(
+        seeds = [
+            VerificationTypeConfig::SEED_PREFIX,
+            &verification_type,
+        ],
+        bump = verification_type_config.bump
+    )]
+    verification_type_config: Account<'info, VerificationTypeConfig>,
+
+    system_program: Program<'info, System>,
+}
+
+/// Updates the root of a verification type from a Wormhole message published by its configured emitter.
+///
+/// NOTE: The Root and LatestRoot fields are reused for VAA verification types:
+/// `read_block_number` is the VAA sequence, `read_block_hash` is the posted VAA address (derived
+/// from the VAA hash), and `read_block_time` is the VAA timestamp (in microseconds, so that expiry
+/// works the same way).
+pub fn update_root_with_vaa(
+    ctx: Context<UpdateRootWithVaa>,
+    root_hash: [u8; 32],
+    verification_type: [u8; 1],
+) -> Result<()> {
+    let verification_type_config = &ctx.accounts.verification_type_config;
+    require!(
+        verification_type_config.enabled,
+        SolanaWorldIDProgramError::VerificationTypeDisabled
+    );
+    // Only Vaa sources may be updated with a VAA.
+    let source = &verification_type_config.source;
+    let (source_emitter_chain, source_emitter_address) = match source {
+        RootSource::Vaa {
+            emitter_chain,
+            emitter_address,
+        } => (*emitter_chain, *emitter_address),
+        _ => return err!(SolanaWorldIDProgramError::InvalidRootSource),
+    };
+    let posted_vaa = &ctx.accounts.posted_vaa;
+    require!(
+        posted_vaa.emitter_chain == source_emitter_chain,
+        SolanaWorldIDProgramError::InvalidVaaEmitterChain
+    );
+    require!(
+        posted_vaa.emitter_address == source_emitter_address,
+        SolanaWorldIDProgramError::InvalidVaaEmitterAddress
+    );
+    // Messages are ordered by sequence instead of block number.
+    // The first message from an emitter has sequence 0, so any sequence is accepted until a root is set.
+    let latest_root = &ctx.accounts.latest_root;
+    require!(
+        latest_root.root == [0; 32] || posted_vaa.sequence > latest_root.read_block_number,
+        SolanaWorldIDProgramError::StaleVaaSequence
+    );
+    // Only accept messages within the allowed update staleness.
+    let current_timestamp: u64 = Clock::get()?
+        .unix_timestamp
+        .try_into()
+        .expect("timestamp underflow");
+    let allowed_update_staleness = verification_type_config.allowed_update_staleness;
+    let min_block_time = if allowed_update_staleness >= current_timestamp {
+        0
+    } else {
+        current_timestamp - allowed_update_staleness
+    };
+    require!(
+        u64::from(posted_vaa.vaa_time) >= min_block_time,
+        SolanaWorldIDProgramError::StaleBlockTime
+    );
+    // Decode the root and ensure it matches the root hash used to derive the root account.
+    let payload = &posted_vaa.payload;
+    require!(
+        payload.len() == ROOT_PAYLOAD_LEN && payload[0] == ROOT_PAYLOAD_ID,
+        SolanaWorldIDProgramError::InvalidVaaPayload
+    );
+    require!(
+        payload[1..] == root_hash,
+        SolanaWorldIDProgramError::RootHashMismatch
+    );
+
+    let sequence = posted_vaa.sequence;
+    let posted_vaa_address = posted_vaa.key().to_bytes();
+    let vaa_time = u64::from(posted_vaa.vaa_time) * 1_000_000;
+
+    ctx.accounts.root.set_inner(Root {
+        bump: ctx.bumps.root,
+        read_block_number: sequence,
+        read_block_hash: posted_vaa_address,
+        read_block_time: vaa_time,
+        refund_recipient: ctx.accounts.payer.key(),
+        root: root_hash,
+        verification_type,
+        revoked: false,
+    });
+
+    ctx.accounts.latest_root.read_block_number = sequence;
+    ctx.accounts.latest_root.read_block_hash = posted_vaa_address;
+    ctx.accounts.latest_root.read_block_time = vaa_time;
+    ctx.accounts.latest_root.root = root_hash;
+
+    emit_cpi!(RootUpdated {
+        root: root_hash,
+        verification_type,
+        read_block_number: sequence,
+        read_block_time: vaa_time,
+        guardian_set_index: None,
+        payer: ctx.accounts.payer.key(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,144 @@
+use crate::{
+    error::SolanaWorldIDProgramError,
+    event::ProofVerified,
+    state::{Config, LatestRoot, Nullifier, Root, VerificationTypeConfig, VerifyingKey},
+};
+use anchor_lang::{prelude::*, solana_program::sysvar};
+
+use super::verify_groth16_proof::{caller_program, verify_proof};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(root_hash: [u8; 32], verification_type: [u8; 1], signal_hash: [u8; 32], nullifier_hash: [u8; 32], external_nullifier_hash: [u8; 32])]
+pub struct VerifyAndConsumeNullifier<'info> {
+    /// Pays for the nullifier account.
+    /// Integrators may use a PDA of their own program when invoking this instruction via CPI.
+    #[account(mut)]
+    payer: Signer<'info>,
+
+    #[account(
+        seeds = [
+            Root::SEED_PREFIX,
+            &root_hash,
+            &verification_type,
+        ],
+        bump = root.bump
+    )]
+    root: Account<'info, Root>,
+
+    #[account(
+        seeds = [
+            LatestRoot::SEED_PREFIX,
+            &verification_type,
+        ],
+        bump = latest_root.bump
+    )]
+    latest_root: Account<'info, LatestRoot>,
+
+    #[account(
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+        constraint = !config.is_paused(Config::PAUSE_PROOF_VERIFICATION) @ SolanaWorldIDProgramError::ProofVerificationPaused
+    )]
+    config: Account<'info, Config>,
+
+    #[account(
+        seeds = [
+            VerificationTypeConfig::SEED_PREFIX,
+            &verification_type,
+        ],
+        bump = verification_type_config.bump
+    )]
+    verification_type_config: Account<'info, VerificationTypeConfig>,
+
+    /// Verifying key of the verification type. Omitted when it uses the built-in key.
+    verifying_key: Option<Box<Account<'info, VerifyingKey>>>,
+
+    /// Records that the nullifier hash has been used for this external nullifier hash.
+    /// `init_if_needed` is used so that a replay fails with a dedicated error instead of an allocation error.
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + Nullifier::INIT_SPACE,
+        seeds = [
+            Nullifier::SEED_PREFIX,
+            &external_nullifier_hash,
+            &nullifier_hash,
+        ],
+        bump
+    )]
+    nullifier: Account<'info, Nullifier>,
+
+    /// CHECK: Instructions sysvar, used to report the calling program in ProofVerified.
+    #[account(address = sysvar::instructions::ID)]
+    instructions: UncheckedAccount<'info>,
+
+    system_program: Program<'info, System>,
+}
+
+impl<'info> VerifyAndConsumeNullifier<'info> {
+    pub fn constraints(
+        ctx: &Context<Self>,
+        root_hash: [u8; 32],
+        signal_hash: [u8; 32],
+        nullifier_hash: [u8; 32],
+        external_nullifier_hash: [u8; 32],
+        proof: [u8; 256],
+    ) -> Result<()> {
+        // Each nullifier may only be consumed once per external nullifier.
+        require!(
+            !ctx.accounts.nullifier.is_consumed(),
+            SolanaWorldIDProgramError::NullifierAlreadyConsumed
+        );
+
+        verify_proof(
+            &ctx.accounts.root,
+            &ctx.accounts.latest_root,
+            &ctx.accounts.verification_type_config,
+            ctx.accounts.verifying_key.as_deref().map(|key| &**key),
+            root_hash,
+            signal_hash,
+            nullifier_hash,
+            external_nullifier_hash,
+            proof,
+        )
+    }
+}
+
+/// Verifies a Groth16 proof exactly like verify_groth16_proof and, on success, records its nullifier hash.
+/// Subsequent proofs with the same nullifier hash and external nullifier hash are rejected,
+/// providing sybil-resistance without integrators needing to track nullifiers themselves.
+#[access_control(VerifyAndConsumeNullifier::constraints(&ctx, root_hash,
+    signal_hash,
+    nullifier_hash,
+    external_nullifier_hash,
+    proof))]
+pub fn verify_and_consume_nullifier(
+    ctx: Context<VerifyAndConsumeNullifier>,
+    root_hash: [u8; 32],
+    verification_type: [u8; 1],
+    signal_hash: [u8; 32],
+    nullifier_hash: [u8; 32],
+    external_nullifier_hash: [u8; 32],
+    proof: [u8; 256],
+) -> Result<()> {
+    ctx.accounts.nullifier.set_inner(Nullifier {
+        bump: ctx.bumps.nullifier,
+        external_nullifier_hash,
+        nullifier_hash,
+        root: root_hash,
+        verification_type,
+        consumed: true,
+    });
+
+    emit_cpi!(ProofVerified {
+        root: root_hash,
+        verification_type,
+        nullifier_hash,
+        external_nullifier_hash,
+        signal_hash,
+        caller_program: caller_program(&ctx.accounts.instructions)?,
+    });
+
+    Ok(())
+}
@@ -1,10 +1,27 @@
 use crate::{
     error::SolanaWorldIDProgramError,
-    state::{Config, LatestRoot, Root},
+    event::ProofVerified,
+    state::{Config, LatestRoot, Root, VerificationTypeConfig, VerifyingKey},
+};
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        alt_bn128::prelude::{alt_bn128_addition, alt_bn128_pairing},
+        instruction::{get_stack_height, TRANSACTION_LEVEL_STACK_HEIGHT},
+        sysvar::{
+            self,
+            instructions::{load_current_index_checked, load_instruction_at_checked},
+        },
+    },
 };
-use anchor_lang::prelude::*;
 use groth16_solana::groth16::{Groth16Verifier, Groth16Verifyingkey};
 
+/// Modulus of the BN254 scalar field, which every public input must be less than.
+pub const SCALAR_FIELD_MODULUS: [u8; 32] = [
+    0x30, 0x64, 0x4e, 0x72, 0xe1, 0x31, 0xa0, 0x29, 0xb8, 0x50, 0x45, 0xb6, 0x81, 0x81, 0x58, 0x5d,
+    0x28, 0x33, 0xe8, 0x48, 0x79, 0xb9, 0x70, 0x91, 0x43, 0xe1, 0xf5, 0x93, 0xf0, 0x00, 0x00, 0x01,
+];
+
 // Generated with https://github.com/Lightprotocol/groth16-solana/tree/5f1a1521bb3032601b235b6df97867801f2dfb0b?tab=readme-ov-file#create-verifyingkey-from-snarkjs-verifyingkeyjson
 // Using the values from https://github.com/worldcoin/world-id-state-bridge/blob/29641f734b1ba8ff107c80c6dfd2b903b4eecc1c/src/SemaphoreVerifier.sol#L59-L115
 // To make a verifyingKey.json
@@ -82,6 +99,40 @@ pub const VERIFYING_KEY: Groth16Verifyingkey = Groth16Verifyingkey {
     ],
 };
 
+/// Returned by verify_groth16_proof (as return data) so that CPI callers can apply their own
+/// freshness policy without reading the root accounts.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct VerificationResult {
+    /// Root against which the proof was verified.
+    pub root: [u8; 32],
+    /// Block time (in microseconds) from which the root was read.
+    pub read_block_time: u64,
+    /// Whether the root is the LatestRoot of its verification type.
+    pub is_latest_root: bool,
+    /// Seconds until the root expires under the root expiry of its verification type.
+    /// This may be zero for the latest root, which remains valid until it is replaced.
+    pub seconds_until_expiry: u64,
+}
+
+impl VerificationResult {
+    /// Result of a proof verified against `root` at `timestamp` (in seconds).
+    pub fn new(
+        root: &Root,
+        latest_root: &LatestRoot,
+        verification_type_config: &VerificationTypeConfig,
+        timestamp: u64,
+    ) -> Self {
+        let expiry_time = root.expiry_time(&verification_type_config.root_expiry);
+        Self {
+            root: root.root,
+            read_block_time: root.read_block_time,
+            is_latest_root: root.root == latest_root.root,
+            seconds_until_expiry: expiry_time.saturating_sub(timestamp),
+        }
+    }
+}
+
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(root_hash: [u8; 32], verification_type: [u8; 1], signal_hash: [u8; 32], nullifier_hash: [u8; 32], external_nullifier_hash: [u8; 32], proof: [u8; 256])]
 pub struct VerifyGroth16Proof<'info> {
@@ -106,9 +157,26 @@ pub struct VerifyGroth16Proof<'info> {
 
     #[account(
         seeds = [Config::SEED_PREFIX],
-        bump = config.bump
+        bump = config.bump,
+        constraint = !config.is_paused(Config::PAUSE_PROOF_VERIFICATION) @ SolanaWorldIDProgramError::ProofVerificationPaused
     )]
     config: Account<'info, Config>,
+
+    #[account(
+        seeds = [
+            VerificationTypeConfig::SEED_PREFIX,
+            &verification_type,
+        ],
+        bump = verification_type_config.bump
+    )]
+    verification_type_config: Account<'info, VerificationTypeConfig>,
+
+    /// Verifying key of the verification type. Omitted when it uses the built-in key.
+    verifying_key: Option<Box<Account<'info, VerifyingKey>>>,
+
+    /// CHECK: Instructions sysvar, used to report the calling program in ProofVerified.
+    #[account(address = sysvar::instructions::ID)]
+    instructions: UncheckedAccount<'info>,
 }
 
 impl<'info> VerifyGroth16Proof<'info> {
@@ -121,43 +189,215 @@ impl<'info> VerifyGroth16Proof<'info> {
         external_nullifier_hash: [u8; 32],
         proof: [u8; 256],
     ) -> Result<()> {
-        let root = &ctx.accounts.root;
-        let latest_root = &ctx.accounts.latest_root;
-        let config = &ctx.accounts.config;
-
-        // The latest root is always valid
-        if root_hash != latest_root.root {
-            // Check that the root not has expired.
-            let current_timestamp = Clock::get()?
-                .unix_timestamp
-                .try_into()
-                .expect("timestamp underflow");
-            require!(
-                root.is_active(&current_timestamp, &config.root_expiry),
-                SolanaWorldIDProgramError::RootExpired
-            );
-        }
-
-        let proof_a = proof[0..64].try_into().unwrap();
-        let proof_b = proof[64..192].try_into().unwrap();
-        let proof_c = proof[192..256].try_into().unwrap();
-
-        let public_inputs = [
+        verify_proof(
+            &ctx.accounts.root,
+            &ctx.accounts.latest_root,
+            &ctx.accounts.verification_type_config,
+            ctx.accounts.verifying_key.as_deref().map(|key| &**key),
             root_hash,
-            nullifier_hash,
             signal_hash,
+            nullifier_hash,
             external_nullifier_hash,
-        ];
+            proof,
+        )
+    }
+}
+
+/// Checks that the root may still be used and that the proof is valid for the given public inputs.
+/// Shared by every instruction which verifies a Groth16 proof against a root.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn verify_proof(
+    root: &Root,
+    latest_root: &LatestRoot,
+    verification_type_config: &VerificationTypeConfig,
+    verifying_key: Option<&VerifyingKey>,
+    root_hash: [u8; 32],
+    signal_hash: [u8; 32],
+    nullifier_hash: [u8; 32],
+    external_nullifier_hash: [u8; 32],
+    proof: [u8; 256],
+) -> Result<()> {
+    let current_timestamp = Clock::get()?
+        .unix_timestamp
+        .try_into()
+        .expect("timestamp underflow");
+    check_root(
+        root,
+        latest_root,
+        verification_type_config,
+        current_timestamp,
+    )?;
+    check_verifying_key(verification_type_config, verifying_key, current_timestamp)?;
+    verify_groth16(
+        verifying_key,
+        root_hash,
+        signal_hash,
+        nullifier_hash,
+        external_nullifier_hash,
+        &proof,
+    )
+}
+
+/// Checks that proofs may be verified against `root` at `timestamp` (in seconds): its verification
+/// type must be enabled, the root must not be revoked and it must not have expired, unless it is
+/// the latest root.
+///
+/// Does not read the clock, so that it can be evaluated off-chain against fetched accounts.
+pub fn check_root(
+    root: &Root,
+    latest_root: &LatestRoot,
+    verification_type_config: &VerificationTypeConfig,
+    timestamp: u64,
+) -> Result<()> {
+    require!(
+        verification_type_config.enabled,
+        SolanaWorldIDProgramError::VerificationTypeDisabled
+    );
+    require!(!root.revoked, SolanaWorldIDProgramError::RootRevoked);
 
-        let mut verifier =
-            Groth16Verifier::new(&proof_a, &proof_b, &proof_c, &public_inputs, &VERIFYING_KEY)
-                .map_err(|_| SolanaWorldIDProgramError::CreateGroth16VerifierFailed)?;
-        verifier
-            .verify()
-            .map_err(|_| SolanaWorldIDProgramError::Groth16ProofVerificationFailed)?;
+    // The latest root is always valid
+    if root.root != latest_root.root {
+        // Check that the root not has expired.
+        require!(
+            root.is_active(&timestamp, &verification_type_config.root_expiry),
+            SolanaWorldIDProgramError::RootExpired
+        );
+    }
 
-        Ok(())
+    Ok(())
+}
+
+/// Checks that proofs of the verification type may be verified with `verifying_key` at `timestamp`
+/// (in seconds): its current key, or the key it replaced during the grace period. Either is `None`
+/// for the built-in [`VERIFYING_KEY`], otherwise an unretired [`VerifyingKey`].
+pub fn check_verifying_key(
+    verification_type_config: &VerificationTypeConfig,
+    verifying_key: Option<&VerifyingKey>,
+    timestamp: u64,
+) -> Result<()> {
+    let key_id = verifying_key.map(|key| key.key_id);
+    if key_id != verification_type_config.verifying_key_id {
+        match &verification_type_config.previous_verifying_key {
+            Some(previous) if previous.key_id == key_id => require!(
+                timestamp <= previous.accepted_until,
+                SolanaWorldIDProgramError::VerifyingKeyGracePeriodEnded
+            ),
+            _ if verifying_key.is_none() => {
+                return err!(SolanaWorldIDProgramError::VerifyingKeyNotProvided)
+            }
+            _ => return err!(SolanaWorldIDProgramError::VerifyingKeyMismatch),
+        }
     }
+    require!(
+        !verifying_key.is_some_and(|key| key.retired),
+        SolanaWorldIDProgramError::RetiredVerifyingKey
+    );
+    Ok(())
+}
+
+/// Verifies a World ID proof for the given public inputs against `verifying_key`, or the built-in
+/// [`VERIFYING_KEY`] when `None`.
+///
+/// Malformed inputs fail with a dedicated error rather than as an invalid proof: each public input
+/// must be a canonical element of the scalar field and each proof point must be on the curve.
+///
+/// Uses the alt_bn128 syscalls on-chain and their native implementation off-chain, which compute
+/// the same result.
+pub fn verify_groth16(
+    verifying_key: Option<&VerifyingKey>,
+    root_hash: [u8; 32],
+    signal_hash: [u8; 32],
+    nullifier_hash: [u8; 32],
+    external_nullifier_hash: [u8; 32],
+    proof: &[u8; 256],
+) -> Result<()> {
+    require!(
+        root_hash < SCALAR_FIELD_MODULUS,
+        SolanaWorldIDProgramError::RootHashOutOfRange
+    );
+    require!(
+        signal_hash < SCALAR_FIELD_MODULUS,
+        SolanaWorldIDProgramError::SignalHashOutOfRange
+    );
+    require!(
+        nullifier_hash < SCALAR_FIELD_MODULUS,
+        SolanaWorldIDProgramError::NullifierHashOutOfRange
+    );
+    require!(
+        external_nullifier_hash < SCALAR_FIELD_MODULUS,
+        SolanaWorldIDProgramError::ExternalNullifierHashOutOfRange
+    );
+
+    let proof_a = proof[0..64].try_into().unwrap();
+    let proof_b = proof[64..192].try_into().unwrap();
+    let proof_c = proof[192..256].try_into().unwrap();
+    require!(
+        is_g1_point(&proof_a),
+        SolanaWorldIDProgramError::InvalidProofA
+    );
+    require!(
+        is_g1_point(&proof_c),
+        SolanaWorldIDProgramError::InvalidProofC
+    );
+
+    let public_inputs = [
+        root_hash,
+        nullifier_hash,
+        signal_hash,
+        external_nullifier_hash,
+    ];
+
+    let registered_key;
+    let verifying_key = match verifying_key {
+        Some(verifying_key) => {
+            registered_key = verifying_key.to_groth16();
+            &registered_key
+        }
+        None => &VERIFYING_KEY,
+    };
+
+    let mut verifier =
+        Groth16Verifier::new(&proof_a, &proof_b, &proof_c, &public_inputs, verifying_key)
+            .map_err(|_| SolanaWorldIDProgramError::CreateGroth16VerifierFailed)?;
+    verifier.verify().map_err(|_| {
+        // Checking proof B costs a pairing, so it is only checked to explain a failure.
+        if are_g2_points(&[proof_b]) {
+            SolanaWorldIDProgramError::Groth16ProofVerificationFailed
+        } else {
+            SolanaWorldIDProgramError::InvalidProofB
+        }
+    })?;
+
+    Ok(())
+}
+
+/// Whether `point` is a point of G1, i.e. on the curve with coordinates less than the base field
+/// modulus, or the point at infinity (zero).
+pub(crate) fn is_g1_point(point: &[u8; 64]) -> bool {
+    // The alt_bn128 syscalls reject invalid points. Adding zero is the cheapest of them.
+    alt_bn128_addition(&[point.as_slice(), &[0; 64]].concat()).is_ok()
+}
+
+/// Whether each of `points` is a point of G2, which must also be in the prime order subgroup.
+/// They are checked with a single pairing, whose cost mostly does not depend on the number of points.
+pub(crate) fn are_g2_points(points: &[[u8; 128]]) -> bool {
+    let input: Vec<u8> = points
+        .iter()
+        .flat_map(|point| [[0; 64].as_slice(), point.as_slice()].concat())
+        .collect();
+    alt_bn128_pairing(&input).is_ok()
+}
+
+/// Returns the program of the top-level instruction when invoked via CPI, or `None` when invoked directly.
+///
+/// NOTE: The runtime does not expose the immediate caller, so for nested CPIs this is the outermost program.
+pub(crate) fn caller_program(instructions: &AccountInfo) -> Result<Option<Pubkey>> {
+    if get_stack_height() == TRANSACTION_LEVEL_STACK_HEIGHT {
+        return Ok(None);
+    }
+    let current_index = load_current_index_checked(instructions)?;
+    let current_ix = load_instruction_at_checked(usize::from(current_index), instructions)?;
+    Ok(Some(current_ix.program_id))
 }
 
 #[access_control(VerifyGroth16Proof::constraints(&ctx, root_hash,
@@ -174,6 +414,25 @@ pub fn verify_groth16_proof(
     nullifier_hash: [u8; 32],
     external_nullifier_hash: [u8; 32],
     proof: [u8; 256],
-) -> Result<()> {
-    Ok(())
+) -> Result<VerificationResult> {
+    emit_cpi!(ProofVerified {
+        root: root_hash,
+        verification_type,
+        nullifier_hash,
+        external_nullifier_hash,
+        signal_hash,
+        caller_program: caller_program(&ctx.accounts.instructions)?,
+    });
+
+    let current_timestamp = Clock::get()?
+        .unix_timestamp
+        .try_into()
+        .expect("timestamp underflow");
+
+    Ok(VerificationResult::new(
+        &ctx.accounts.root,
+        &ctx.accounts.latest_root,
+        &ctx.accounts.verification_type_config,
+        current_timestamp,
+    ))
 }
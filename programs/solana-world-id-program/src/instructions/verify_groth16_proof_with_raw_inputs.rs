@@ -0,0 +1,106 @@
+use crate::{
+    error::SolanaWorldIDProgramError,
+    event::ProofVerified,
+    hashing::{external_nullifier_hash, signal_hash},
+    state::{Config, LatestRoot, Root, VerificationTypeConfig, VerifyingKey},
+};
+use anchor_lang::{prelude::*, solana_program::sysvar};
+
+use super::verify_groth16_proof::{caller_program, verify_proof, VerificationResult};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(root_hash: [u8; 32], verification_type: [u8; 1])]
+pub struct VerifyGroth16ProofWithRawInputs<'info> {
+    #[account(
+        seeds = [
+            Root::SEED_PREFIX,
+            &root_hash,
+            &verification_type,
+        ],
+        bump = root.bump
+    )]
+    root: Account<'info, Root>,
+
+    #[account(
+        seeds = [
+            LatestRoot::SEED_PREFIX,
+            &verification_type,
+        ],
+        bump = latest_root.bump
+    )]
+    latest_root: Account<'info, LatestRoot>,
+
+    #[account(
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+        constraint = !config.is_paused(Config::PAUSE_PROOF_VERIFICATION) @ SolanaWorldIDProgramError::ProofVerificationPaused
+    )]
+    config: Account<'info, Config>,
+
+    #[account(
+        seeds = [
+            VerificationTypeConfig::SEED_PREFIX,
+            &verification_type,
+        ],
+        bump = verification_type_config.bump
+    )]
+    verification_type_config: Account<'info, VerificationTypeConfig>,
+
+    /// Verifying key of the verification type. Omitted when it uses the built-in key.
+    verifying_key: Option<Box<Account<'info, VerifyingKey>>>,
+
+    /// CHECK: Instructions sysvar, used to report the calling program in ProofVerified.
+    #[account(address = sysvar::instructions::ID)]
+    instructions: UncheckedAccount<'info>,
+}
+
+/// Same as verify_groth16_proof, but derives the signal hash and external nullifier hash from the
+/// raw signal, app id and action, so that integrators cannot pass hashes which were not reduced
+/// with `hashToField`.
+#[allow(clippy::too_many_arguments)]
+pub fn verify_groth16_proof_with_raw_inputs(
+    ctx: Context<VerifyGroth16ProofWithRawInputs>,
+    root_hash: [u8; 32],
+    verification_type: [u8; 1],
+    app_id: String,
+    action: String,
+    signal: Vec<u8>,
+    nullifier_hash: [u8; 32],
+    proof: [u8; 256],
+) -> Result<VerificationResult> {
+    let signal_hash = signal_hash(&signal);
+    let external_nullifier_hash = external_nullifier_hash(&app_id, &action);
+    verify_proof(
+        &ctx.accounts.root,
+        &ctx.accounts.latest_root,
+        &ctx.accounts.verification_type_config,
+        ctx.accounts.verifying_key.as_deref().map(|key| &**key),
+        root_hash,
+        signal_hash,
+        nullifier_hash,
+        external_nullifier_hash,
+        proof,
+    )?;
+
+    emit_cpi!(ProofVerified {
+        root: root_hash,
+        verification_type,
+        nullifier_hash,
+        external_nullifier_hash,
+        signal_hash,
+        caller_program: caller_program(&ctx.accounts.instructions)?,
+    });
+
+    let current_timestamp = Clock::get()?
+        .unix_timestamp
+        .try_into()
+        .expect("timestamp underflow");
+
+    Ok(VerificationResult::new(
+        &ctx.accounts.root,
+        &ctx.accounts.latest_root,
+        &ctx.accounts.verification_type_config,
+        current_timestamp,
+    ))
+}
@@ -0,0 +1,165 @@
+use crate::{
+    error::SolanaWorldIDProgramError,
+    event::{ProofsVerified, VerifiedProof},
+    state::{Config, LatestRoot, Root, VerificationTypeConfig, VerifyingKey},
+};
+use anchor_lang::{prelude::*, solana_program::sysvar};
+
+use super::verify_groth16_proof::{caller_program, check_verifying_key, verify_proof};
+
+/// Public inputs and proof for one entry of a batch.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct Groth16ProofArgs {
+    pub root_hash: [u8; 32],
+    pub signal_hash: [u8; 32],
+    pub nullifier_hash: [u8; 32],
+    pub external_nullifier_hash: [u8; 32],
+    pub proof: [u8; 256],
+}
+
+/// How verify_groth16_proofs handles a proof which fails verification.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BatchVerificationMode {
+    /// Fail the instruction if any proof is invalid.
+    Atomic,
+    /// Return whether each proof is valid.
+    Independent,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(verification_type: [u8; 1])]
+pub struct VerifyGroth16Proofs<'info> {
+    #[account(
+        seeds = [
+            LatestRoot::SEED_PREFIX,
+            &verification_type,
+        ],
+        bump = latest_root.bump
+    )]
+    latest_root: Account<'info, LatestRoot>,
+
+    #[account(
+        seeds = [Config::SEED_PREFIX],
+        bump = config.bump,
+        constraint = !config.is_paused(Config::PAUSE_PROOF_VERIFICATION) @ SolanaWorldIDProgramError::ProofVerificationPaused
+    )]
+    config: Account<'info, Config>,
+
+    #[account(
+        seeds = [
+            VerificationTypeConfig::SEED_PREFIX,
+            &verification_type,
+        ],
+        bump = verification_type_config.bump
+    )]
+    verification_type_config: Account<'info, VerificationTypeConfig>,
+
+    /// Verifying key of the verification type. Omitted when it uses the built-in key.
+    verifying_key: Option<Box<Account<'info, VerifyingKey>>>,
+
+    /// CHECK: Instructions sysvar, used to report the calling program in ProofsVerified.
+    #[account(address = sysvar::instructions::ID)]
+    instructions: UncheckedAccount<'info>,
+}
+
+/// Verifies several proofs against roots of the same verification type.
+///
+/// The distinct Root accounts used by the proofs are passed as remaining accounts, each at most once.
+/// Returns whether each proof is valid, in order. In `Atomic` mode, this is always all `true`.
+///
+/// NOTE: A missing Root account fails the instruction regardless of the mode, since it is a
+/// mistake by the caller rather than an invalid proof.
+pub fn verify_groth16_proofs(
+    ctx: Context<VerifyGroth16Proofs>,
+    verification_type: [u8; 1],
+    proofs: Vec<Groth16ProofArgs>,
+    mode: BatchVerificationMode,
+) -> Result<Vec<bool>> {
+    require!(
+        !proofs.is_empty(),
+        SolanaWorldIDProgramError::EmptyProofBatch
+    );
+    // Checked up front, since neither a disabled verification type nor a wrong verifying key is
+    // a property of any one proof.
+    require!(
+        ctx.accounts.verification_type_config.enabled,
+        SolanaWorldIDProgramError::VerificationTypeDisabled
+    );
+    let verifying_key = ctx.accounts.verifying_key.as_deref().map(|key| &**key);
+    let current_timestamp = Clock::get()?
+        .unix_timestamp
+        .try_into()
+        .expect("timestamp underflow");
+    check_verifying_key(
+        &ctx.accounts.verification_type_config,
+        verifying_key,
+        current_timestamp,
+    )?;
+
+    // Load the roots. The PDA check is omitted since a Root owned by this program is uniquely
+    // identified by its root hash and verification type, which are both stored in the account.
+    let mut roots: Vec<Root> = Vec::with_capacity(ctx.remaining_accounts.len());
+    for account_info in ctx.remaining_accounts.iter() {
+        require_keys_eq!(
+            *account_info.owner,
+            crate::ID,
+            ErrorCode::AccountOwnedByWrongProgram
+        );
+        let root = Root::try_deserialize(&mut &account_info.data.borrow()[..])?;
+        require!(
+            root.verification_type == verification_type,
+            SolanaWorldIDProgramError::RootVerificationTypeMismatch
+        );
+        require!(
+            !roots.iter().any(|loaded| loaded.root == root.root),
+            SolanaWorldIDProgramError::DuplicateRootAccount
+        );
+        roots.push(root);
+    }
+
+    let caller = caller_program(&ctx.accounts.instructions)?;
+    let mut results = Vec::with_capacity(proofs.len());
+    let mut verified_proofs = Vec::with_capacity(proofs.len());
+    for args in proofs {
+        let root = roots
+            .iter()
+            .find(|root| root.root == args.root_hash)
+            .ok_or(SolanaWorldIDProgramError::RootAccountNotProvided)?;
+        let result = verify_proof(
+            root,
+            &ctx.accounts.latest_root,
+            &ctx.accounts.verification_type_config,
+            verifying_key,
+            args.root_hash,
+            args.signal_hash,
+            args.nullifier_hash,
+            args.external_nullifier_hash,
+            args.proof,
+        );
+        let is_valid = match result {
+            Ok(()) => true,
+            Err(err) if mode == BatchVerificationMode::Atomic => return Err(err),
+            Err(_) => false,
+        };
+        if is_valid {
+            verified_proofs.push(VerifiedProof {
+                root: args.root_hash,
+                nullifier_hash: args.nullifier_hash,
+                external_nullifier_hash: args.external_nullifier_hash,
+                signal_hash: args.signal_hash,
+            });
+        }
+        results.push(is_valid);
+    }
+
+    if !verified_proofs.is_empty() {
+        emit_cpi!(ProofsVerified {
+            verification_type,
+            proofs: verified_proofs,
+            caller_program: caller,
+        });
+    }
+
+    Ok(results)
+}
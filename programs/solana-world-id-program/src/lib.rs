@@ -4,10 +4,15 @@ declare_id!("9QwAWx3TKg4CaTjHNhBefQeNSzEKDe2JDxL46F76tVDv");
 
 pub mod error;
 
+pub mod event;
+
+pub mod hashing;
+
 mod instructions;
-pub(crate) use instructions::*;
+pub use instructions::*;
 
 pub mod state;
+use state::{ConfigChange, Role};
 
 #[program]
 pub mod solana_world_id_program {
@@ -17,6 +22,14 @@ pub mod solana_world_id_program {
         instructions::initialize(ctx, args)
     }
 
+    pub fn migrate_config(ctx: Context<MigrateConfig>) -> Result<()> {
+        instructions::migrate_config(ctx)
+    }
+
+    pub fn migrate_root(ctx: Context<MigrateRoot>) -> Result<()> {
+        instructions::migrate_root(ctx)
+    }
+
     pub fn post_signatures(
         ctx: Context<PostSignatures>,
         guardian_signatures: Vec<[u8; 66]>,
@@ -29,9 +42,40 @@ pub mod solana_world_id_program {
         ctx: Context<UpdateRootWithQuery>,
         bytes: Vec<u8>,
         root_hash: [u8; 32],
+        verification_type: [u8; 1],
+        guardian_set_index: u32,
+    ) -> Result<()> {
+        instructions::update_root_with_query(
+            ctx,
+            bytes,
+            root_hash,
+            verification_type,
+            guardian_set_index,
+        )
+    }
+
+    pub fn update_root_with_query_precompile(
+        ctx: Context<UpdateRootWithQueryPrecompile>,
+        bytes: Vec<u8>,
+        root_hash: [u8; 32],
+        verification_type: [u8; 1],
         guardian_set_index: u32,
     ) -> Result<()> {
-        instructions::update_root_with_query(ctx, bytes, root_hash, guardian_set_index)
+        instructions::update_root_with_query_precompile(
+            ctx,
+            bytes,
+            root_hash,
+            verification_type,
+            guardian_set_index,
+        )
+    }
+
+    pub fn update_root_with_vaa(
+        ctx: Context<UpdateRootWithVaa>,
+        root_hash: [u8; 32],
+        verification_type: [u8; 1],
+    ) -> Result<()> {
+        instructions::update_root_with_vaa(ctx, root_hash, verification_type)
     }
 
     pub fn clean_up_root(ctx: Context<CleanUpRoot>) -> Result<()> {
@@ -50,15 +94,135 @@ pub mod solana_world_id_program {
         instructions::claim_ownership(ctx)
     }
 
-    pub fn set_root_expiry(ctx: Context<SetRootExpiry>, root_expiry: u64) -> Result<()> {
-        instructions::set_root_expiry(ctx, root_expiry)
+    pub fn register_verification_type(
+        ctx: Context<RegisterVerificationType>,
+        verification_type: [u8; 1],
+        args: RegisterVerificationTypeArgs,
+    ) -> Result<()> {
+        instructions::register_verification_type(ctx, verification_type, args)
+    }
+
+    pub fn set_verification_type_enabled(
+        ctx: Context<SetVerificationTypeEnabled>,
+        verification_type: [u8; 1],
+        enabled: bool,
+    ) -> Result<()> {
+        instructions::set_verification_type_enabled(ctx, verification_type, enabled)
+    }
+
+    pub fn register_verifying_key(
+        ctx: Context<RegisterVerifyingKey>,
+        key_id: [u8; 1],
+        args: RegisterVerifyingKeyArgs,
+    ) -> Result<()> {
+        instructions::register_verifying_key(ctx, key_id, args)
+    }
+
+    pub fn retire_verifying_key(ctx: Context<RetireVerifyingKey>, key_id: [u8; 1]) -> Result<()> {
+        instructions::retire_verifying_key(ctx, key_id)
+    }
+
+    pub fn propose_verifying_key(
+        ctx: Context<ProposeVerifyingKey>,
+        verification_type: [u8; 1],
+        grace_period: u64,
+    ) -> Result<()> {
+        instructions::propose_verifying_key(ctx, verification_type, grace_period)
+    }
+
+    pub fn activate_verifying_key(
+        ctx: Context<ActivateVerifyingKey>,
+        verification_type: [u8; 1],
+    ) -> Result<()> {
+        instructions::activate_verifying_key(ctx, verification_type)
+    }
+
+    pub fn grant_role(ctx: Context<GrantRole>, role: Role, grantee: Pubkey) -> Result<()> {
+        instructions::grant_role(ctx, role, grantee)
+    }
+
+    pub fn revoke_role(ctx: Context<RevokeRole>, role: Role) -> Result<()> {
+        instructions::revoke_role(ctx, role)
+    }
+
+    pub fn pause(ctx: Context<Pause>, flags: u8) -> Result<()> {
+        instructions::pause(ctx, flags)
+    }
+
+    pub fn unpause(ctx: Context<Unpause>, flags: u8) -> Result<()> {
+        instructions::unpause(ctx, flags)
+    }
+
+    pub fn revoke_root(
+        ctx: Context<RevokeRoot>,
+        root_hash: [u8; 32],
+        verification_type: [u8; 1],
+    ) -> Result<()> {
+        instructions::revoke_root(ctx, root_hash, verification_type)
+    }
+
+    pub fn propose_config_change(
+        ctx: Context<ProposeConfigChange>,
+        change: ConfigChange,
+    ) -> Result<()> {
+        instructions::propose_config_change(ctx, change)
+    }
+
+    pub fn execute_config_change(ctx: Context<ExecuteConfigChange>) -> Result<()> {
+        instructions::execute_config_change(ctx)
+    }
+
+    pub fn cancel_config_change(ctx: Context<CancelConfigChange>) -> Result<()> {
+        instructions::cancel_config_change(ctx)
+    }
+
+    pub fn initialize_admin_set(
+        ctx: Context<InitializeAdminSet>,
+        members: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        instructions::initialize_admin_set(ctx, members, threshold)
+    }
+
+    pub fn add_admin_set_member(ctx: Context<UpdateAdminSet>, member: Pubkey) -> Result<()> {
+        instructions::add_admin_set_member(ctx, member)
+    }
+
+    pub fn remove_admin_set_member(ctx: Context<UpdateAdminSet>, member: Pubkey) -> Result<()> {
+        instructions::remove_admin_set_member(ctx, member)
+    }
+
+    pub fn set_admin_set_threshold(ctx: Context<UpdateAdminSet>, threshold: u8) -> Result<()> {
+        instructions::set_admin_set_threshold(ctx, threshold)
+    }
+
+    pub fn execute_as_admin_set<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExecuteAsAdminSet<'info>>,
+        data: Vec<u8>,
+    ) -> Result<()> {
+        instructions::execute_as_admin_set(ctx, data)
     }
 
-    pub fn set_allowed_update_staleness(
-        ctx: Context<SetAllowedUpdateStaleness>,
-        allowed_update_staleness: u64,
+    pub fn initialize_relayer_allowlist(
+        ctx: Context<InitializeRelayerAllowlist>,
+        relayers: Vec<Pubkey>,
     ) -> Result<()> {
-        instructions::set_allowed_update_staleness(ctx, allowed_update_staleness)
+        instructions::initialize_relayer_allowlist(ctx, relayers)
+    }
+
+    pub fn add_relayer(ctx: Context<UpdateRelayerAllowlist>, relayer: Pubkey) -> Result<()> {
+        instructions::add_relayer(ctx, relayer)
+    }
+
+    pub fn remove_relayer(ctx: Context<UpdateRelayerAllowlist>, relayer: Pubkey) -> Result<()> {
+        instructions::remove_relayer(ctx, relayer)
+    }
+
+    pub fn set_relayer_allowlist_enabled(
+        ctx: Context<SetRelayerAllowlistEnabled>,
+        enabled: bool,
+    ) -> Result<()> {
+        instructions::set_relayer_allowlist_enabled(ctx, enabled)
     }
 
     pub fn verify_groth16_proof(
@@ -69,7 +233,7 @@ pub mod solana_world_id_program {
         nullifier_hash: [u8; 32],
         external_nullifier_hash: [u8; 32],
         proof: [u8; 256],
-    ) -> Result<()> {
+    ) -> Result<VerificationResult> {
         instructions::verify_groth16_proof(
             ctx,
             root_hash,
@@ -80,4 +244,56 @@ pub mod solana_world_id_program {
             proof,
         )
     }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn verify_groth16_proof_with_raw_inputs(
+        ctx: Context<VerifyGroth16ProofWithRawInputs>,
+        root_hash: [u8; 32],
+        verification_type: [u8; 1],
+        app_id: String,
+        action: String,
+        signal: Vec<u8>,
+        nullifier_hash: [u8; 32],
+        proof: [u8; 256],
+    ) -> Result<VerificationResult> {
+        instructions::verify_groth16_proof_with_raw_inputs(
+            ctx,
+            root_hash,
+            verification_type,
+            app_id,
+            action,
+            signal,
+            nullifier_hash,
+            proof,
+        )
+    }
+
+    pub fn verify_groth16_proofs(
+        ctx: Context<VerifyGroth16Proofs>,
+        verification_type: [u8; 1],
+        proofs: Vec<Groth16ProofArgs>,
+        mode: BatchVerificationMode,
+    ) -> Result<Vec<bool>> {
+        instructions::verify_groth16_proofs(ctx, verification_type, proofs, mode)
+    }
+
+    pub fn verify_and_consume_nullifier(
+        ctx: Context<VerifyAndConsumeNullifier>,
+        root_hash: [u8; 32],
+        verification_type: [u8; 1],
+        signal_hash: [u8; 32],
+        nullifier_hash: [u8; 32],
+        external_nullifier_hash: [u8; 32],
+        proof: [u8; 256],
+    ) -> Result<()> {
+        instructions::verify_and_consume_nullifier(
+            ctx,
+            root_hash,
+            verification_type,
+            signal_hash,
+            nullifier_hash,
+            external_nullifier_hash,
+            proof,
+        )
+    }
 }
@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+
+use crate::error::SolanaWorldIDProgramError;
+
+/// An M-of-N set of admins. Its PDA signs instructions approved by `threshold` distinct members with
+/// execute_as_admin_set, so that ownership of the program can be transferred to it.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct AdminSet {
+    pub bump: u8,
+    /// Number of distinct members which must sign to execute an instruction.
+    pub threshold: u8,
+    #[max_len(AdminSet::MAX_MEMBERS)]
+    pub members: Vec<Pubkey>,
+}
+
+impl AdminSet {
+    pub const SEED_PREFIX: &'static [u8] = b"AdminSet";
+
+    pub const MAX_MEMBERS: usize = 10;
+
+    /// Checks that the members are distinct and at most [`AdminSet::MAX_MEMBERS`], and that the
+    /// threshold can be met.
+    pub fn check(&self) -> Result<()> {
+        require!(
+            self.members.len() <= Self::MAX_MEMBERS
+                && self.threshold > 0
+                && usize::from(self.threshold) <= self.members.len()
+                && self
+                    .members
+                    .iter()
+                    .enumerate()
+                    .all(|(i, member)| !self.members[..i].contains(member)),
+            SolanaWorldIDProgramError::InvalidAdminSet
+        );
+        Ok(())
+    }
+
+    /// Returns the distinct members which signed, among `accounts`.
+    pub fn signers(&self, accounts: &[AccountInfo]) -> Vec<Pubkey> {
+        self.members
+            .iter()
+            .filter(|member| {
+                accounts
+                    .iter()
+                    .any(|account| account.is_signer && account.key == *member)
+            })
+            .copied()
+            .collect()
+    }
+}
@@ -8,12 +8,80 @@ pub struct Config {
     pub owner: Pubkey,
     /// Pending next owner (before claiming ownership).
     pub pending_owner: Option<Pubkey>,
-    /// Time (in seconds) after which a root should be considered expired.
+    /// Time (in seconds) after which a proposed verifying key may be activated.
+    pub verifying_key_activation_delay: u64,
+    /// Bitfield of the paused instructions, see [`Config::PAUSE_ROOT_UPDATES`] and the like.
+    pub paused: u8,
+    /// May pause, but not unpause, instructions in addition to the owner.
+    pub pauser: Option<Pubkey>,
+    /// Time (in seconds) after which a proposed config change may be executed.
+    pub config_change_delay: u64,
+    /// May propose, execute and cancel config changes and enable or disable verification types in
+    /// addition to the owner.
+    pub config_manager: Option<Pubkey>,
+    /// May revoke roots in addition to the owner. Revoking the latest root only rolls back to an
+    /// older root which is still unexpired, so this role cannot pin a stale root.
+    pub root_revoker: Option<Pubkey>,
+    /// Whether only the relayers of the [`RelayerAllowlist`](crate::state::RelayerAllowlist) may
+    /// update roots with a QueryResponse.
+    pub relayer_allowlist_enabled: bool,
+}
+
+/// Layout of the config before verification types were introduced, whose `root_expiry` and
+/// `allowed_update_staleness` moved to the `VerificationTypeConfig` of
+/// `Root::VERIFICATION_TYPE_QUERY`. Only read by migrate_config.
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, InitSpace)]
+pub struct LegacyConfig {
+    pub bump: u8,
+    pub owner: Pubkey,
+    pub pending_owner: Option<Pubkey>,
     pub root_expiry: u64,
-    /// Time (in seconds) after which an attempted update should be rejected.
     pub allowed_update_staleness: u64,
 }
 
+/// A role which the owner grants to another account, e.g. a hot key, so that day-to-day
+/// operations do not need the owner, which also holds the upgrade authority.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Role {
+    ConfigManager,
+    Pauser,
+    RootRevoker,
+}
+
 impl Config {
     pub const SEED_PREFIX: &'static [u8] = b"Config";
+
+    /// Pauses update_root_with_query, update_root_with_query_precompile and update_root_with_vaa.
+    pub const PAUSE_ROOT_UPDATES: u8 = 1 << 0;
+    /// Pauses every instruction which verifies a Groth16 proof.
+    pub const PAUSE_PROOF_VERIFICATION: u8 = 1 << 1;
+    /// Pauses clean_up_root.
+    pub const PAUSE_CLEANUP: u8 = 1 << 2;
+    pub const PAUSE_ALL: u8 =
+        Self::PAUSE_ROOT_UPDATES | Self::PAUSE_PROOF_VERIFICATION | Self::PAUSE_CLEANUP;
+
+    pub fn is_paused(&self, flags: u8) -> bool {
+        self.paused & flags != 0
+    }
+
+    pub fn role(&self, role: Role) -> &Option<Pubkey> {
+        match role {
+            Role::ConfigManager => &self.config_manager,
+            Role::Pauser => &self.pauser,
+            Role::RootRevoker => &self.root_revoker,
+        }
+    }
+
+    pub fn role_mut(&mut self, role: Role) -> &mut Option<Pubkey> {
+        match role {
+            Role::ConfigManager => &mut self.config_manager,
+            Role::Pauser => &mut self.pauser,
+            Role::RootRevoker => &mut self.root_revoker,
+        }
+    }
+
+    /// Whether `authority` is the owner, which holds every role, or has been granted `role`.
+    pub fn has_role(&self, role: Role, authority: &Pubkey) -> bool {
+        *authority == self.owner || *self.role(role) == Some(*authority)
+    }
 }
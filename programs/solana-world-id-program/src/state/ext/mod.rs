@@ -50,3 +50,73 @@ impl WormholeGuardianSet {
         }
     }
 }
+
+/// A VAA which has been verified and posted by the Core Bridge (`PostedVAAV1`).
+/// The Core Bridge only writes these accounts after verifying a quorum of guardian signatures,
+/// so ownership and the `vaa\x01` magic are sufficient to trust the contents.
+#[derive(Debug, AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct WormholePostedVaa {
+    /// Always `vaa\x01`.
+    pub magic: [u8; 4],
+
+    /// Header of the posted VAA.
+    pub vaa_version: u8,
+
+    /// Level of consistency requested by the emitter.
+    pub consistency_level: u8,
+
+    /// Time the message was submitted.
+    pub vaa_time: u32,
+
+    /// Account where signatures are stored.
+    pub vaa_signature_account: Pubkey,
+
+    /// Time the posted message was created.
+    pub submission_time: u32,
+
+    /// Unique nonce for this message.
+    pub nonce: u32,
+
+    /// Sequence number of this message.
+    pub sequence: u64,
+
+    /// Emitter of the message.
+    pub emitter_chain: u16,
+
+    /// Emitter of the message.
+    pub emitter_address: [u8; 32],
+
+    /// Message payload.
+    pub payload: Vec<u8>,
+}
+
+impl Owner for WormholePostedVaa {
+    fn owner() -> Pubkey {
+        CORE_BRIDGE_PROGRAM_ID
+    }
+}
+
+// workaround for anchor 0.30.1
+// https://github.com/coral-xyz/anchor/blob/e6d7dafe12da661a36ad1b4f3b5970e8986e5321/spl/src/idl_build.rs#L11
+impl anchor_lang::Discriminator for WormholePostedVaa {
+    const DISCRIMINATOR: [u8; 8] = [0; 8];
+}
+
+impl AccountSerialize for WormholePostedVaa {}
+
+impl AccountDeserialize for WormholePostedVaa {
+    fn try_deserialize(buf: &mut &[u8]) -> Result<Self> {
+        if buf.len() < Self::MAGIC.len() || buf[..Self::MAGIC.len()] != *Self::MAGIC {
+            return err!(ErrorCode::AccountDiscriminatorMismatch);
+        }
+        Self::try_deserialize_unchecked(buf)
+    }
+
+    fn try_deserialize_unchecked(buf: &mut &[u8]) -> Result<Self> {
+        Self::deserialize(buf).map_err(Into::into)
+    }
+}
+
+impl WormholePostedVaa {
+    pub const MAGIC: &'static [u8; 4] = b"vaa\x01";
+}
@@ -1,6 +1,9 @@
 mod ext;
 pub use ext::*;
 
+mod admin_set;
+pub use admin_set::*;
+
 mod config;
 pub use config::*;
 
@@ -10,5 +13,20 @@ pub use guardian_signatures::*;
 mod latest_root;
 pub use latest_root::*;
 
+mod nullifier;
+pub use nullifier::*;
+
+mod pending_config_change;
+pub use pending_config_change::*;
+
+mod relayer_allowlist;
+pub use relayer_allowlist::*;
+
 mod root;
 pub use root::*;
+
+mod verification_type_config;
+pub use verification_type_config::*;
+
+mod verifying_key;
+pub use verifying_key::*;
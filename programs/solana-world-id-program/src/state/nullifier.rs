@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct Nullifier {
+    pub bump: u8,
+    /// SEED: External nullifier hash (app and action) the nullifier was consumed for.
+    pub external_nullifier_hash: [u8; 32],
+    /// SEED: Nullifier hash of the consumed proof.
+    pub nullifier_hash: [u8; 32],
+    /// Root hash the proof was verified against.
+    pub root: [u8; 32],
+    /// Verification type of the above root.
+    pub verification_type: [u8; 1],
+    /// Set when the nullifier is written, as the account is created with `init_if_needed`.
+    pub consumed: bool,
+}
+
+impl Nullifier {
+    pub const SEED_PREFIX: &'static [u8] = b"Nullifier";
+
+    /// Whether the account was written by a previous proof rather than just created.
+    pub fn is_consumed(&self) -> bool {
+        self.consumed
+    }
+}
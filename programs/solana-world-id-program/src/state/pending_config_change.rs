@@ -0,0 +1,121 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::SolanaWorldIDProgramError, state::RootSource};
+
+/// A change of a configured value, which the owner proposes with propose_config_change and which
+/// takes effect with execute_config_change once the config change delay has passed.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum ConfigChange {
+    RootExpiry {
+        verification_type: [u8; 1],
+        root_expiry: u64,
+    },
+    AllowedUpdateStaleness {
+        verification_type: [u8; 1],
+        allowed_update_staleness: u64,
+    },
+    VerifyingKeyActivationDelay {
+        verifying_key_activation_delay: u64,
+    },
+    ConfigChangeDelay {
+        config_change_delay: u64,
+    },
+    /// Only the contract, selector or emitter address may change, see
+    /// [`RootSource::has_same_chain_and_kind`].
+    RootSource {
+        verification_type: [u8; 1],
+        source: RootSource,
+    },
+}
+
+impl ConfigChange {
+    pub const MIN_ROOT_EXPIRY: u64 = 1;
+    pub const MAX_ROOT_EXPIRY: u64 = 7 * 24 * 60 * 60;
+    pub const MAX_ALLOWED_UPDATE_STALENESS: u64 = 24 * 60 * 60;
+    pub const MAX_VERIFYING_KEY_ACTIVATION_DELAY: u64 = 30 * 24 * 60 * 60;
+    pub const MAX_CONFIG_CHANGE_DELAY: u64 = 30 * 24 * 60 * 60;
+
+    /// Whether only the owner may propose, execute or cancel the change. A root source controls
+    /// which roots are accepted and the delays bound how quickly integrators can react, so the
+    /// config manager only tunes `root_expiry` and `allowed_update_staleness`.
+    pub fn is_owner_only(&self) -> bool {
+        match self {
+            Self::RootExpiry { .. } | Self::AllowedUpdateStaleness { .. } => false,
+            Self::VerifyingKeyActivationDelay { .. }
+            | Self::ConfigChangeDelay { .. }
+            | Self::RootSource { .. } => true,
+        }
+    }
+
+    /// SEED: Identifies the changed value, so that at most one change of each value is pending.
+    pub fn seed(&self) -> [u8; 2] {
+        match self {
+            Self::RootExpiry {
+                verification_type, ..
+            } => [0, verification_type[0]],
+            Self::AllowedUpdateStaleness {
+                verification_type, ..
+            } => [1, verification_type[0]],
+            Self::VerifyingKeyActivationDelay { .. } => [2, 0],
+            Self::ConfigChangeDelay { .. } => [3, 0],
+            Self::RootSource {
+                verification_type, ..
+            } => [4, verification_type[0]],
+        }
+    }
+
+    /// Verification type whose `VerificationTypeConfig` is changed, if any.
+    pub fn verification_type(&self) -> Option<[u8; 1]> {
+        match self {
+            Self::RootExpiry {
+                verification_type, ..
+            }
+            | Self::AllowedUpdateStaleness {
+                verification_type, ..
+            }
+            | Self::RootSource {
+                verification_type, ..
+            } => Some(*verification_type),
+            Self::VerifyingKeyActivationDelay { .. } | Self::ConfigChangeDelay { .. } => None,
+        }
+    }
+
+    /// Checks that the new value is within its bounds, so that e.g. a compromised owner key
+    /// cannot make every historical root valid again with a huge `root_expiry`.
+    pub fn check_bounds(&self) -> Result<()> {
+        let in_bounds = match *self {
+            Self::RootExpiry { root_expiry, .. } => {
+                (Self::MIN_ROOT_EXPIRY..=Self::MAX_ROOT_EXPIRY).contains(&root_expiry)
+            }
+            Self::AllowedUpdateStaleness {
+                allowed_update_staleness,
+                ..
+            } => allowed_update_staleness <= Self::MAX_ALLOWED_UPDATE_STALENESS,
+            Self::VerifyingKeyActivationDelay {
+                verifying_key_activation_delay,
+            } => verifying_key_activation_delay <= Self::MAX_VERIFYING_KEY_ACTIVATION_DELAY,
+            Self::ConfigChangeDelay {
+                config_change_delay,
+            } => config_change_delay <= Self::MAX_CONFIG_CHANGE_DELAY,
+            // Checked against the current source when executed.
+            Self::RootSource { .. } => true,
+        };
+        require!(in_bounds, SolanaWorldIDProgramError::ConfigValueOutOfBounds);
+        Ok(())
+    }
+}
+
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct PendingConfigChange {
+    pub bump: u8,
+    pub change: ConfigChange,
+    /// Time (in seconds) from which the change may be executed.
+    pub executable_at: u64,
+    /// Payer of this account, reimbursed when the change is executed or cancelled.
+    pub refund_recipient: Pubkey,
+}
+
+impl PendingConfigChange {
+    pub const SEED_PREFIX: &'static [u8] = b"PendingConfigChange";
+}
@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+use crate::error::SolanaWorldIDProgramError;
+
+/// Relayers which may update roots with a QueryResponse while
+/// [`Config::relayer_allowlist_enabled`](crate::state::Config::relayer_allowlist_enabled) is set.
+/// Otherwise anyone may relay roots and become the refund recipient of their `Root` accounts.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct RelayerAllowlist {
+    pub bump: u8,
+    #[max_len(RelayerAllowlist::MAX_RELAYERS)]
+    pub relayers: Vec<Pubkey>,
+}
+
+impl RelayerAllowlist {
+    pub const SEED_PREFIX: &'static [u8] = b"RelayerAllowlist";
+
+    pub const MAX_RELAYERS: usize = 20;
+
+    /// Checks that the relayers are distinct and at most [`RelayerAllowlist::MAX_RELAYERS`].
+    pub fn check(&self) -> Result<()> {
+        require!(
+            self.relayers.len() <= Self::MAX_RELAYERS
+                && self
+                    .relayers
+                    .iter()
+                    .enumerate()
+                    .all(|(i, relayer)| !self.relayers[..i].contains(relayer)),
+            SolanaWorldIDProgramError::InvalidRelayerAllowlist
+        );
+        Ok(())
+    }
+}
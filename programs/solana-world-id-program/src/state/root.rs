@@ -16,15 +16,22 @@ pub struct Root {
     pub root: [u8; 32],
     /// SEED: Verification type.
     pub verification_type: [u8; 1],
+    /// Set by revoke_root. Proofs are no longer verified against the root, which may be cleaned
+    /// up right away.
+    pub revoked: bool,
 }
 
 impl Root {
     pub const SEED_PREFIX: &'static [u8] = b"Root";
     pub const VERIFICATION_TYPE_QUERY: &'static [u8; 1] = &[0x00];
 
-    pub fn is_active(&self, timestamp: &u64, config_root_expiry: &u64) -> bool {
+    pub fn is_active(&self, timestamp: &u64, root_expiry: &u64) -> bool {
+        self.expiry_time(root_expiry) >= *timestamp
+    }
+
+    /// Time (in seconds) after which this root is expired, unless it is the latest root.
+    pub fn expiry_time(&self, root_expiry: &u64) -> u64 {
         let read_block_time_in_secs = self.read_block_time / 1_000_000;
-        let expiry_time = read_block_time_in_secs.saturating_add(*config_root_expiry);
-        expiry_time >= *timestamp
+        read_block_time_in_secs.saturating_add(*root_expiry)
     }
 }
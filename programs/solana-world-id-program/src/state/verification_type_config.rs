@@ -0,0 +1,91 @@
+use anchor_lang::prelude::*;
+
+/// Where the roots of a verification type come from and how they are delivered.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum RootSource {
+    /// An `eth_call` which returns a World ID root, e.g. `latestRoot()` on the World ID Identity Manager
+    /// or a bridged WorldID contract (such as OpWorldID or PolygonWorldID) on an L2.
+    /// Accepted by update_root_with_query.
+    Query {
+        /// Wormhole chain id of the chain which is queried.
+        chain_id: u16,
+        /// Address of the contract which is called.
+        contract: [u8; 20],
+        /// Selector of the call which returns the latest root.
+        selector: [u8; 4],
+    },
+    /// A Wormhole message published by a known emitter whose payload is a World ID root.
+    /// Accepted by update_root_with_vaa.
+    Vaa {
+        /// Wormhole chain id of the emitter.
+        emitter_chain: u16,
+        /// Universal address of the emitter.
+        emitter_address: [u8; 32],
+    },
+}
+
+impl RootSource {
+    /// Whether `other` is the same kind of source on the same chain, whose roots are ordered by the
+    /// same block numbers or VAA sequences in [`LatestRoot`](super::LatestRoot).
+    pub fn has_same_chain_and_kind(&self, other: &Self) -> bool {
+        match (*self, *other) {
+            (Self::Query { chain_id: a, .. }, Self::Query { chain_id: b, .. }) => a == b,
+            (
+                Self::Vaa {
+                    emitter_chain: a, ..
+                },
+                Self::Vaa {
+                    emitter_chain: b, ..
+                },
+            ) => a == b,
+            _ => false,
+        }
+    }
+}
+
+/// A verifying key proposed by propose_verifying_key, which replaces the current one when activated.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq, InitSpace)]
+pub struct PendingVerifyingKey {
+    /// Id of the proposed [`VerifyingKey`](super::VerifyingKey), `None` for the built-in key.
+    pub key_id: Option<[u8; 1]>,
+    /// Time (in seconds) from which the key may be activated.
+    pub activation_time: u64,
+    /// Time (in seconds) for which the replaced key remains accepted after the activation.
+    pub grace_period: u64,
+}
+
+/// A verifying key replaced by activate_verifying_key, which is accepted during its grace period.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq, InitSpace)]
+pub struct PreviousVerifyingKey {
+    /// Id of the replaced [`VerifyingKey`](super::VerifyingKey), `None` for the built-in key.
+    pub key_id: Option<[u8; 1]>,
+    /// Last time (in seconds) at which proofs are verified with the key.
+    pub accepted_until: u64,
+}
+
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct VerificationTypeConfig {
+    pub bump: u8,
+    /// SEED: Verification type.
+    pub verification_type: [u8; 1],
+    /// Whether roots of this verification type may be updated and used to verify proofs.
+    pub enabled: bool,
+    /// Time (in seconds) after which a root should be considered expired.
+    pub root_expiry: u64,
+    /// Time (in seconds) after which an attempted update should be rejected.
+    pub allowed_update_staleness: u64,
+    /// Source from which roots of this verification type are accepted.
+    pub source: RootSource,
+    /// Id of the [`VerifyingKey`](super::VerifyingKey) which proofs of this verification type
+    /// are verified with. `None` for the built-in [`VERIFYING_KEY`](crate::VERIFYING_KEY).
+    pub verifying_key_id: Option<[u8; 1]>,
+    /// Key which replaces the current one once activated.
+    pub pending_verifying_key: Option<PendingVerifyingKey>,
+    /// Key which was replaced by the current one and may still be accepted.
+    pub previous_verifying_key: Option<PreviousVerifyingKey>,
+}
+
+impl VerificationTypeConfig {
+    pub const SEED_PREFIX: &'static [u8] = b"VerificationTypeConfig";
+}
@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+use groth16_solana::groth16::Groth16Verifyingkey;
+
+/// A Groth16 verifying key registered by the owner, e.g. for a World ID circuit of another tree
+/// depth, which verification types may use instead of the built-in
+/// [`VERIFYING_KEY`](crate::VERIFYING_KEY).
+///
+/// The points are big-endian, as for groth16-solana, with beta, gamma and delta negated like the
+/// built-in key, so that proofs are used as ABI-encoded.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct VerifyingKey {
+    pub bump: u8,
+    /// SEED: Verifying key id.
+    pub key_id: [u8; 1],
+    /// Whether the key has been retired, after which no proof is verified with it.
+    pub retired: bool,
+    pub alpha_g1: [u8; 64],
+    pub beta_g2: [u8; 128],
+    pub gamma_g2: [u8; 128],
+    pub delta_g2: [u8; 128],
+    /// One point for each of the four public inputs of a World ID proof, plus one.
+    pub ic: [[u8; 64]; 5],
+}
+
+impl VerifyingKey {
+    pub const SEED_PREFIX: &'static [u8] = b"VerifyingKey";
+
+    pub fn to_groth16(&self) -> Groth16Verifyingkey<'_> {
+        Groth16Verifyingkey {
+            nr_pubinputs: self.ic.len(),
+            vk_alpha_g1: self.alpha_g1,
+            vk_beta_g2: self.beta_g2,
+            vk_gamme_g2: self.gamma_g2,
+            vk_delta_g2: self.delta_g2,
+            vk_ic: &self.ic,
+        }
+    }
+}
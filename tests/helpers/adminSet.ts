@@ -0,0 +1,28 @@
+import * as anchor from "@coral-xyz/anchor";
+
+export function deriveAdminSetKey(
+  worldIdProgramId: anchor.web3.PublicKey
+): anchor.web3.PublicKey {
+  return anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("AdminSet")],
+    worldIdProgramId
+  )[0];
+}
+
+// Remaining accounts of execute_as_admin_set: the accounts of the invoked
+// instruction, for which only the program signs as the admin set, followed by
+// the signing members.
+export function adminSetRemainingAccounts(
+  worldIdProgramId: anchor.web3.PublicKey,
+  ix: anchor.web3.TransactionInstruction,
+  signers: anchor.web3.PublicKey[]
+): anchor.web3.AccountMeta[] {
+  const adminSet = deriveAdminSetKey(worldIdProgramId);
+  return [
+    ...ix.keys.map((key) => ({
+      ...key,
+      isSigner: key.isSigner && !key.pubkey.equals(adminSet),
+    })),
+    ...signers.map((pubkey) => ({ pubkey, isSigner: true, isWritable: false })),
+  ];
+}
@@ -0,0 +1,39 @@
+import * as anchor from "@coral-xyz/anchor";
+
+// https://github.com/coral-xyz/anchor/blob/v0.30.1/lang/src/event.rs
+const EVENT_IX_TAG_LENGTH = 8;
+
+/**
+ * Decodes the events emitted via `emit_cpi!` by a confirmed transaction.
+ * These are the program's self-CPI inner instructions, which are not
+ * subject to log truncation.
+ */
+export async function getCpiEvents<T extends anchor.Idl>(
+  program: anchor.Program<T>,
+  signature: string
+): Promise<anchor.Event[]> {
+  const tx = await program.provider.connection.getTransaction(signature, {
+    commitment: "confirmed",
+    maxSupportedTransactionVersion: 0,
+  });
+  if (tx === null) {
+    throw Error("transaction not found");
+  }
+  const accountKeys = tx.transaction.message.getAccountKeys();
+  const events: anchor.Event[] = [];
+  for (const { instructions } of tx.meta?.innerInstructions ?? []) {
+    for (const ix of instructions) {
+      if (!accountKeys.get(ix.programIdIndex)?.equals(program.programId)) {
+        continue;
+      }
+      const data = Buffer.from(anchor.utils.bytes.bs58.decode(ix.data));
+      const event = program.coder.events.decode(
+        data.subarray(EVENT_IX_TAG_LENGTH).toString("base64")
+      );
+      if (event) {
+        events.push(event);
+      }
+    }
+  }
+  return events;
+}
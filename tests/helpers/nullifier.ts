@@ -0,0 +1,12 @@
+import * as anchor from "@coral-xyz/anchor";
+
+export function deriveNullifierKey(
+  worldIdProgramId: anchor.web3.PublicKey,
+  externalNullifierHash: Buffer,
+  nullifierHash: Buffer
+): anchor.web3.PublicKey {
+  return anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("Nullifier"), externalNullifierHash, nullifierHash],
+    worldIdProgramId
+  )[0];
+}
@@ -0,0 +1,39 @@
+import * as anchor from "@coral-xyz/anchor";
+
+// Mirrors ConfigChange::seed in programs/solana-world-id-program/src/state/pending_config_change.rs
+const CONFIG_CHANGE_SEEDS = {
+  rootExpiry: 0,
+  allowedUpdateStaleness: 1,
+  verifyingKeyActivationDelay: 2,
+  configChangeDelay: 3,
+  rootSource: 4,
+};
+
+type ConfigChange = {
+  [variant: string]: { verificationType?: number[] };
+};
+
+// Returns the verification type whose config is changed, if any.
+export function configChangeVerificationType(
+  change: ConfigChange
+): number | null {
+  const [variant] = Object.keys(change);
+  return change[variant].verificationType?.[0] ?? null;
+}
+
+export function derivePendingConfigChangeKey(
+  worldIdProgramId: anchor.web3.PublicKey,
+  change: ConfigChange
+): anchor.web3.PublicKey {
+  const [variant] = Object.keys(change);
+  return anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("PendingConfigChange"),
+      Buffer.from([
+        CONFIG_CHANGE_SEEDS[variant],
+        configChangeVerificationType(change) ?? 0,
+      ]),
+    ],
+    worldIdProgramId
+  )[0];
+}
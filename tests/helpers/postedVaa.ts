@@ -0,0 +1,53 @@
+import * as anchor from "@coral-xyz/anchor";
+
+// Posted VAA accounts loaded into the test validator from tests/accounts/core_bridge_mainnet.
+// All are published at the max u32 timestamp, so that they are never stale.
+// Each payload is `0x01 || root`, see programs/solana-world-id-program/src/instructions/update_root_with_vaa.rs
+export const MOCK_VAA_EMITTER_CHAIN = 2;
+export const MOCK_VAA_EMITTER_ADDRESS =
+  "000000000000000000000000e0a4f8b6d9e334b37c76e93d1e60420e08487194";
+
+export const mockPostedVaas = {
+  // sequence 0
+  root0: {
+    address: new anchor.web3.PublicKey(
+      "HEnBrfFNaS5NMuwvu47rczf5kv9bzdWTVGY5a6719tQE"
+    ),
+    root: "63cbdb23d05ae98a537a3e445788e7777248707e94275f0ab2fe79647875f6f5",
+  },
+  // sequence 1
+  root1: {
+    address: new anchor.web3.PublicKey(
+      "AiGakTVfp1XRdxdTgZe5fb9ZhDCmGbxPBXmjJAX9KxkS"
+    ),
+    root: "82c04a35744ee4547499d0f9075bc5abd4427bf180d5acd8abd5ad14c7ed9315",
+  },
+  // sequence 0
+  staleSequence: {
+    address: new anchor.web3.PublicKey(
+      "B4orqtzvNufDmauNdkFbN9iBE3ZHkRdsLM26byemC3RQ"
+    ),
+    root: "c69951c401366c50c9271fc74cc6734dec0dee2aa4e5f0059e15685f57eaa12c",
+  },
+  // payload id 2
+  invalidPayload: {
+    address: new anchor.web3.PublicKey(
+      "3Qk1zTpCDQydf4wsyRNNUD2DAbjVT2qnsFSQr2aNpg3V"
+    ),
+    root: "4fd8011d4044352917b645821735ce343a14ad68934592c1a0ff74f3593d786a",
+  },
+  // emitter chain 4
+  invalidEmitterChain: {
+    address: new anchor.web3.PublicKey(
+      "BTDVbGGKiB27RjhHLMyqW51kWvy4xDjLEbbDp6GjzYF1"
+    ),
+    root: "4fd8011d4044352917b645821735ce343a14ad68934592c1a0ff74f3593d786a",
+  },
+  // emitter address 0x4bc29e425f4104daf5a111bfb8f82de08a25792c
+  invalidEmitterAddress: {
+    address: new anchor.web3.PublicKey(
+      "3KqeNgwwyWAxH2FeVorExLWVpxYwZJLzTmknNTcwCdWt"
+    ),
+    root: "4fd8011d4044352917b645821735ce343a14ad68934592c1a0ff74f3593d786a",
+  },
+};
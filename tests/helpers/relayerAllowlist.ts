@@ -0,0 +1,10 @@
+import * as anchor from "@coral-xyz/anchor";
+
+export function deriveRelayerAllowlistKey(
+  worldIdProgramId: anchor.web3.PublicKey
+): anchor.web3.PublicKey {
+  return anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("RelayerAllowlist")],
+    worldIdProgramId
+  )[0];
+}
@@ -0,0 +1,62 @@
+import * as anchor from "@coral-xyz/anchor";
+import { keccak256 } from "@ethersproject/keccak256";
+
+// Same as wormhole_query_sdk::MESSAGE_PREFIX
+export const QUERY_MESSAGE_PREFIX = Buffer.from(
+  "query_response_0000000000000000000|"
+);
+
+// https://docs.solanalabs.com/runtime/programs#secp256k1-program
+const SIGNATURE_OFFSETS_SIZE = 11;
+const ETH_ADDRESS_SIZE = 20;
+const SIGNATURE_SIZE = 65;
+
+export function queryMessageHash(bytes: Uint8Array): Buffer {
+  return Buffer.concat([
+    QUERY_MESSAGE_PREFIX,
+    Buffer.from(keccak256(bytes).substring(2), "hex"),
+  ]);
+}
+
+/**
+ * Creates a secp256k1 program instruction which verifies every query signature over the query message hash.
+ * @param querySignatures signatures as returned by QueryProxyMock.sign, i.e. `r || s || v || guardianIndex` in hex
+ * @param guardianKeys the Ethereum addresses of the guardian set which signed
+ * @param bytes the serialized QueryResponse
+ * @param instructionIndex the index of this instruction in the transaction
+ */
+export function createSecp256k1Instruction(
+  querySignatures: string[],
+  guardianKeys: Buffer[],
+  bytes: Uint8Array,
+  instructionIndex: number = 0
+): anchor.web3.TransactionInstruction {
+  const message = queryMessageHash(bytes);
+  const numSignatures = querySignatures.length;
+  const dataStart = 1 + numSignatures * SIGNATURE_OFFSETS_SIZE;
+  const entrySize = ETH_ADDRESS_SIZE + SIGNATURE_SIZE;
+  const messageOffset = dataStart + numSignatures * entrySize;
+  const data = Buffer.alloc(messageOffset + message.length);
+  data.writeUInt8(numSignatures, 0);
+  querySignatures.forEach((signature, i) => {
+    const guardianIndex = parseInt(signature.substring(130, 132), 16);
+    const ethAddressOffset = dataStart + i * entrySize;
+    const signatureOffset = ethAddressOffset + ETH_ADDRESS_SIZE;
+    const offsets = 1 + i * SIGNATURE_OFFSETS_SIZE;
+    data.writeUInt16LE(signatureOffset, offsets);
+    data.writeUInt8(instructionIndex, offsets + 2);
+    data.writeUInt16LE(ethAddressOffset, offsets + 3);
+    data.writeUInt8(instructionIndex, offsets + 5);
+    data.writeUInt16LE(messageOffset, offsets + 6);
+    data.writeUInt16LE(message.length, offsets + 8);
+    data.writeUInt8(instructionIndex, offsets + 10);
+    guardianKeys[guardianIndex].copy(data, ethAddressOffset);
+    Buffer.from(signature.substring(0, 130), "hex").copy(data, signatureOffset);
+  });
+  message.copy(data, messageOffset);
+  return new anchor.web3.TransactionInstruction({
+    keys: [],
+    programId: anchor.web3.Secp256k1Program.programId,
+    data,
+  });
+}
@@ -0,0 +1,11 @@
+import * as anchor from "@coral-xyz/anchor";
+
+export function deriveVerificationTypeConfigKey(
+  worldIdProgramId: anchor.web3.PublicKey,
+  type: number
+): anchor.web3.PublicKey {
+  return anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("VerificationTypeConfig"), Buffer.from([type])],
+    worldIdProgramId
+  )[0];
+}
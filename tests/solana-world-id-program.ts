@@ -17,10 +17,33 @@ import { BN } from "bn.js";
 import { assert, expect, use } from "chai";
 import chaiAsPromised from "chai-as-promised";
 import { SolanaWorldIdProgram } from "../target/types/solana_world_id_program";
+import {
+  adminSetRemainingAccounts,
+  deriveAdminSetKey,
+} from "./helpers/adminSet";
 import { deriveConfigKey } from "./helpers/config";
-import { deriveGuardianSetKey } from "./helpers/guardianSet";
+import { getCpiEvents } from "./helpers/events";
+import { deriveGuardianSetKey, getGuardianSet } from "./helpers/guardianSet";
 import { deriveLatestRootKey } from "./helpers/latestRoot";
+import { deriveNullifierKey } from "./helpers/nullifier";
+import {
+  configChangeVerificationType,
+  derivePendingConfigChangeKey,
+} from "./helpers/pendingConfigChange";
+import {
+  MOCK_VAA_EMITTER_ADDRESS,
+  MOCK_VAA_EMITTER_CHAIN,
+  mockPostedVaas,
+} from "./helpers/postedVaa";
+import { deriveRelayerAllowlistKey } from "./helpers/relayerAllowlist";
 import { deriveRootKey } from "./helpers/root";
+import { createSecp256k1Instruction } from "./helpers/secp256k1";
+import { deriveVerificationTypeConfigKey } from "./helpers/verificationTypeConfig";
+import {
+  builtInVerifyingKey,
+  deriveVerifyingKeyKey,
+  verifyingKeyArgs,
+} from "./helpers/verifyingKey";
 import {
   appIdActionToExternalNullifierHash,
   hashToField,
@@ -73,6 +96,15 @@ describe("solana-world-id-program", () => {
   const noQuorumMockGuardianSetIndex = 7;
   const twoMockGuardianSetIndex = 8;
   const nineteenMockGuardianSetIndex = 9;
+  const ethRootSource = {
+    query: {
+      chainId: ETH_CHAIN_ID,
+      contract: [
+        ...Buffer.from(ETH_WORLD_ID_IDENTITY_MANAGER.substring(2), "hex"),
+      ],
+      selector: [...Buffer.from(LATEST_ROOT_SIGNATURE.substring(2), "hex")],
+    },
+  };
 
   // This is an example ISuccessResult from IDKitWidget's onSuccess callback
   const idkitSuccessResult = {
@@ -107,6 +139,58 @@ describe("solana-world-id-program", () => {
       .rpc();
   }
 
+  async function getGuardianKeys(guardianSetIndex: number) {
+    return (
+      await getGuardianSet(
+        anchor.getProvider().connection,
+        coreBridgeAddress,
+        guardianSetIndex
+      )
+    ).keys;
+  }
+
+  // Returns a response from the same block as the mock query response,
+  // advanced by `blocks` and with the given root hash.
+  function futureQueryResponseBytes(blocks: number, rootHash: string) {
+    const futureResponse = QueryResponse.from(mockQueryResponse.bytes);
+    const futureEthCallQueryResponse = futureResponse.responses[0]
+      .response as EthCallQueryResponse;
+    futureEthCallQueryResponse.blockNumber += BigInt(blocks);
+    futureEthCallQueryResponse.results[0] = `0x${rootHash}`;
+    return futureResponse.serialize();
+  }
+
+  type ConfigChange = Parameters<typeof program.methods.proposeConfigChange>[0];
+
+  // Proposes and executes a config change, which takes effect immediately
+  // since the suite initializes with no config change delay.
+  // Returns the signature of the execution.
+  async function changeConfig(change: ConfigChange): Promise<string> {
+    const pendingConfigChange = derivePendingConfigChangeKey(
+      program.programId,
+      change
+    );
+    const verificationType = configChangeVerificationType(change);
+    await program.methods
+      .proposeConfigChange(change)
+      .accountsPartial({ pendingConfigChange })
+      .rpc();
+    return program.methods
+      .executeConfigChange()
+      .accountsPartial({
+        pendingConfigChange,
+        verificationTypeConfig:
+          verificationType === null
+            ? null
+            : deriveVerificationTypeConfigKey(
+                program.programId,
+                verificationType
+              ),
+        refundRecipient: anchor.getProvider().publicKey,
+      })
+      .rpc({ commitment: "confirmed" });
+  }
+
   it(fmtTest("initialize", "Rejects deployer account mismatch"), async () => {
     {
       const p = anchor.getProvider();
@@ -189,6 +273,38 @@ describe("solana-world-id-program", () => {
     );
   });
 
+  it(fmtTest("initialize", "Rejects out of bounds values"), async () => {
+    const programData = anchor.web3.PublicKey.findProgramAddressSync(
+      [program.programId.toBuffer()],
+      new anchor.web3.PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
+    )[0];
+    const twentyFourHours = new BN(24 * 60 * 60);
+    const fiveMinutes = new BN(5 * 60);
+    await expect(
+      program.methods
+        .initialize({
+          rootExpiry: new BN(0),
+          allowedUpdateStaleness: fiveMinutes,
+        })
+        .accountsPartial({
+          programData,
+        })
+        .rpc()
+    ).to.be.rejectedWith("ConfigValueOutOfBounds.");
+    await expect(
+      program.methods
+        .initialize({
+          rootExpiry: twentyFourHours,
+          allowedUpdateStaleness: fiveMinutes,
+          configChangeDelay: new BN(31 * 24 * 60 * 60),
+        })
+        .accountsPartial({
+          programData,
+        })
+        .rpc()
+    ).to.be.rejectedWith("ConfigValueOutOfBounds.");
+  });
+
   it(fmtTest("initialize", "Successfully initializes"), async () => {
     const programData = anchor.web3.PublicKey.findProgramAddressSync(
       [program.programId.toBuffer()],
@@ -201,6 +317,8 @@ describe("solana-world-id-program", () => {
         .initialize({
           rootExpiry: twentyFourHours,
           allowedUpdateStaleness: fiveMinutes,
+          // config changes are exercised without waiting
+          configChangeDelay: new BN(0),
         })
         .accountsPartial({
           programData,
@@ -210,16 +328,44 @@ describe("solana-world-id-program", () => {
     const config = await program.account.config.fetch(
       deriveConfigKey(program.programId)
     );
-    assert(
-      config.allowedUpdateStaleness.eq(fiveMinutes),
-      "allowed update staleness does not match"
-    );
     assert(
       config.owner.equals(anchor.getProvider().publicKey),
       "owner does not match"
     );
     assert(config.pendingOwner === null, "pending owner is set");
-    assert(config.rootExpiry.eq(twentyFourHours), "root expiry does not match");
+    assert(
+      config.configChangeDelay.eq(new BN(0)),
+      "config change delay does not match"
+    );
+    const verificationTypeConfig =
+      await program.account.verificationTypeConfig.fetch(
+        deriveVerificationTypeConfigKey(program.programId, 0)
+      );
+    assert(verificationTypeConfig.enabled, "verification type is disabled");
+    assert(
+      verificationTypeConfig.allowedUpdateStaleness.eq(fiveMinutes),
+      "allowed update staleness does not match"
+    );
+    assert(
+      verificationTypeConfig.rootExpiry.eq(twentyFourHours),
+      "root expiry does not match"
+    );
+    assert(
+      verificationTypeConfig.source.query.chainId === ETH_CHAIN_ID,
+      "source chain id does not match"
+    );
+    assert(
+      Buffer.from(verificationTypeConfig.source.query.contract).equals(
+        Buffer.from(ETH_WORLD_ID_IDENTITY_MANAGER.substring(2), "hex")
+      ),
+      "source contract does not match"
+    );
+    assert(
+      Buffer.from(verificationTypeConfig.source.query.selector).equals(
+        Buffer.from(LATEST_ROOT_SIGNATURE.substring(2), "hex")
+      ),
+      "source selector does not match"
+    );
   });
 
   it(fmtTest("initialize", "Rejects duplicate initialization"), async () => {
@@ -343,6 +489,7 @@ describe("solana-world-id-program", () => {
           .updateRootWithQuery(
             Buffer.from(mockQueryResponse.bytes, "hex"),
             [...Buffer.from(rootHash, "hex")],
+            [0],
             0
           )
           .accountsPartial({
@@ -364,6 +511,7 @@ describe("solana-world-id-program", () => {
           .updateRootWithQuery(
             Buffer.from(mockQueryResponse.bytes, "hex"),
             [...Buffer.from(rootHash, "hex")],
+            [0],
             mockGuardianSetIndex
           )
           .accountsPartial({
@@ -388,6 +536,7 @@ describe("solana-world-id-program", () => {
           .updateRootWithQuery(
             Buffer.from(mockQueryResponse.bytes, "hex"),
             [...Buffer.from(rootHash, "hex")],
+            [0],
             mockGuardianSetIndex
           )
           .accountsPartial({
@@ -416,6 +565,7 @@ describe("solana-world-id-program", () => {
           .updateRootWithQuery(
             Buffer.from(mockQueryResponse.bytes, "hex"),
             new Array(32).fill(0),
+            [0],
             mockGuardianSetIndex
           )
           .accountsPartial({
@@ -438,6 +588,7 @@ describe("solana-world-id-program", () => {
           .updateRootWithQuery(
             Buffer.from(mockQueryResponse.bytes + "00", "hex"),
             [...Buffer.from(rootHash, "hex")],
+            [0],
             mockGuardianSetIndex
           )
           .accountsPartial({
@@ -464,6 +615,7 @@ describe("solana-world-id-program", () => {
           .updateRootWithQuery(
             badBytes,
             [...Buffer.from(rootHash, "hex")],
+            [0],
             mockGuardianSetIndex
           )
           .accountsPartial({
@@ -488,6 +640,7 @@ describe("solana-world-id-program", () => {
           .updateRootWithQuery(
             Buffer.from(mockQueryResponse.bytes, "hex"),
             [...Buffer.from(rootHash, "hex")],
+            [0],
             expiredMockGuardianSetIndex
           )
           .accountsPartial({
@@ -510,6 +663,7 @@ describe("solana-world-id-program", () => {
         .updateRootWithQuery(
           Buffer.from(mockQueryResponse.bytes, "hex"),
           [...Buffer.from(rootHash, "hex")],
+          [0],
           noQuorumMockGuardianSetIndex
         )
         .accountsPartial({
@@ -542,6 +696,7 @@ describe("solana-world-id-program", () => {
           .updateRootWithQuery(
             Buffer.from(mockQueryResponse.bytes, "hex"),
             [...Buffer.from(rootHash, "hex")],
+            [0],
             twoMockGuardianSetIndex
           )
           .accountsPartial({
@@ -564,6 +719,7 @@ describe("solana-world-id-program", () => {
           .updateRootWithQuery(
             Buffer.from(mockQueryResponse.bytes, "hex"),
             [...Buffer.from(rootHash, "hex")],
+            [0],
             twoMockGuardianSetIndex
           )
           .accountsPartial({
@@ -591,6 +747,7 @@ describe("solana-world-id-program", () => {
           .updateRootWithQuery(
             Buffer.from(mockQueryResponse.bytes, "hex"),
             [...Buffer.from(rootHash, "hex")],
+            [0],
             noQuorumMockGuardianSetIndex
           )
           .accountsPartial({
@@ -618,6 +775,7 @@ describe("solana-world-id-program", () => {
           .updateRootWithQuery(
             Buffer.from(mockQueryResponse.bytes, "hex"),
             [...Buffer.from(rootHash, "hex")],
+            [0],
             mockGuardianSetIndex
           )
           .accountsPartial({
@@ -651,6 +809,7 @@ describe("solana-world-id-program", () => {
           .updateRootWithQuery(
             Buffer.from(mockQueryResponse.bytes, "hex"),
             [...Buffer.from(rootHash, "hex")],
+            [0],
             mockGuardianSetIndex
           )
           .accountsPartial({
@@ -683,6 +842,7 @@ describe("solana-world-id-program", () => {
           .updateRootWithQuery(
             Buffer.from(invalidResponseBytes),
             [...Buffer.from(rootHash, "hex")],
+            [0],
             mockGuardianSetIndex
           )
           .accountsPartial({
@@ -713,6 +873,7 @@ describe("solana-world-id-program", () => {
           .updateRootWithQuery(
             Buffer.from(invalidResponseBytes),
             [...Buffer.from(rootHash, "hex")],
+            [0],
             mockGuardianSetIndex
           )
           .accountsPartial({
@@ -761,6 +922,7 @@ describe("solana-world-id-program", () => {
           .updateRootWithQuery(
             Buffer.from(mockQueryResponse.bytes, "hex"),
             [...Buffer.from(rootHash, "hex")],
+            [0],
             mockGuardianSetIndex
           )
           .accountsPartial({
@@ -796,6 +958,7 @@ describe("solana-world-id-program", () => {
           .updateRootWithQuery(
             Buffer.from(invalidResponseBytes),
             [...Buffer.from(rootHash, "hex")],
+            [0],
             mockGuardianSetIndex
           )
           .accountsPartial({
@@ -830,6 +993,7 @@ describe("solana-world-id-program", () => {
           .updateRootWithQuery(
             Buffer.from(invalidResponseBytes),
             [...Buffer.from(rootHash, "hex")],
+            [0],
             mockGuardianSetIndex
           )
           .accountsPartial({
@@ -862,6 +1026,7 @@ describe("solana-world-id-program", () => {
           .updateRootWithQuery(
             Buffer.from(invalidResponseBytes),
             [...Buffer.from(rootHash, "hex")],
+            [0],
             mockGuardianSetIndex
           )
           .accountsPartial({
@@ -892,6 +1057,7 @@ describe("solana-world-id-program", () => {
           .updateRootWithQuery(
             Buffer.from(invalidResponseBytes),
             [...Buffer.from(rootHash, "hex")],
+            [0],
             mockGuardianSetIndex
           )
           .accountsPartial({
@@ -922,6 +1088,7 @@ describe("solana-world-id-program", () => {
           .updateRootWithQuery(
             Buffer.from(invalidResponseBytes),
             [...Buffer.from(rootHash, "hex")],
+            [0],
             mockGuardianSetIndex
           )
           .accountsPartial({
@@ -976,6 +1143,7 @@ describe("solana-world-id-program", () => {
           .updateRootWithQuery(
             Buffer.from(invalidResponseBytes),
             [...Buffer.from(rootHash, "hex")],
+            [0],
             mockGuardianSetIndex
           )
           .accountsPartial({
@@ -1011,6 +1179,7 @@ describe("solana-world-id-program", () => {
           .updateRootWithQuery(
             Buffer.from(invalidResponseBytes),
             [...Buffer.from(rootHash, "hex")],
+            [0],
             mockGuardianSetIndex
           )
           .accountsPartial({
@@ -1043,6 +1212,7 @@ describe("solana-world-id-program", () => {
           .updateRootWithQuery(
             Buffer.from(invalidResponseBytes),
             [...Buffer.from(rootHash, "hex")],
+            [0],
             mockGuardianSetIndex
           )
           .accountsPartial({
@@ -1059,18 +1229,25 @@ describe("solana-world-id-program", () => {
 
   it(
     fmtTest(
-      "set_allowed_update_staleness",
+      "execute_config_change",
       "Successfully updates staleness config"
     ),
     async () => {
       const zeroSeconds = new BN(0);
-      await expect(program.methods.setAllowedUpdateStaleness(zeroSeconds).rpc())
-        .to.be.fulfilled;
-      const config = await program.account.config.fetch(
-        deriveConfigKey(program.programId)
-      );
+      await expect(
+        changeConfig({
+          allowedUpdateStaleness: {
+            verificationType: [0],
+            allowedUpdateStaleness: zeroSeconds,
+          },
+        })
+      ).to.be.fulfilled;
+      const verificationTypeConfig =
+        await program.account.verificationTypeConfig.fetch(
+          deriveVerificationTypeConfigKey(program.programId, 0)
+        );
       assert(
-        config.allowedUpdateStaleness.eq(zeroSeconds),
+        verificationTypeConfig.allowedUpdateStaleness.eq(zeroSeconds),
         "config does not match"
       );
     }
@@ -1084,6 +1261,7 @@ describe("solana-world-id-program", () => {
           .updateRootWithQuery(
             Buffer.from(mockQueryResponse.bytes, "hex"),
             [...Buffer.from(rootHash, "hex")],
+            [0],
             mockGuardianSetIndex
           )
           .accountsPartial({
@@ -1100,18 +1278,25 @@ describe("solana-world-id-program", () => {
 
   it(
     fmtTest(
-      "set_allowed_update_staleness",
+      "execute_config_change",
       "Successfully updates staleness config (again)"
     ),
     async () => {
       const fiveMinutes = new BN(5 * 60);
-      await expect(program.methods.setAllowedUpdateStaleness(fiveMinutes).rpc())
-        .to.be.fulfilled;
-      const config = await program.account.config.fetch(
-        deriveConfigKey(program.programId)
-      );
+      await expect(
+        changeConfig({
+          allowedUpdateStaleness: {
+            verificationType: [0],
+            allowedUpdateStaleness: fiveMinutes,
+          },
+        })
+      ).to.be.fulfilled;
+      const verificationTypeConfig =
+        await program.account.verificationTypeConfig.fetch(
+          deriveVerificationTypeConfigKey(program.programId, 0)
+        );
       assert(
-        config.allowedUpdateStaleness.eq(fiveMinutes),
+        verificationTypeConfig.allowedUpdateStaleness.eq(fiveMinutes),
         "config does not match"
       );
     }
@@ -1146,6 +1331,7 @@ describe("solana-world-id-program", () => {
           .updateRootWithQuery(
             Buffer.from(mockQueryResponse.bytes, "hex"),
             [...Buffer.from(rootHash, "hex")],
+            [0],
             nineteenMockGuardianSetIndex
           )
           .accountsPartial({
@@ -1165,6 +1351,170 @@ describe("solana-world-id-program", () => {
     }
   );
 
+  it(
+    fmtTest("initialize_relayer_allowlist", "Rejects owner account mismatch"),
+    async () => {
+      const program = programPaidBy(next_owner);
+      await expect(
+        program.methods.initializeRelayerAllowlist([next_owner.publicKey]).rpc()
+      ).to.be.rejectedWith(
+        "AnchorError caused by account: config. Error Code: ConstraintHasOne."
+      );
+    }
+  );
+
+  it(
+    fmtTest(
+      "initialize_relayer_allowlist",
+      "Successfully initializes a relayer allowlist"
+    ),
+    async () => {
+      await expect(
+        program.methods
+          .initializeRelayerAllowlist([anchor.getProvider().publicKey])
+          .rpc()
+      ).to.be.fulfilled;
+      const relayerAllowlist = await program.account.relayerAllowlist.fetch(
+        deriveRelayerAllowlistKey(program.programId)
+      );
+      assert(
+        relayerAllowlist.relayers.length === 1 &&
+          relayerAllowlist.relayers[0].equals(anchor.getProvider().publicKey),
+        "relayers do not match"
+      );
+    }
+  );
+
+  it(
+    fmtTest("add_relayer", "Rejects a relayer which is already allowed"),
+    async () => {
+      await expect(
+        program.methods.addRelayer(anchor.getProvider().publicKey).rpc()
+      ).to.be.rejectedWith("InvalidRelayerAllowlist.");
+    }
+  );
+
+  it(fmtTest("add_relayer", "Rejects owner account mismatch"), async () => {
+    const program = programPaidBy(next_owner);
+    await expect(
+      program.methods.addRelayer(next_owner.publicKey).rpc()
+    ).to.be.rejectedWith(
+      "AnchorError caused by account: config. Error Code: ConstraintHasOne."
+    );
+  });
+
+  it(
+    fmtTest("set_relayer_allowlist_enabled", "Rejects owner account mismatch"),
+    async () => {
+      const program = programPaidBy(next_owner);
+      await expect(
+        program.methods.setRelayerAllowlistEnabled(true).rpc()
+      ).to.be.rejectedWith(
+        "AnchorError caused by account: config. Error Code: ConstraintHasOne."
+      );
+    }
+  );
+
+  it(
+    fmtTest(
+      "set_relayer_allowlist_enabled",
+      "Successfully enables the relayer allowlist"
+    ),
+    async () => {
+      const tx = await program.methods
+        .setRelayerAllowlistEnabled(true)
+        .rpc({ commitment: "confirmed" });
+      const config = await program.account.config.fetch(
+        deriveConfigKey(program.programId)
+      );
+      assert(config.relayerAllowlistEnabled, "relayer allowlist is disabled");
+      const [event] = await getCpiEvents(program, tx);
+      assert(
+        event.name === "relayerAllowlistEnabledUpdated",
+        "event does not match"
+      );
+    }
+  );
+
+  it(
+    fmtTest("update_root_with_query", "Rejects without the relayer allowlist"),
+    async () => {
+      await expect(
+        program.methods
+          .updateRootWithQuery(
+            Buffer.from(mockQueryResponse.bytes, "hex"),
+            [...Buffer.from(rootHash, "hex")],
+            [0],
+            mockGuardianSetIndex
+          )
+          .accountsPartial({
+            guardianSet: deriveGuardianSetKey(
+              coreBridgeAddress,
+              mockGuardianSetIndex
+            ),
+            guardianSignatures: validMockSignatureSet.publicKey,
+          })
+          .rpc()
+      ).to.be.rejectedWith("RelayerAllowlistNotProvided.");
+    }
+  );
+
+  it(fmtTest("remove_relayer", "Successfully removes a relayer"), async () => {
+    await expect(
+      program.methods.removeRelayer(anchor.getProvider().publicKey).rpc()
+    ).to.be.fulfilled;
+    const relayerAllowlist = await program.account.relayerAllowlist.fetch(
+      deriveRelayerAllowlistKey(program.programId)
+    );
+    assert(relayerAllowlist.relayers.length === 0, "relayers do not match");
+  });
+
+  it(fmtTest("remove_relayer", "Rejects an unknown relayer"), async () => {
+    await expect(
+      program.methods.removeRelayer(anchor.getProvider().publicKey).rpc()
+    ).to.be.rejectedWith("RelayerNotFound.");
+  });
+
+  it(
+    fmtTest(
+      "update_root_with_query",
+      "Rejects a payer which is not on the relayer allowlist"
+    ),
+    async () => {
+      await expect(
+        program.methods
+          .updateRootWithQuery(
+            Buffer.from(mockQueryResponse.bytes, "hex"),
+            [...Buffer.from(rootHash, "hex")],
+            [0],
+            mockGuardianSetIndex
+          )
+          .accountsPartial({
+            guardianSet: deriveGuardianSetKey(
+              coreBridgeAddress,
+              mockGuardianSetIndex
+            ),
+            guardianSignatures: validMockSignatureSet.publicKey,
+            relayerAllowlist: deriveRelayerAllowlistKey(program.programId),
+          })
+          .rpc()
+      ).to.be.rejectedWith("RelayerNotAllowed.");
+    }
+  );
+
+  it(fmtTest("add_relayer", "Successfully adds a relayer"), async () => {
+    await expect(
+      program.methods.addRelayer(anchor.getProvider().publicKey).rpc()
+    ).to.be.fulfilled;
+    const relayerAllowlist = await program.account.relayerAllowlist.fetch(
+      deriveRelayerAllowlistKey(program.programId)
+    );
+    assert(
+      relayerAllowlist.relayers[0].equals(anchor.getProvider().publicKey),
+      "relayers do not match"
+    );
+  });
+
   it(
     fmtTest(
       "update_root_with_query",
@@ -1177,6 +1527,7 @@ describe("solana-world-id-program", () => {
           .updateRootWithQuery(
             Buffer.from(mockQueryResponse.bytes, "hex"),
             [...Buffer.from(rootHash, "hex")],
+            [0],
             mockGuardianSetIndex
           )
           .accountsPartial({
@@ -1185,6 +1536,7 @@ describe("solana-world-id-program", () => {
               mockGuardianSetIndex
             ),
             guardianSignatures: validMockSignatureSet.publicKey,
+            relayerAllowlist: deriveRelayerAllowlistKey(program.programId),
           })
           .rpc()
       ).to.be.fulfilled;
@@ -1260,6 +1612,21 @@ describe("solana-world-id-program", () => {
     }
   );
 
+  it(
+    fmtTest(
+      "set_relayer_allowlist_enabled",
+      "Successfully disables the relayer allowlist"
+    ),
+    async () => {
+      await expect(program.methods.setRelayerAllowlistEnabled(false).rpc()).to
+        .be.fulfilled;
+      const config = await program.account.config.fetch(
+        deriveConfigKey(program.programId)
+      );
+      assert(!config.relayerAllowlistEnabled, "relayer allowlist is enabled");
+    }
+  );
+
   it(
     fmtTest(
       "update_root_with_query",
@@ -1273,6 +1640,7 @@ describe("solana-world-id-program", () => {
           .updateRootWithQuery(
             Buffer.from(mockQueryResponse.bytes, "hex"),
             [...Buffer.from(rootHash, "hex")],
+            [0],
             mockGuardianSetIndex
           )
           .accountsPartial({
@@ -1310,6 +1678,7 @@ describe("solana-world-id-program", () => {
           .updateRootWithQuery(
             Buffer.from(invalidResponseBytes),
             [...Buffer.from(rootHash.substring(2), "hex")],
+            [0],
             mockGuardianSetIndex
           )
           .accountsPartial({
@@ -1356,6 +1725,7 @@ describe("solana-world-id-program", () => {
         .updateRootWithQuery(
           Buffer.from(futureResponseBytes),
           [...Buffer.from(dummyRootHash, "hex")],
+          [0],
           mockGuardianSetIndex
         )
         .accountsPartial({
@@ -1384,15 +1754,23 @@ describe("solana-world-id-program", () => {
   });
 
   it(
-    fmtTest("set_root_expiry", "Successfully updates expiry config"),
+    fmtTest("execute_config_change", "Successfully updates expiry config"),
     async () => {
       const oneSecond = new BN(1);
-      await expect(program.methods.setRootExpiry(oneSecond).rpc()).to.be
-        .fulfilled;
-      const config = await program.account.config.fetch(
-        deriveConfigKey(program.programId)
+      const tx = await changeConfig({
+        rootExpiry: { verificationType: [0], rootExpiry: oneSecond },
+      });
+      const verificationTypeConfig =
+        await program.account.verificationTypeConfig.fetch(
+          deriveVerificationTypeConfigKey(program.programId, 0)
+        );
+      assert(
+        verificationTypeConfig.rootExpiry.eq(oneSecond),
+        "config does not match"
       );
-      assert(config.rootExpiry.eq(oneSecond), "config does not match");
+      const [event] = await getCpiEvents(program, tx);
+      assert(event.name === "rootExpiryUpdated", "event does not match");
+      assert(event.data.rootExpiry.eq(oneSecond), "event does not match");
     }
   );
 
@@ -1434,25 +1812,55 @@ describe("solana-world-id-program", () => {
   );
 
   it(
-    fmtTest("clean_up_root", "Successfully cleans up an expired root"),
+    fmtTest(
+      "revoke_root",
+      "Rejects without the owner or root revoker as signer"
+    ),
     async () => {
-      await sleep(1000);
+      const program = programPaidBy(next_owner);
       await expect(
         program.methods
-          .cleanUpRoot()
-          .accounts({
-            root: deriveRootKey(
-              program.programId,
-              Buffer.from(rootHash, "hex"),
-              0
-            ),
-            latestRoot: deriveLatestRootKey(program.programId, 0),
-          })
+          .revokeRoot([...Buffer.from(rootHash, "hex")], [0])
           .rpc()
-      ).to.be.fulfilled;
+      ).to.be.rejectedWith("InvalidRootRevoker.");
+    }
+  );
+
+  it(fmtTest("revoke_root", "Successfully revokes a root"), async () => {
+    const tx = await program.methods
+      .revokeRoot([...Buffer.from(rootHash, "hex")], [0])
+      .rpc({ commitment: "confirmed" });
+    const root = await program.account.root.fetch(rootKey);
+    assert(root.revoked, "root is not revoked");
+    const [event] = await getCpiEvents(program, tx);
+    assert(event.name === "rootRevoked", "event does not match");
+    assert(event.data.latestRoot === null, "event does not match");
+  });
+
+  it(
+    fmtTest("clean_up_root", "Successfully cleans up an expired root"),
+    async () => {
+      await sleep(1000);
+      const tx = await program.methods
+        .cleanUpRoot()
+        .accounts({
+          root: deriveRootKey(
+            program.programId,
+            Buffer.from(rootHash, "hex"),
+            0
+          ),
+          latestRoot: deriveLatestRootKey(program.programId, 0),
+        })
+        .rpc({ commitment: "confirmed" });
       await expect(program.account.root.fetch(rootKey)).to.be.rejectedWith(
         "Account does not exist or has no data"
       );
+      const [event] = await getCpiEvents(program, tx);
+      assert(event.name === "rootCleanedUp", "event does not match");
+      assert(
+        Buffer.from(event.data.root).equals(Buffer.from(rootHash, "hex")),
+        "event does not match"
+      );
     }
   );
 
@@ -1491,6 +1899,7 @@ describe("solana-world-id-program", () => {
         .updateRootWithQuery(
           Buffer.from(futureResponseBytes),
           [...Buffer.from(rootHash, "hex")],
+          [0],
           mockGuardianSetIndex
         )
         .accountsPartial({
@@ -1589,67 +1998,68 @@ describe("solana-world-id-program", () => {
   );
 
   it(
-    fmtTest("set_root_expiry", "Successfully updates expiry config (again)"),
+    fmtTest(
+      "execute_config_change",
+      "Successfully updates expiry config (again)"
+    ),
     async () => {
       const twentyFourHours = new BN(24 * 60 * 60);
-      await expect(program.methods.setRootExpiry(twentyFourHours).rpc()).to.be
-        .fulfilled;
-      const config = await program.account.config.fetch(
-        deriveConfigKey(program.programId)
+      await expect(
+        changeConfig({
+          rootExpiry: { verificationType: [0], rootExpiry: twentyFourHours },
+        })
+      ).to.be.fulfilled;
+      const verificationTypeConfig =
+        await program.account.verificationTypeConfig.fetch(
+          deriveVerificationTypeConfigKey(program.programId, 0)
+        );
+      assert(
+        verificationTypeConfig.rootExpiry.eq(twentyFourHours),
+        "config does not match"
       );
-      assert(config.rootExpiry.eq(twentyFourHours), "config does not match");
     }
   );
 
-  it(fmtTest("set_root_expiry", "Rejects owner account mismatch"), async () => {
-    const program = programPaidBy(next_owner);
-    await expect(
-      program.methods.setRootExpiry(new BN(1)).rpc()
-    ).to.be.rejectedWith(
-      "AnchorError caused by account: config. Error Code: ConstraintHasOne."
-    );
-  });
-
   it(
-    fmtTest("set_root_expiry", "Rejects without owner as signer"),
+    fmtTest(
+      "propose_config_change",
+      "Rejects without the owner or config manager as signer"
+    ),
     async () => {
       const program = programPaidBy(next_owner);
+      const change = {
+        rootExpiry: { verificationType: [0], rootExpiry: new BN(1) },
+      };
       await expect(
         program.methods
-          .setRootExpiry(new BN(1))
+          .proposeConfigChange(change)
           .accountsPartial({
-            owner: anchor.getProvider().publicKey,
+            pendingConfigChange: derivePendingConfigChangeKey(
+              program.programId,
+              change
+            ),
           })
           .rpc()
-      ).to.be.rejectedWith(
-        `Missing signature for public key [\`${anchor
-          .getProvider()
-          .publicKey.toString()}\`].`
-      );
-    }
-  );
-
-  it(
-    fmtTest("set_allowed_update_staleness", "Rejects owner account mismatch"),
-    async () => {
-      const program = programPaidBy(next_owner);
-      await expect(
-        program.methods.setAllowedUpdateStaleness(new BN(1)).rpc()
-      ).to.be.rejectedWith(
-        "AnchorError caused by account: config. Error Code: ConstraintHasOne."
-      );
+      ).to.be.rejectedWith("InvalidConfigManager.");
     }
   );
 
   it(
-    fmtTest("set_allowed_update_staleness", "Rejects without owner as signer"),
+    fmtTest("propose_config_change", "Rejects without owner as signer"),
     async () => {
       const program = programPaidBy(next_owner);
+      const change = {
+        rootExpiry: { verificationType: [0], rootExpiry: new BN(1) },
+      };
       await expect(
         program.methods
-          .setAllowedUpdateStaleness(new BN(1))
+          .proposeConfigChange(change)
           .accountsPartial({
-            owner: anchor.getProvider().publicKey,
+            authority: anchor.getProvider().publicKey,
+            pendingConfigChange: derivePendingConfigChangeKey(
+              program.programId,
+              change
+            ),
           })
           .rpc()
       ).to.be.rejectedWith(
@@ -1661,443 +2071,2473 @@ describe("solana-world-id-program", () => {
   );
 
   it(
-    fmtTest("transfer_ownership", "Rejects owner account mismatch"),
+    fmtTest(
+      "propose_config_change",
+      "Rejects an owner-only change by the config manager"
+    ),
     async () => {
-      const program = programPaidBy(next_owner);
-      const programData = anchor.web3.PublicKey.findProgramAddressSync(
-        [program.programId.toBuffer()],
-        new anchor.web3.PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
-      )[0];
       await expect(
         program.methods
-          .transferOwnership()
+          .grantRole({ configManager: {} }, next_owner.publicKey)
+          .rpc()
+      ).to.be.fulfilled;
+      const configManagerProgram = programPaidBy(next_owner);
+      const change = {
+        configChangeDelay: { configChangeDelay: new BN(0) },
+      };
+      await expect(
+        configManagerProgram.methods
+          .proposeConfigChange(change)
           .accountsPartial({
-            newOwner: next_owner.publicKey,
-            programData,
+            pendingConfigChange: derivePendingConfigChangeKey(
+              program.programId,
+              change
+            ),
           })
           .rpc()
-      ).to.be.rejectedWith(
-        "AnchorError caused by account: config. Error Code: ConstraintHasOne."
-      );
+      ).to.be.rejectedWith("OwnerOnlyConfigChange.");
+      // put things back the way they were
+      await expect(
+        program.methods.revokeRole({ configManager: {} }).rpc()
+      ).to.be.fulfilled;
     }
   );
 
   it(
-    fmtTest("transfer_ownership", "Rejects without owner as signer"),
+    fmtTest("propose_config_change", "Rejects an out of bounds value"),
     async () => {
-      const program = programPaidBy(next_owner);
-      const programData = anchor.web3.PublicKey.findProgramAddressSync(
-        [program.programId.toBuffer()],
-        new anchor.web3.PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
-      )[0];
+      const change = {
+        allowedUpdateStaleness: {
+          verificationType: [0],
+          allowedUpdateStaleness: new BN(24 * 60 * 60 + 1),
+        },
+      };
       await expect(
         program.methods
-          .transferOwnership()
+          .proposeConfigChange(change)
           .accountsPartial({
-            owner: anchor.getProvider().publicKey,
-            newOwner: next_owner.publicKey,
-            programData,
+            pendingConfigChange: derivePendingConfigChangeKey(
+              program.programId,
+              change
+            ),
           })
           .rpc()
-      ).to.be.rejectedWith(
-        `Missing signature for public key [\`${anchor
-          .getProvider()
-          .publicKey.toString()}\`].`
-      );
+      ).to.be.rejectedWith("ConfigValueOutOfBounds.");
     }
   );
 
   it(
-    fmtTest(
-      "claim_ownership",
-      "Rejects owner or pending owner account mismatch"
-    ),
+    fmtTest("cancel_config_change", "Successfully cancels a change"),
     async () => {
-      const program = programPaidBy(next_owner);
-      const programData = anchor.web3.PublicKey.findProgramAddressSync(
-        [program.programId.toBuffer()],
-        new anchor.web3.PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
-      )[0];
+      const change = {
+        rootExpiry: { verificationType: [0], rootExpiry: new BN(1) },
+      };
+      const pendingConfigChange = derivePendingConfigChangeKey(
+        program.programId,
+        change
+      );
       await expect(
         program.methods
-          .claimOwnership()
-          .accountsPartial({
-            newOwner: next_owner.publicKey,
-            programData,
-          })
+          .proposeConfigChange(change)
+          .accountsPartial({ pendingConfigChange })
           .rpc()
-      ).to.be.rejectedWith(
-        "AnchorError caused by account: config. Error Code: InvalidPendingOwner."
+      ).to.be.fulfilled;
+      const tx = await program.methods
+        .cancelConfigChange()
+        .accountsPartial({
+          pendingConfigChange,
+          refundRecipient: anchor.getProvider().publicKey,
+        })
+        .rpc({ commitment: "confirmed" });
+      assert(
+        (await anchor.getProvider().connection.getAccountInfo(
+          pendingConfigChange
+        )) === null,
+        "pending config change is not closed"
+      );
+      const [event] = await getCpiEvents(program, tx);
+      assert(event.name === "configChangeCancelled", "event does not match");
+      assert(
+        event.data.change.rootExpiry.rootExpiry.eq(new BN(1)),
+        "event does not match"
       );
     }
   );
 
   it(
-    fmtTest(
-      "claim_ownership",
-      "Rejects without owner or pending owner as signer"
-    ),
+    fmtTest("execute_config_change", "Rejects before the change delay"),
     async () => {
-      const program = programPaidBy(next_owner);
-      const programData = anchor.web3.PublicKey.findProgramAddressSync(
-        [program.programId.toBuffer()],
-        new anchor.web3.PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
-      )[0];
+      const twoSeconds = new BN(2);
+      await expect(
+        changeConfig({ configChangeDelay: { configChangeDelay: twoSeconds } })
+      ).to.be.fulfilled;
+      const change = {
+        rootExpiry: { verificationType: [0], rootExpiry: new BN(1) },
+      };
+      const pendingConfigChange = derivePendingConfigChangeKey(
+        program.programId,
+        change
+      );
       await expect(
         program.methods
-          .claimOwnership()
+          .proposeConfigChange(change)
+          .accountsPartial({ pendingConfigChange })
+          .rpc()
+      ).to.be.fulfilled;
+      await expect(
+        program.methods
+          .executeConfigChange()
           .accountsPartial({
-            newOwner: anchor.getProvider().publicKey,
-            programData,
+            pendingConfigChange,
+            verificationTypeConfig: deriveVerificationTypeConfigKey(
+              program.programId,
+              0
+            ),
+            refundRecipient: anchor.getProvider().publicKey,
           })
           .rpc()
-      ).to.be.rejectedWith(
-        `Missing signature for public key [\`${anchor
-          .getProvider()
-          .publicKey.toString()}\`].`
+      ).to.be.rejectedWith("ConfigChangeDelayNotElapsed.");
+      // put things back the way they were
+      await expect(
+        program.methods
+          .cancelConfigChange()
+          .accountsPartial({
+            pendingConfigChange,
+            refundRecipient: anchor.getProvider().publicKey,
+          })
+          .rpc()
+      ).to.be.fulfilled;
+      const zeroDelayChange = {
+        configChangeDelay: { configChangeDelay: new BN(0) },
+      };
+      const pendingZeroDelayChange = derivePendingConfigChangeKey(
+        program.programId,
+        zeroDelayChange
       );
-    }
-  );
-
-  it(
-    fmtTest("transfer_ownership", "Rejects incorrect program_data"),
-    async () => {
-      const programData = anchor.web3.PublicKey.findProgramAddressSync(
-        [devnetCoreBridgeAddress.toBuffer()],
-        new anchor.web3.PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
-      )[0];
       await expect(
         program.methods
-          .transferOwnership()
+          .proposeConfigChange(zeroDelayChange)
+          .accountsPartial({ pendingConfigChange: pendingZeroDelayChange })
+          .rpc()
+      ).to.be.fulfilled;
+      await sleep(3000);
+      await expect(
+        program.methods
+          .executeConfigChange()
           .accountsPartial({
-            newOwner: next_owner.publicKey,
-            programData,
+            pendingConfigChange: pendingZeroDelayChange,
+            verificationTypeConfig: null,
+            refundRecipient: anchor.getProvider().publicKey,
           })
           .rpc()
-      ).to.be.rejectedWith(
-        "AnchorError caused by account: program_data. Error Code: ConstraintSeeds."
+      ).to.be.fulfilled;
+      const config = await program.account.config.fetch(
+        deriveConfigKey(program.programId)
+      );
+      assert(
+        config.configChangeDelay.eq(new BN(0)),
+        "config change delay does not match"
       );
     }
   );
 
-  it(fmtTest("claim_ownership", "Rejects incorrect program_data"), async () => {
-    const programData = anchor.web3.PublicKey.findProgramAddressSync(
-      [devnetCoreBridgeAddress.toBuffer()],
-      new anchor.web3.PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
-    )[0];
-    await expect(
-      program.methods
-        .claimOwnership()
-        .accountsPartial({
-          newOwner: anchor.getProvider().publicKey,
-          programData,
-        })
-        .rpc()
-    ).to.be.rejectedWith(
-      "AnchorError caused by account: program_data. Error Code: ConstraintSeeds."
-    );
-  });
-
   it(
-    fmtTest(
-      "claim_ownership",
-      "Rejects when upgrade_lock is not upgrade_authority_address"
-    ),
+    fmtTest("initialize_admin_set", "Rejects owner account mismatch"),
     async () => {
-      const programData = anchor.web3.PublicKey.findProgramAddressSync(
-        [program.programId.toBuffer()],
-        new anchor.web3.PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
-      )[0];
+      const program = programPaidBy(next_owner);
       await expect(
-        program.methods
-          .claimOwnership()
-          .accountsPartial({
-            newOwner: anchor.getProvider().publicKey,
-            programData,
-          })
-          .rpc()
+        program.methods.initializeAdminSet([next_owner.publicKey], 1).rpc()
       ).to.be.rejectedWith(
-        "Program BPFLoaderUpgradeab1e11111111111111111111111 failed: Incorrect authority provided"
+        "AnchorError caused by account: config. Error Code: ConstraintHasOne."
       );
     }
   );
 
-  // This will fail on a test validator with
-  // Program BPFLoaderUpgradeab1e11111111111111111111111 failed: instruction changed executable accounts data
-  it.skip(
-    fmtTest("transfer_ownership", "Successfully initiates ownership transfer"),
+  it(
+    fmtTest("initialize_admin_set", "Rejects an invalid admin set"),
     async () => {
-      const programData = anchor.web3.PublicKey.findProgramAddressSync(
-        [program.programId.toBuffer()],
-        new anchor.web3.PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
-      )[0];
       await expect(
         program.methods
-          .transferOwnership()
-          .accountsPartial({
-            newOwner: next_owner.publicKey,
-            programData,
-          })
+          .initializeAdminSet(
+            [anchor.getProvider().publicKey, anchor.getProvider().publicKey],
+            1
+          )
           .rpc()
-      ).to.be.fulfilled;
-      const config = await program.account.config.fetch(
-        deriveConfigKey(program.programId)
+      ).to.be.rejectedWith("InvalidAdminSet.");
+    }
+  );
+
+  it(
+    fmtTest("initialize_admin_set", "Successfully initializes an admin set"),
+    async () => {
+      const members = [anchor.getProvider().publicKey, next_owner.publicKey];
+      const tx = await program.methods
+        .initializeAdminSet(members, 2)
+        .rpc({ commitment: "confirmed" });
+      const adminSet = await program.account.adminSet.fetch(
+        deriveAdminSetKey(program.programId)
       );
+      assert(adminSet.threshold === 2, "threshold does not match");
       assert(
-        config.pendingOwner.equals(next_owner.publicKey),
-        "pending owner does not match"
+        adminSet.members.every((member, i) => member.equals(members[i])),
+        "members do not match"
       );
+      const [event] = await getCpiEvents(program, tx);
+      assert(event.name === "adminSetUpdated", "event does not match");
     }
   );
 
-  // This cannot complete because `transfer_ownership` cannot complete
-  it.skip(
-    fmtTest("claim_ownership", "Successfully cancels ownership transfer"),
+  it(
+    fmtTest("execute_as_admin_set", "Rejects below the threshold"),
     async () => {
-      const programData = anchor.web3.PublicKey.findProgramAddressSync(
-        [program.programId.toBuffer()],
+      const ix = await program.methods
+        .setAdminSetThreshold(1)
+        .accountsPartial({ adminSet: deriveAdminSetKey(program.programId) })
+        .instruction();
+      await expect(
+        program.methods
+          .executeAsAdminSet(ix.data)
+          .accounts({ targetProgram: program.programId })
+          .remainingAccounts(
+            adminSetRemainingAccounts(program.programId, ix, [
+              anchor.getProvider().publicKey,
+            ])
+          )
+          .rpc()
+      ).to.be.rejectedWith("AdminSetThresholdNotMet.");
+    }
+  );
+
+  it(
+    fmtTest(
+      "execute_as_admin_set",
+      "Successfully updates the threshold with all members"
+    ),
+    async () => {
+      const ix = await program.methods
+        .setAdminSetThreshold(1)
+        .accountsPartial({ adminSet: deriveAdminSetKey(program.programId) })
+        .instruction();
+      await expect(
+        program.methods
+          .executeAsAdminSet(ix.data)
+          .accounts({ targetProgram: program.programId })
+          .remainingAccounts(
+            adminSetRemainingAccounts(program.programId, ix, [
+              anchor.getProvider().publicKey,
+              next_owner.publicKey,
+            ])
+          )
+          .signers([next_owner])
+          .rpc()
+      ).to.be.fulfilled;
+      const adminSet = await program.account.adminSet.fetch(
+        deriveAdminSetKey(program.programId)
+      );
+      assert(adminSet.threshold === 1, "threshold does not match");
+    }
+  );
+
+  it(
+    fmtTest("execute_config_change", "Successfully updates a root source"),
+    async () => {
+      // Only the contract may change, roots are still read from the same chain.
+      const otherIdentityManager = [
+        ...Buffer.from("b2ead588f14e69266d1b87936b75325181377076", "hex"),
+      ];
+      const tx = await changeConfig({
+        rootSource: {
+          verificationType: [0],
+          source: {
+            query: { ...ethRootSource.query, contract: otherIdentityManager },
+          },
+        },
+      });
+      const [event] = await getCpiEvents(program, tx);
+      assert(event.name === "rootSourceUpdated", "event does not match");
+      const verificationTypeConfig =
+        await program.account.verificationTypeConfig.fetch(
+          deriveVerificationTypeConfigKey(program.programId, 0)
+        );
+      assert(
+        Buffer.from(verificationTypeConfig.source.query.contract).equals(
+          Buffer.from(otherIdentityManager)
+        ),
+        "source contract does not match"
+      );
+      // queries of the previous source are no longer accepted
+      const signatureSet = anchor.web3.Keypair.generate();
+      await postQuerySigs(mockQueryResponse.signatures, signatureSet);
+      await expect(
+        program.methods
+          .updateRootWithQuery(
+            Buffer.from(mockQueryResponse.bytes, "hex"),
+            [...Buffer.from(rootHash, "hex")],
+            [0],
+            mockGuardianSetIndex
+          )
+          .accountsPartial({
+            guardianSet: deriveGuardianSetKey(
+              coreBridgeAddress,
+              mockGuardianSetIndex
+            ),
+            guardianSignatures: signatureSet.publicKey,
+          })
+          .rpc()
+      ).to.be.rejectedWith("InvalidRequestContract.");
+      // put things back the way they were
+      await expect(
+        changeConfig({
+          rootSource: { verificationType: [0], source: ethRootSource },
+        })
+      ).to.be.fulfilled;
+    }
+  );
+
+  it(
+    fmtTest("execute_config_change", "Rejects a root source on another chain"),
+    async () => {
+      const change = {
+        rootSource: {
+          verificationType: [0],
+          source: { query: { ...ethRootSource.query, chainId: 10002 } },
+        },
+      };
+      await expect(changeConfig(change)).to.be.rejectedWith(
+        "RootSourceChainMismatch."
+      );
+      // put things back the way they were
+      await expect(
+        program.methods
+          .cancelConfigChange()
+          .accountsPartial({
+            pendingConfigChange: derivePendingConfigChangeKey(
+              program.programId,
+              change
+            ),
+            refundRecipient: anchor.getProvider().publicKey,
+          })
+          .rpc()
+      ).to.be.fulfilled;
+    }
+  );
+
+  it(
+    fmtTest(
+      "register_verification_type",
+      "Successfully registers a new verification type"
+    ),
+    async () => {
+      // Register the same Identity Manager under a second verification type,
+      // as would be done for a bridged WorldID contract on an L2.
+      await expect(
+        program.methods
+          .registerVerificationType([1], {
+            rootExpiry: new BN(24 * 60 * 60),
+            allowedUpdateStaleness: new BN(5 * 60),
+            source: ethRootSource,
+          })
+          .rpc()
+      ).to.be.fulfilled;
+      const verificationTypeConfig =
+        await program.account.verificationTypeConfig.fetch(
+          deriveVerificationTypeConfigKey(program.programId, 1)
+        );
+      assert(verificationTypeConfig.enabled, "verification type is disabled");
+      assert(
+        verificationTypeConfig.rootExpiry.eq(new BN(24 * 60 * 60)),
+        "root expiry does not match"
+      );
+      const latestRoot = await program.account.latestRoot.fetch(
+        deriveLatestRootKey(program.programId, 1)
+      );
+      assert(
+        Buffer.from(latestRoot.verificationType).equals(
+          Buffer.from("01", "hex")
+        ),
+        "verificationType does not match"
+      );
+      assert(latestRoot.readBlockNumber.eq(new BN(0)), "latest root is set");
+      const signatureSet = anchor.web3.Keypair.generate();
+      await postQuerySigs(mockQueryResponse.signatures, signatureSet);
+      await expect(
+        program.methods
+          .updateRootWithQuery(
+            Buffer.from(mockQueryResponse.bytes, "hex"),
+            [...Buffer.from(rootHash, "hex")],
+            [1],
+            mockGuardianSetIndex
+          )
+          .accountsPartial({
+            guardianSet: deriveGuardianSetKey(
+              coreBridgeAddress,
+              mockGuardianSetIndex
+            ),
+            guardianSignatures: signatureSet.publicKey,
+          })
+          .rpc()
+      ).to.be.fulfilled;
+      const root = await program.account.root.fetch(
+        deriveRootKey(program.programId, Buffer.from(rootHash, "hex"), 1)
+      );
+      assert(
+        Buffer.from(root.verificationType).equals(Buffer.from("01", "hex")),
+        "verificationType does not match"
+      );
+    }
+  );
+
+  it(
+    fmtTest("register_verification_type", "Rejects an out of bounds value"),
+    async () => {
+      await expect(
+        program.methods
+          .registerVerificationType([3], {
+            rootExpiry: new BN(0),
+            allowedUpdateStaleness: new BN(5 * 60),
+            source: ethRootSource,
+          })
+          .rpc()
+      ).to.be.rejectedWith("ConfigValueOutOfBounds.");
+    }
+  );
+
+  function updateRootWithQueryPrecompile(
+    bytes: Uint8Array,
+    guardianSetIndex: number,
+    preInstructions: anchor.web3.TransactionInstruction[]
+  ) {
+    return program.methods
+      .updateRootWithQueryPrecompile(
+        Buffer.from(bytes),
+        [...Buffer.from(dummyRootHash, "hex")],
+        [1],
+        guardianSetIndex
+      )
+      .accountsPartial({
+        guardianSet: deriveGuardianSetKey(coreBridgeAddress, guardianSetIndex),
+      })
+      .preInstructions(preInstructions)
+      .rpc({ commitment: "confirmed" });
+  }
+
+  it(
+    fmtTest(
+      "update_root_with_query_precompile",
+      "Rejects missing secp256k1 instruction"
+    ),
+    async () => {
+      const bytes = futureQueryResponseBytes(1, dummyRootHash);
+      await expect(
+        updateRootWithQueryPrecompile(bytes, mockGuardianSetIndex, [])
+      ).to.be.rejectedWith("InvalidSecp256k1Instruction.");
+    }
+  );
+
+  it(
+    fmtTest(
+      "update_root_with_query_precompile",
+      "Rejects signatures of a different message"
+    ),
+    async () => {
+      const bytes = futureQueryResponseBytes(1, dummyRootHash);
+      const secp256k1Ix = createSecp256k1Instruction(
+        mockQueryResponse.signatures,
+        await getGuardianKeys(mockGuardianSetIndex),
+        Buffer.from(mockQueryResponse.bytes, "hex")
+      );
+      await expect(
+        updateRootWithQueryPrecompile(bytes, mockGuardianSetIndex, [
+          secp256k1Ix,
+        ])
+      ).to.be.rejectedWith("InvalidSecp256k1Message.");
+    }
+  );
+
+  it(
+    fmtTest(
+      "update_root_with_query_precompile",
+      "Rejects unknown guardian key"
+    ),
+    async () => {
+      const bytes = futureQueryResponseBytes(1, dummyRootHash);
+      // Only the second guardian of the two guardian set signs.
+      const secp256k1Ix = createSecp256k1Instruction(
+        new QueryProxyMock({}, [
+          "c3b2e45c422a1602333a64078aeb42637370b0f48fe385f9cfa6ad54a8e0c47e",
+        ]).sign(bytes),
+        (await getGuardianKeys(twoMockGuardianSetIndex)).slice(1),
+        bytes
+      );
+      await expect(
+        updateRootWithQueryPrecompile(bytes, mockGuardianSetIndex, [
+          secp256k1Ix,
+        ])
+      ).to.be.rejectedWith("UnknownGuardianKey.");
+    }
+  );
+
+  it(
+    fmtTest("update_root_with_query_precompile", "Rejects no quorum"),
+    async () => {
+      const bytes = futureQueryResponseBytes(1, dummyRootHash);
+      const secp256k1Ix = createSecp256k1Instruction(
+        new QueryProxyMock({}).sign(bytes),
+        await getGuardianKeys(twoMockGuardianSetIndex),
+        bytes
+      );
+      await expect(
+        updateRootWithQueryPrecompile(bytes, twoMockGuardianSetIndex, [
+          secp256k1Ix,
+        ])
+      ).to.be.rejectedWith("NoQuorum.");
+    }
+  );
+
+  it(
+    fmtTest(
+      "update_root_with_query_precompile",
+      "Rejects out of order guardian signatures"
+    ),
+    async () => {
+      const bytes = futureQueryResponseBytes(1, dummyRootHash);
+      const secp256k1Ix = createSecp256k1Instruction(
+        new QueryProxyMock({}, [
+          "cfb12303a19cde580bb4dd771639b0d26bc68353645571a8cff516ab2ee113a0",
+          "c3b2e45c422a1602333a64078aeb42637370b0f48fe385f9cfa6ad54a8e0c47e",
+        ])
+          .sign(bytes)
+          .reverse(),
+        await getGuardianKeys(twoMockGuardianSetIndex),
+        bytes
+      );
+      await expect(
+        updateRootWithQueryPrecompile(bytes, twoMockGuardianSetIndex, [
+          secp256k1Ix,
+        ])
+      ).to.be.rejectedWith("InvalidGuardianIndexNonIncreasing.");
+    }
+  );
+
+  it(
+    fmtTest(
+      "update_root_with_query_precompile",
+      "Successfully verifies signatures and updates root"
+    ),
+    async () => {
+      const bytes = futureQueryResponseBytes(1, dummyRootHash);
+      const secp256k1Ix = createSecp256k1Instruction(
+        new QueryProxyMock({}).sign(bytes),
+        await getGuardianKeys(mockGuardianSetIndex),
+        bytes
+      );
+      const tx = await updateRootWithQueryPrecompile(
+        bytes,
+        mockGuardianSetIndex,
+        [secp256k1Ix]
+      );
+      const latestRoot = await program.account.latestRoot.fetch(
+        deriveLatestRootKey(program.programId, 1)
+      );
+      assert(
+        Buffer.from(latestRoot.root).equals(Buffer.from(dummyRootHash, "hex")),
+        "latest root does not match"
+      );
+      const root = await program.account.root.fetch(
+        deriveRootKey(program.programId, Buffer.from(dummyRootHash, "hex"), 1)
+      );
+      assert(
+        root.refundRecipient.equals(anchor.getProvider().publicKey),
+        "refundRecipient does not match"
+      );
+      const [event] = await getCpiEvents(program, tx);
+      assert(event.name === "rootUpdated", "event does not match");
+      assert(
+        event.data.guardianSetIndex === mockGuardianSetIndex,
+        "event does not match"
+      );
+    }
+  );
+
+  it(
+    fmtTest("register_verification_type", "Rejects duplicate registration"),
+    async () => {
+      await expect(
+        program.methods
+          .registerVerificationType([0], {
+            rootExpiry: new BN(1),
+            allowedUpdateStaleness: new BN(1),
+            source: ethRootSource,
+          })
+          .rpc()
+      ).to.be.rejectedWith("already in use");
+    }
+  );
+
+  it(
+    fmtTest("register_verification_type", "Rejects owner account mismatch"),
+    async () => {
+      const program = programPaidBy(next_owner);
+      await expect(
+        program.methods
+          .registerVerificationType([2], {
+            rootExpiry: new BN(1),
+            allowedUpdateStaleness: new BN(1),
+            source: ethRootSource,
+          })
+          .rpc()
+      ).to.be.rejectedWith(
+        "AnchorError caused by account: config. Error Code: ConstraintHasOne."
+      );
+    }
+  );
+
+  it(
+    fmtTest(
+      "set_verification_type_enabled",
+      "Successfully disables a verification type"
+    ),
+    async () => {
+      await expect(
+        program.methods.setVerificationTypeEnabled([1], false).rpc()
+      ).to.be.fulfilled;
+      const verificationTypeConfig =
+        await program.account.verificationTypeConfig.fetch(
+          deriveVerificationTypeConfigKey(program.programId, 1)
+        );
+      assert(!verificationTypeConfig.enabled, "verification type is enabled");
+    }
+  );
+
+  it(
+    fmtTest("update_root_with_query", "Rejects disabled verification type"),
+    async () => {
+      const signatureSet = anchor.web3.Keypair.generate();
+      await postQuerySigs(mockQueryResponse.signatures, signatureSet);
+      await expect(
+        program.methods
+          .updateRootWithQuery(
+            Buffer.from(mockQueryResponse.bytes, "hex"),
+            [...Buffer.from(rootHash, "hex")],
+            [1],
+            mockGuardianSetIndex
+          )
+          .accountsPartial({
+            guardianSet: deriveGuardianSetKey(
+              coreBridgeAddress,
+              mockGuardianSetIndex
+            ),
+            guardianSignatures: signatureSet.publicKey,
+          })
+          .rpc()
+      ).to.be.rejectedWith("VerificationTypeDisabled.");
+    }
+  );
+
+  it(
+    fmtTest("verify_groth16_proof", "Rejects disabled verification type"),
+    async () => {
+      await expect(
+        program.methods
+          .verifyGroth16Proof(
+            [...Buffer.from(rootHash, "hex")],
+            [1],
+            new Array(32).fill(0),
+            new Array(32).fill(0),
+            new Array(32).fill(0),
+            new Array(256).fill(0)
+          )
+          .rpc()
+      ).to.be.rejectedWith("VerificationTypeDisabled.");
+    }
+  );
+
+  it(
+    fmtTest(
+      "set_verification_type_enabled",
+      "Successfully enables a verification type"
+    ),
+    async () => {
+      await expect(program.methods.setVerificationTypeEnabled([1], true).rpc())
+        .to.be.fulfilled;
+      const verificationTypeConfig =
+        await program.account.verificationTypeConfig.fetch(
+          deriveVerificationTypeConfigKey(program.programId, 1)
+        );
+      assert(verificationTypeConfig.enabled, "verification type is disabled");
+    }
+  );
+
+  it(
+    fmtTest(
+      "set_verification_type_enabled",
+      "Rejects without the owner or config manager as signer"
+    ),
+    async () => {
+      const program = programPaidBy(next_owner);
+      await expect(
+        program.methods.setVerificationTypeEnabled([1], false).rpc()
+      ).to.be.rejectedWith("InvalidConfigManager.");
+    }
+  );
+
+  it(
+    fmtTest(
+      "register_verification_type",
+      "Successfully registers a VAA verification type"
+    ),
+    async () => {
+      await expect(
+        program.methods
+          .registerVerificationType([2], {
+            rootExpiry: new BN(24 * 60 * 60),
+            allowedUpdateStaleness: new BN(24 * 60 * 60),
+            source: {
+              vaa: {
+                emitterChain: MOCK_VAA_EMITTER_CHAIN,
+                emitterAddress: [
+                  ...Buffer.from(MOCK_VAA_EMITTER_ADDRESS, "hex"),
+                ],
+              },
+            },
+          })
+          .rpc()
+      ).to.be.fulfilled;
+      const verificationTypeConfig =
+        await program.account.verificationTypeConfig.fetch(
+          deriveVerificationTypeConfigKey(program.programId, 2)
+        );
+      assert(
+        verificationTypeConfig.source.vaa.emitterChain ===
+          MOCK_VAA_EMITTER_CHAIN,
+        "emitter chain does not match"
+      );
+    }
+  );
+
+  it(
+    fmtTest("update_root_with_vaa", "Rejects invalid emitter chain"),
+    async () => {
+      await expect(
+        program.methods
+          .updateRootWithVaa(
+            [...Buffer.from(mockPostedVaas.invalidEmitterChain.root, "hex")],
+            [2]
+          )
+          .accountsPartial({
+            postedVaa: mockPostedVaas.invalidEmitterChain.address,
+          })
+          .rpc()
+      ).to.be.rejectedWith("InvalidVaaEmitterChain.");
+    }
+  );
+
+  it(
+    fmtTest("update_root_with_vaa", "Rejects invalid emitter address"),
+    async () => {
+      await expect(
+        program.methods
+          .updateRootWithVaa(
+            [...Buffer.from(mockPostedVaas.invalidEmitterAddress.root, "hex")],
+            [2]
+          )
+          .accountsPartial({
+            postedVaa: mockPostedVaas.invalidEmitterAddress.address,
+          })
+          .rpc()
+      ).to.be.rejectedWith("InvalidVaaEmitterAddress.");
+    }
+  );
+
+  it(fmtTest("update_root_with_vaa", "Rejects invalid payload"), async () => {
+      await expect(
+        program.methods
+          .updateRootWithVaa(
+            [...Buffer.from(mockPostedVaas.invalidPayload.root, "hex")],
+            [2]
+          )
+          .accountsPartial({
+            postedVaa: mockPostedVaas.invalidPayload.address,
+          })
+          .rpc()
+      ).to.be.rejectedWith("InvalidVaaPayload.");
+  });
+
+  it(
+    fmtTest(
+      "update_root_with_vaa",
+      "Rejects root hash instruction argument mismatch"
+    ),
+    async () => {
+      await expect(
+        program.methods
+          .updateRootWithVaa(
+            [...Buffer.from(mockPostedVaas.root1.root, "hex")],
+            [2]
+          )
+          .accountsPartial({
+            postedVaa: mockPostedVaas.root0.address,
+          })
+          .rpc()
+      ).to.be.rejectedWith("RootHashMismatch.");
+    }
+  );
+
+  it(
+    fmtTest("update_root_with_vaa", "Rejects query verification type"),
+    async () => {
+      await expect(
+        program.methods
+          .updateRootWithVaa(
+            [...Buffer.from(mockPostedVaas.root0.root, "hex")],
+            [0]
+          )
+          .accountsPartial({
+            postedVaa: mockPostedVaas.root0.address,
+          })
+          .rpc()
+      ).to.be.rejectedWith("InvalidRootSource.");
+    }
+  );
+
+  it(
+    fmtTest(
+      "update_root_with_vaa",
+      "Rejects posted VAA account not owned by the core bridge"
+    ),
+    async () => {
+      await expect(
+        program.methods
+          .updateRootWithVaa(
+            [...Buffer.from(mockPostedVaas.root0.root, "hex")],
+            [2]
+          )
+          .accountsPartial({
+            postedVaa: deriveLatestRootKey(program.programId, 2),
+          })
+          .rpc()
+      ).to.be.rejectedWith(
+        "AnchorError caused by account: posted_vaa. Error Code: AccountOwnedByWrongProgram."
+      );
+    }
+  );
+
+  it(
+    fmtTest("update_root_with_vaa", "Successfully updates root"),
+    async () => {
+      await expect(
+        program.methods
+          .updateRootWithVaa(
+            [...Buffer.from(mockPostedVaas.root0.root, "hex")],
+            [2]
+          )
+          .accountsPartial({
+            postedVaa: mockPostedVaas.root0.address,
+          })
+          .rpc()
+      ).to.be.fulfilled;
+      const latestRoot = await program.account.latestRoot.fetch(
+        deriveLatestRootKey(program.programId, 2)
+      );
+      assert(
+        Buffer.from(latestRoot.root).equals(
+          Buffer.from(mockPostedVaas.root0.root, "hex")
+        ),
+        "latest root does not match"
+      );
+      assert(
+        latestRoot.readBlockNumber.eq(new BN(0)),
+        "sequence does not match"
+      );
+      const root = await program.account.root.fetch(
+        deriveRootKey(
+          program.programId,
+          Buffer.from(mockPostedVaas.root0.root, "hex"),
+          2
+        )
+      );
+      assert(
+        Buffer.from(root.verificationType).equals(Buffer.from("02", "hex")),
+        "verificationType does not match"
+      );
+    }
+  );
+
+  it(
+    fmtTest("update_root_with_vaa", "Successfully updates subsequent root"),
+    async () => {
+      await expect(
+        program.methods
+          .updateRootWithVaa(
+            [...Buffer.from(mockPostedVaas.root1.root, "hex")],
+            [2]
+          )
+          .accountsPartial({
+            postedVaa: mockPostedVaas.root1.address,
+          })
+          .rpc()
+      ).to.be.fulfilled;
+      const latestRoot = await program.account.latestRoot.fetch(
+        deriveLatestRootKey(program.programId, 2)
+      );
+      assert(
+        Buffer.from(latestRoot.root).equals(
+          Buffer.from(mockPostedVaas.root1.root, "hex")
+        ),
+        "latest root does not match"
+      );
+      assert(
+        latestRoot.readBlockNumber.eq(new BN(1)),
+        "sequence does not match"
+      );
+    }
+  );
+
+  it(
+    fmtTest("update_root_with_vaa", "Rejects stale sequence"),
+    async () => {
+      await expect(
+        program.methods
+          .updateRootWithVaa(
+            [...Buffer.from(mockPostedVaas.staleSequence.root, "hex")],
+            [2]
+          )
+          .accountsPartial({
+            postedVaa: mockPostedVaas.staleSequence.address,
+          })
+          .rpc()
+      ).to.be.rejectedWith("StaleVaaSequence.");
+    }
+  );
+
+  it(
+    fmtTest("update_root_with_query", "Rejects VAA verification type"),
+    async () => {
+      const signatureSet = anchor.web3.Keypair.generate();
+      await postQuerySigs(mockQueryResponse.signatures, signatureSet);
+      await expect(
+        program.methods
+          .updateRootWithQuery(
+            Buffer.from(mockQueryResponse.bytes, "hex"),
+            [...Buffer.from(rootHash, "hex")],
+            [2],
+            mockGuardianSetIndex
+          )
+          .accountsPartial({
+            guardianSet: deriveGuardianSetKey(
+              coreBridgeAddress,
+              mockGuardianSetIndex
+            ),
+            guardianSignatures: signatureSet.publicKey,
+          })
+          .rpc()
+      ).to.be.rejectedWith("InvalidRootSource.");
+    }
+  );
+
+  it(
+    fmtTest("transfer_ownership", "Rejects owner account mismatch"),
+    async () => {
+      const program = programPaidBy(next_owner);
+      const programData = anchor.web3.PublicKey.findProgramAddressSync(
+        [program.programId.toBuffer()],
+        new anchor.web3.PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
+      )[0];
+      await expect(
+        program.methods
+          .transferOwnership()
+          .accountsPartial({
+            newOwner: next_owner.publicKey,
+            programData,
+          })
+          .rpc()
+      ).to.be.rejectedWith(
+        "AnchorError caused by account: config. Error Code: ConstraintHasOne."
+      );
+    }
+  );
+
+  it(
+    fmtTest("transfer_ownership", "Rejects without owner as signer"),
+    async () => {
+      const program = programPaidBy(next_owner);
+      const programData = anchor.web3.PublicKey.findProgramAddressSync(
+        [program.programId.toBuffer()],
+        new anchor.web3.PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
+      )[0];
+      await expect(
+        program.methods
+          .transferOwnership()
+          .accountsPartial({
+            owner: anchor.getProvider().publicKey,
+            newOwner: next_owner.publicKey,
+            programData,
+          })
+          .rpc()
+      ).to.be.rejectedWith(
+        `Missing signature for public key [\`${anchor
+          .getProvider()
+          .publicKey.toString()}\`].`
+      );
+    }
+  );
+
+  it(
+    fmtTest(
+      "claim_ownership",
+      "Rejects owner or pending owner account mismatch"
+    ),
+    async () => {
+      const program = programPaidBy(next_owner);
+      const programData = anchor.web3.PublicKey.findProgramAddressSync(
+        [program.programId.toBuffer()],
+        new anchor.web3.PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
+      )[0];
+      await expect(
+        program.methods
+          .claimOwnership()
+          .accountsPartial({
+            newOwner: next_owner.publicKey,
+            programData,
+          })
+          .rpc()
+      ).to.be.rejectedWith(
+        "AnchorError caused by account: config. Error Code: InvalidPendingOwner."
+      );
+    }
+  );
+
+  it(
+    fmtTest(
+      "claim_ownership",
+      "Rejects without owner or pending owner as signer"
+    ),
+    async () => {
+      const program = programPaidBy(next_owner);
+      const programData = anchor.web3.PublicKey.findProgramAddressSync(
+        [program.programId.toBuffer()],
+        new anchor.web3.PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
+      )[0];
+      await expect(
+        program.methods
+          .claimOwnership()
+          .accountsPartial({
+            newOwner: anchor.getProvider().publicKey,
+            programData,
+          })
+          .rpc()
+      ).to.be.rejectedWith(
+        `Missing signature for public key [\`${anchor
+          .getProvider()
+          .publicKey.toString()}\`].`
+      );
+    }
+  );
+
+  it(
+    fmtTest("transfer_ownership", "Rejects incorrect program_data"),
+    async () => {
+      const programData = anchor.web3.PublicKey.findProgramAddressSync(
+        [devnetCoreBridgeAddress.toBuffer()],
+        new anchor.web3.PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
+      )[0];
+      await expect(
+        program.methods
+          .transferOwnership()
+          .accountsPartial({
+            newOwner: next_owner.publicKey,
+            programData,
+          })
+          .rpc()
+      ).to.be.rejectedWith(
+        "AnchorError caused by account: program_data. Error Code: ConstraintSeeds."
+      );
+    }
+  );
+
+  it(fmtTest("claim_ownership", "Rejects incorrect program_data"), async () => {
+    const programData = anchor.web3.PublicKey.findProgramAddressSync(
+      [devnetCoreBridgeAddress.toBuffer()],
+      new anchor.web3.PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
+    )[0];
+    await expect(
+      program.methods
+        .claimOwnership()
+        .accountsPartial({
+          newOwner: anchor.getProvider().publicKey,
+          programData,
+        })
+        .rpc()
+    ).to.be.rejectedWith(
+      "AnchorError caused by account: program_data. Error Code: ConstraintSeeds."
+    );
+  });
+
+  it(
+    fmtTest(
+      "claim_ownership",
+      "Rejects when upgrade_lock is not upgrade_authority_address"
+    ),
+    async () => {
+      const programData = anchor.web3.PublicKey.findProgramAddressSync(
+        [program.programId.toBuffer()],
+        new anchor.web3.PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
+      )[0];
+      await expect(
+        program.methods
+          .claimOwnership()
+          .accountsPartial({
+            newOwner: anchor.getProvider().publicKey,
+            programData,
+          })
+          .rpc()
+      ).to.be.rejectedWith(
+        "Program BPFLoaderUpgradeab1e11111111111111111111111 failed: Incorrect authority provided"
+      );
+    }
+  );
+
+  // This will fail on a test validator with
+  // Program BPFLoaderUpgradeab1e11111111111111111111111 failed: instruction changed executable accounts data
+  it.skip(
+    fmtTest("transfer_ownership", "Successfully initiates ownership transfer"),
+    async () => {
+      const programData = anchor.web3.PublicKey.findProgramAddressSync(
+        [program.programId.toBuffer()],
+        new anchor.web3.PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
+      )[0];
+      await expect(
+        program.methods
+          .transferOwnership()
+          .accountsPartial({
+            newOwner: next_owner.publicKey,
+            programData,
+          })
+          .rpc()
+      ).to.be.fulfilled;
+      const config = await program.account.config.fetch(
+        deriveConfigKey(program.programId)
+      );
+      assert(
+        config.pendingOwner.equals(next_owner.publicKey),
+        "pending owner does not match"
+      );
+    }
+  );
+
+  // This cannot complete because `transfer_ownership` cannot complete
+  it.skip(
+    fmtTest("claim_ownership", "Successfully cancels ownership transfer"),
+    async () => {
+      const programData = anchor.web3.PublicKey.findProgramAddressSync(
+        [program.programId.toBuffer()],
+        new anchor.web3.PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
+      )[0];
+      await expect(
+        program.methods
+          .claimOwnership()
+          .accountsPartial({
+            newOwner: anchor.getProvider().publicKey,
+            programData,
+          })
+          .rpc()
+      ).to.be.fulfilled;
+      const config = await program.account.config.fetch(
+        deriveConfigKey(program.programId)
+      );
+      assert(config.pendingOwner === null, "pending owner does not match");
+      assert(
+        config.owner.equals(anchor.getProvider().publicKey),
+        "owner does not match"
+      );
+    }
+  );
+
+  // This cannot complete because `transfer_ownership` cannot complete
+  it.skip(
+    fmtTest("claim_ownership", "Successfully completes ownership transfer"),
+    async () => {
+      const programData = anchor.web3.PublicKey.findProgramAddressSync(
+        [program.programId.toBuffer()],
         new anchor.web3.PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
       )[0];
       await expect(
         program.methods
-          .claimOwnership()
+          .transferOwnership()
+          .accountsPartial({
+            newOwner: next_owner.publicKey,
+            programData,
+          })
+          .rpc()
+      ).to.be.fulfilled;
+      const programNextOwner = programPaidBy(next_owner);
+      await expect(
+        programNextOwner.methods
+          .claimOwnership()
+          .accountsPartial({
+            newOwner: next_owner.publicKey,
+            programData,
+          })
+          .rpc()
+      ).to.be.fulfilled;
+      const config = await program.account.config.fetch(
+        deriveConfigKey(program.programId)
+      );
+      assert(config.pendingOwner === null, "pending owner does not match");
+      assert(config.owner.equals(next_owner.publicKey), "owner does not match");
+    }
+  );
+
+  it(
+    fmtTest(
+      "verify_groth16_proof",
+      "Successfully verifies a valid groth16 proof"
+    ),
+    async () => {
+      // This is the default anvil wallet
+      const signal = "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266";
+      const signalHash = hashToField(signal);
+      // This is an example appId and action created via https://developer.worldcoin.org
+      const appId = "app_staging_7d23b838b02776cebd87b86ac3248641";
+      const action = "testing";
+      const externalNullifierHash = appIdActionToExternalNullifierHash(
+        appId,
+        action
+      );
+      const rootHash = [
+        ...Buffer.from(idkitSuccessResult.merkle_root.substring(2), "hex"),
+      ];
+      const nullifierHash = [
+        ...Buffer.from(idkitSuccessResult.nullifier_hash.substring(2), "hex"),
+      ];
+      const proof = [
+        ...Buffer.from(idkitSuccessResult.proof.substring(2), "hex"),
+      ];
+      const tx = await program.methods
+        .verifyGroth16Proof(
+          rootHash,
+          [0],
+          signalHash,
+          nullifierHash,
+          externalNullifierHash,
+          proof
+        )
+        .rpc({ commitment: "confirmed" });
+      const [event] = await getCpiEvents(program, tx);
+      assert(event.name === "proofVerified", "event does not match");
+      assert(
+        Buffer.from(event.data.nullifierHash).equals(
+          Buffer.from(nullifierHash)
+        ),
+        "event does not match"
+      );
+      assert(event.data.callerProgram === null, "event does not match");
+    }
+  );
+
+  it(
+    fmtTest(
+      "verify_groth16_proof",
+      "Successfully returns the verification result"
+    ),
+    async () => {
+      const args = validGroth16ProofArgs();
+      const result = await program.methods
+        .verifyGroth16Proof(
+          args.rootHash,
+          [0],
+          args.signalHash,
+          args.nullifierHash,
+          args.externalNullifierHash,
+          args.proof
+        )
+        .view();
+      const root = await program.account.root.fetch(
+        deriveRootKey(program.programId, Buffer.from(args.rootHash), 0)
+      );
+      assert(
+        Buffer.from(result.root).equals(Buffer.from(args.rootHash)),
+        "root does not match"
+      );
+      assert(
+        result.readBlockTime.eq(root.readBlockTime),
+        "readBlockTime does not match"
+      );
+      assert(result.isLatestRoot, "isLatestRoot does not match");
+      assert(
+        result.secondsUntilExpiry.gtn(0),
+        "secondsUntilExpiry does not match"
+      );
+    }
+  );
+
+  function validGroth16ProofArgs() {
+    // This is the default anvil wallet
+    const signal = "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266";
+    // This is an example appId and action created via https://developer.worldcoin.org
+    const appId = "app_staging_7d23b838b02776cebd87b86ac3248641";
+    const action = "testing";
+    return {
+      rootHash: [
+        ...Buffer.from(idkitSuccessResult.merkle_root.substring(2), "hex"),
+      ],
+      signalHash: hashToField(signal),
+      nullifierHash: [
+        ...Buffer.from(idkitSuccessResult.nullifier_hash.substring(2), "hex"),
+      ],
+      externalNullifierHash: appIdActionToExternalNullifierHash(appId, action),
+      proof: [...Buffer.from(idkitSuccessResult.proof.substring(2), "hex")],
+    };
+  }
+
+  function invalidGroth16ProofArgs() {
+    const args = validGroth16ProofArgs();
+    return { ...args, signalHash: hashToField("0x00") };
+  }
+
+  function idkitRootAccount() {
+    return {
+      pubkey: deriveRootKey(
+        program.programId,
+        Buffer.from(idkitSuccessResult.merkle_root.substring(2), "hex"),
+        0
+      ),
+      isSigner: false,
+      isWritable: false,
+    };
+  }
+
+  it(
+    fmtTest("verify_groth16_proofs", "Successfully verifies a batch of proofs"),
+    async () => {
+      const results = await program.methods
+        .verifyGroth16Proofs(
+          [0],
+          [validGroth16ProofArgs(), validGroth16ProofArgs()],
+          { atomic: {} }
+        )
+        .remainingAccounts([idkitRootAccount()])
+        .view();
+      assert.deepEqual(results, [true, true], "results do not match");
+    }
+  );
+
+  it(
+    fmtTest("verify_groth16_proofs", "Emits one event for the batch"),
+    async () => {
+      const tx = await program.methods
+        .verifyGroth16Proofs(
+          [0],
+          [validGroth16ProofArgs(), invalidGroth16ProofArgs()],
+          { independent: {} }
+        )
+        .remainingAccounts([idkitRootAccount()])
+        .rpc({ commitment: "confirmed" });
+      const events = await getCpiEvents(program, tx);
+      assert(events.length === 1, "event count does not match");
+      assert(events[0].name === "proofsVerified", "event does not match");
+      assert(events[0].data.proofs.length === 1, "event does not match");
+    }
+  );
+
+  it(
+    fmtTest(
+      "verify_groth16_proofs",
+      "Successfully reports an invalid proof independently"
+    ),
+    async () => {
+      const results = await program.methods
+        .verifyGroth16Proofs(
+          [0],
+          [validGroth16ProofArgs(), invalidGroth16ProofArgs()],
+          { independent: {} }
+        )
+        .remainingAccounts([idkitRootAccount()])
+        .view();
+      assert.deepEqual(results, [true, false], "results do not match");
+    }
+  );
+
+  it(
+    fmtTest("verify_groth16_proofs", "Rejects an invalid proof atomically"),
+    async () => {
+      await expect(
+        program.methods
+          .verifyGroth16Proofs(
+            [0],
+            [validGroth16ProofArgs(), invalidGroth16ProofArgs()],
+            { atomic: {} }
+          )
+          .remainingAccounts([idkitRootAccount()])
+          .rpc()
+      ).to.be.rejectedWith("Groth16ProofVerificationFailed.");
+    }
+  );
+
+  it(
+    fmtTest("verify_groth16_proofs", "Rejects an empty batch"),
+    async () => {
+      await expect(
+        program.methods
+          .verifyGroth16Proofs([0], [], { atomic: {} })
+          .remainingAccounts([idkitRootAccount()])
+          .rpc()
+      ).to.be.rejectedWith("EmptyProofBatch.");
+    }
+  );
+
+  it(
+    fmtTest("verify_groth16_proofs", "Rejects a missing root account"),
+    async () => {
+      await expect(
+        program.methods
+          .verifyGroth16Proofs([0], [validGroth16ProofArgs()], {
+            independent: {},
+          })
+          .rpc()
+      ).to.be.rejectedWith("RootAccountNotProvided.");
+    }
+  );
+
+  it(
+    fmtTest("verify_groth16_proofs", "Rejects a duplicate root account"),
+    async () => {
+      await expect(
+        program.methods
+          .verifyGroth16Proofs([0], [validGroth16ProofArgs()], { atomic: {} })
+          .remainingAccounts([idkitRootAccount(), idkitRootAccount()])
+          .rpc()
+      ).to.be.rejectedWith("DuplicateRootAccount.");
+    }
+  );
+
+  it(
+    fmtTest(
+      "verify_groth16_proofs",
+      "Rejects a root account of another verification type"
+    ),
+    async () => {
+      await expect(
+        program.methods
+          .verifyGroth16Proofs([1], [validGroth16ProofArgs()], { atomic: {} })
+          .remainingAccounts([idkitRootAccount()])
+          .rpc()
+      ).to.be.rejectedWith("RootVerificationTypeMismatch.");
+    }
+  );
+
+  it(
+    fmtTest(
+      "verify_groth16_proofs",
+      "Rejects a root account not owned by the program"
+    ),
+    async () => {
+      await expect(
+        program.methods
+          .verifyGroth16Proofs([0], [validGroth16ProofArgs()], { atomic: {} })
+          .remainingAccounts([
+            {
+              pubkey: deriveGuardianSetKey(
+                coreBridgeAddress,
+                mockGuardianSetIndex
+              ),
+              isSigner: false,
+              isWritable: false,
+            },
+          ])
+          .rpc()
+      ).to.be.rejectedWith("AccountOwnedByWrongProgram.");
+    }
+  );
+
+  it(
+    fmtTest(
+      "verify_groth16_proof",
+      "Rejects root hash without a corresponding PDA"
+    ),
+    async () => {
+      // This is the default anvil wallet
+      const signal = "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266";
+      const signalHash = hashToField(signal);
+      // This is an example appId and action created via https://developer.worldcoin.org
+      const appId = "app_staging_7d23b838b02776cebd87b86ac3248641";
+      const action = "testing";
+      const externalNullifierHash = appIdActionToExternalNullifierHash(
+        appId,
+        action
+      );
+      const rootHash = [
+        ...Buffer.from(
+          "00" + idkitSuccessResult.merkle_root.substring(4),
+          "hex"
+        ),
+      ];
+      const nullifierHash = [
+        ...Buffer.from(idkitSuccessResult.nullifier_hash.substring(2), "hex"),
+      ];
+      const proof = [
+        ...Buffer.from(idkitSuccessResult.proof.substring(2), "hex"),
+      ];
+      await expect(
+        program.methods
+          .verifyGroth16Proof(
+            rootHash,
+            [0],
+            signalHash,
+            nullifierHash,
+            externalNullifierHash,
+            proof
+          )
+          .rpc()
+      ).to.be.rejectedWith("AccountNotInitialized.");
+    }
+  );
+
+  it(
+    fmtTest(
+      "verify_groth16_proof",
+      "Rejects root hash instruction argument mismatch"
+    ),
+    async () => {
+      // This is the default anvil wallet
+      const signal = "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266";
+      const signalHash = hashToField(signal);
+      // This is an example appId and action created via https://developer.worldcoin.org
+      const appId = "app_staging_7d23b838b02776cebd87b86ac3248641";
+      const action = "testing";
+      const externalNullifierHash = appIdActionToExternalNullifierHash(
+        appId,
+        action
+      );
+      const badRootHash = [
+        ...Buffer.from(
+          "00" + idkitSuccessResult.merkle_root.substring(4),
+          "hex"
+        ),
+      ];
+      const nullifierHash = [
+        ...Buffer.from(idkitSuccessResult.nullifier_hash.substring(2), "hex"),
+      ];
+      const proof = [
+        ...Buffer.from(idkitSuccessResult.proof.substring(2), "hex"),
+      ];
+      await expect(
+        program.methods
+          .verifyGroth16Proof(
+            badRootHash,
+            [0],
+            signalHash,
+            nullifierHash,
+            externalNullifierHash,
+            proof
+          )
+          .accountsPartial({
+            root: deriveRootKey(
+              program.programId,
+              Buffer.from(idkitSuccessResult.merkle_root.substring(2), "hex"),
+              0
+            ),
+          })
+          .rpc()
+      ).to.be.rejectedWith(
+        "AnchorError caused by account: root. Error Code: ConstraintSeeds."
+      );
+    }
+  );
+
+  it(
+    fmtTest(
+      "verify_groth16_proof",
+      "Rejects verification type instruction argument mismatch"
+    ),
+    async () => {
+      // This is the default anvil wallet
+      const signal = "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266";
+      const signalHash = hashToField(signal);
+      // This is an example appId and action created via https://developer.worldcoin.org
+      const appId = "app_staging_7d23b838b02776cebd87b86ac3248641";
+      const action = "testing";
+      const externalNullifierHash = appIdActionToExternalNullifierHash(
+        appId,
+        action
+      );
+      const rootHash = [
+        ...Buffer.from(idkitSuccessResult.merkle_root.substring(2), "hex"),
+      ];
+      const nullifierHash = [
+        ...Buffer.from(idkitSuccessResult.nullifier_hash.substring(2), "hex"),
+      ];
+      const proof = [
+        ...Buffer.from(idkitSuccessResult.proof.substring(2), "hex"),
+      ];
+      await expect(
+        program.methods
+          .verifyGroth16Proof(
+            rootHash,
+            [1],
+            signalHash,
+            nullifierHash,
+            externalNullifierHash,
+            proof
+          )
+          .accountsPartial({
+            root: deriveRootKey(program.programId, Buffer.from(rootHash), 0),
+            latestRoot: deriveLatestRootKey(program.programId, 0),
+          })
+          .rpc()
+      ).to.be.rejectedWith(
+        "AnchorError caused by account: root. Error Code: ConstraintSeeds."
+      );
+    }
+  );
+
+  it(
+    fmtTest(
+      "verify_groth16_proof",
+      "Successfully verifies against an expired, but latest root"
+    ),
+    async () => {
+      // This is the default anvil wallet
+      const signal = "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266";
+      const signalHash = hashToField(signal);
+      // This is an example appId and action created via https://developer.worldcoin.org
+      const appId = "app_staging_7d23b838b02776cebd87b86ac3248641";
+      const action = "testing";
+      const externalNullifierHash = appIdActionToExternalNullifierHash(
+        appId,
+        action
+      );
+      const rootHash = [
+        ...Buffer.from(idkitSuccessResult.merkle_root.substring(2), "hex"),
+      ];
+      const nullifierHash = [
+        ...Buffer.from(idkitSuccessResult.nullifier_hash.substring(2), "hex"),
+      ];
+      const proof = [
+        ...Buffer.from(idkitSuccessResult.proof.substring(2), "hex"),
+      ];
+      // update the expiry config
+      const oneSecond = new BN(1);
+      await expect(
+        changeConfig({
+          rootExpiry: { verificationType: [0], rootExpiry: oneSecond },
+        })
+      ).to.be.fulfilled;
+      await sleep(1000);
+      await expect(
+        program.methods
+          .verifyGroth16Proof(
+            rootHash,
+            [0],
+            signalHash,
+            nullifierHash,
+            externalNullifierHash,
+            proof
+          )
+          .rpc()
+      ).to.be.fulfilled;
+      // put things back the way they were
+      const twentyFourHours = new BN(24 * 60 * 60);
+      await expect(
+        changeConfig({
+          rootExpiry: { verificationType: [0], rootExpiry: twentyFourHours },
+        })
+      ).to.be.fulfilled;
+    }
+  );
+
+  it(
+    fmtTest("verify_groth16_proof", "Rejects an expired, non-latest root"),
+    async () => {
+      // Update a new root
+      const signatureSet = anchor.web3.Keypair.generate();
+      const futureResponse = QueryResponse.from(mockQueryResponse.bytes);
+      const mockEthCallQueryResponse = futureResponse.responses[0]
+        .response as EthCallQueryResponse;
+      mockEthCallQueryResponse.blockNumber += BigInt(3);
+      const futureResponseBytes = futureResponse.serialize();
+      const futureResponseSigs = new QueryProxyMock({}).sign(
+        futureResponseBytes
+      );
+      await postQuerySigs(futureResponseSigs, signatureSet);
+      await expect(
+        program.methods
+          .updateRootWithQuery(
+            Buffer.from(futureResponseBytes),
+            [...Buffer.from(rootHash, "hex")],
+            [0],
+            mockGuardianSetIndex
+          )
+          .accountsPartial({
+            guardianSet: deriveGuardianSetKey(
+              coreBridgeAddress,
+              mockGuardianSetIndex
+            ),
+            guardianSignatures: signatureSet.publicKey,
+          })
+          .rpc()
+      ).to.be.fulfilled;
+      // This is the default anvil wallet
+      const signal = "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266";
+      const signalHash = hashToField(signal);
+      // This is an example appId and action created via https://developer.worldcoin.org
+      const appId = "app_staging_7d23b838b02776cebd87b86ac3248641";
+      const action = "testing";
+      const externalNullifierHash = appIdActionToExternalNullifierHash(
+        appId,
+        action
+      );
+      const merkleRootHash = [
+        ...Buffer.from(idkitSuccessResult.merkle_root.substring(2), "hex"),
+      ];
+      const nullifierHash = [
+        ...Buffer.from(idkitSuccessResult.nullifier_hash.substring(2), "hex"),
+      ];
+      const proof = [
+        ...Buffer.from(idkitSuccessResult.proof.substring(2), "hex"),
+      ];
+      // update the expiry config
+      const oneSecond = new BN(1);
+      await expect(
+        changeConfig({
+          rootExpiry: { verificationType: [0], rootExpiry: oneSecond },
+        })
+      ).to.be.fulfilled;
+      await sleep(1000);
+      await expect(
+        program.methods
+          .verifyGroth16Proof(
+            merkleRootHash,
+            [0],
+            signalHash,
+            nullifierHash,
+            externalNullifierHash,
+            proof
+          )
+          .rpc()
+      ).to.be.rejectedWith("RootExpired.");
+      // put things back the way they were
+      const twentyFourHours = new BN(24 * 60 * 60);
+      await expect(
+        changeConfig({
+          rootExpiry: { verificationType: [0], rootExpiry: twentyFourHours },
+        })
+      ).to.be.fulfilled;
+    }
+  );
+
+  it(
+    fmtTest("propose_config_change", "Rejects an unbounded root expiry"),
+    async () => {
+      // a root expiry of the max u64 would make every root valid forever
+      const change = {
+        rootExpiry: {
+          verificationType: [0],
+          rootExpiry: new BN("ff".repeat(64 / 8), 16),
+        },
+      };
+      await expect(
+        program.methods
+          .proposeConfigChange(change)
+          .accountsPartial({
+            pendingConfigChange: derivePendingConfigChangeKey(
+              program.programId,
+              change
+            ),
+          })
+          .rpc()
+      ).to.be.rejectedWith("ConfigValueOutOfBounds.");
+    }
+  );
+
+  it(fmtTest("verify_groth16_proof", "Rejects an invalid proof"), async () => {
+    // This is the default anvil wallet
+    const signal = "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266";
+    const signalHash = hashToField(signal);
+    const badSignal = "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92260";
+    const badSignalHash = hashToField(badSignal);
+    // This is an example appId and action created via https://developer.worldcoin.org
+    const appId = "app_staging_7d23b838b02776cebd87b86ac3248641";
+    const action = "testing";
+    const externalNullifierHash = appIdActionToExternalNullifierHash(
+      appId,
+      action
+    );
+    const badExternalNullifierHash = appIdActionToExternalNullifierHash(
+      appId,
+      "garbage"
+    );
+    const rootHash = [
+      ...Buffer.from(idkitSuccessResult.merkle_root.substring(2), "hex"),
+    ];
+    const nullifierHash = [
+      ...Buffer.from(idkitSuccessResult.nullifier_hash.substring(2), "hex"),
+    ];
+    const badNullifierHash = [
+      ...Buffer.from(
+        "00" + idkitSuccessResult.nullifier_hash.substring(4),
+        "hex"
+      ),
+    ];
+    const proof = [
+      ...Buffer.from(idkitSuccessResult.proof.substring(2), "hex"),
+    ];
+    const badProof = [
+      ...Buffer.from("00" + idkitSuccessResult.proof.substring(4), "hex"),
+    ];
+    await expect(
+      program.methods
+        .verifyGroth16Proof(
+          rootHash,
+          [0],
+          badSignalHash,
+          nullifierHash,
+          externalNullifierHash,
+          proof
+        )
+        .rpc()
+    ).to.be.rejectedWith("Groth16ProofVerificationFailed.");
+    await expect(
+      program.methods
+        .verifyGroth16Proof(
+          rootHash,
+          [0],
+          signalHash,
+          badNullifierHash,
+          externalNullifierHash,
+          proof
+        )
+        .rpc()
+    ).to.be.rejectedWith("Groth16ProofVerificationFailed.");
+    await expect(
+      program.methods
+        .verifyGroth16Proof(
+          rootHash,
+          [0],
+          signalHash,
+          nullifierHash,
+          badExternalNullifierHash,
+          proof
+        )
+        .rpc()
+    ).to.be.rejectedWith("Groth16ProofVerificationFailed.");
+    await expect(
+      program.methods
+        .verifyGroth16Proof(
+          rootHash,
+          [0],
+          signalHash,
+          nullifierHash,
+          externalNullifierHash,
+          badProof
+        )
+        .rpc()
+    ).to.be.rejectedWith("InvalidProofA.");
+  });
+
+  it(
+    fmtTest(
+      "verify_groth16_proof",
+      "Rejects out of range public inputs and invalid proof points"
+    ),
+    async () => {
+      const args = validGroth16ProofArgs();
+      const outOfRange = Array(32).fill(0xff);
+      const offCurvePoint = [...Array(31).fill(0), 1, ...Array(31).fill(0), 3];
+      const verify = (overrides: Partial<typeof args>) => {
+        const {
+          rootHash,
+          signalHash,
+          nullifierHash,
+          externalNullifierHash,
+          proof,
+        } = { ...args, ...overrides };
+        return program.methods
+          .verifyGroth16Proof(
+            rootHash,
+            [0],
+            signalHash,
+            nullifierHash,
+            externalNullifierHash,
+            proof
+          )
+          .rpc();
+      };
+      await expect(verify({ signalHash: outOfRange })).to.be.rejectedWith(
+        "SignalHashOutOfRange."
+      );
+      await expect(verify({ nullifierHash: outOfRange })).to.be.rejectedWith(
+        "NullifierHashOutOfRange."
+      );
+      await expect(
+        verify({ externalNullifierHash: outOfRange })
+      ).to.be.rejectedWith("ExternalNullifierHashOutOfRange.");
+      await expect(
+        verify({ proof: [...offCurvePoint, ...args.proof.slice(64)] })
+      ).to.be.rejectedWith("InvalidProofA.");
+      const badProofB = [...args.proof];
+      badProofB[127] ^= 1;
+      await expect(verify({ proof: badProofB })).to.be.rejectedWith(
+        "InvalidProofB."
+      );
+      await expect(
+        verify({ proof: [...args.proof.slice(0, 192), ...offCurvePoint] })
+      ).to.be.rejectedWith("InvalidProofC.");
+    }
+  );
+
+  it(
+    fmtTest("register_verifying_key", "Rejects owner account mismatch"),
+    async () => {
+      const program = programPaidBy(next_owner);
+      await expect(
+        program.methods
+          .registerVerifyingKey([1], verifyingKeyArgs(builtInVerifyingKey))
+          .rpc()
+      ).to.be.rejectedWith(
+        "AnchorError caused by account: config. Error Code: ConstraintHasOne."
+      );
+    }
+  );
+
+  it(
+    fmtTest("register_verifying_key", "Rejects an invalid verifying key"),
+    async () => {
+      // (1, 3) is not on the curve
+      const alphaG1 = "00".repeat(31) + "01" + "00".repeat(31) + "03";
+      await expect(
+        program.methods
+          .registerVerifyingKey(
+            [1],
+            verifyingKeyArgs({ ...builtInVerifyingKey, alphaG1 })
+          )
+          .rpc()
+      ).to.be.rejectedWith("InvalidVerifyingKey.");
+    }
+  );
+
+  it(
+    fmtTest("register_verifying_key", "Successfully registers a verifying key"),
+    async () => {
+      await expect(
+        program.methods
+          .registerVerifyingKey([1], verifyingKeyArgs(builtInVerifyingKey))
+          .rpc()
+      ).to.be.fulfilled;
+      const verifyingKey = await program.account.verifyingKey.fetch(
+        deriveVerifyingKeyKey(program.programId, 1)
+      );
+      assert(verifyingKey.keyId[0] === 1, "key id does not match");
+      assert(!verifyingKey.retired, "verifying key is retired");
+      assert(
+        Buffer.from(verifyingKey.alphaG1).equals(
+          Buffer.from(builtInVerifyingKey.alphaG1, "hex")
+        ),
+        "alpha does not match"
+      );
+    }
+  );
+
+  it(
+    fmtTest(
+      "verify_groth16_proof",
+      "Rejects a verifying key which the verification type does not use"
+    ),
+    async () => {
+      const args = validGroth16ProofArgs();
+      await expect(
+        program.methods
+          .verifyGroth16Proof(
+            args.rootHash,
+            [0],
+            args.signalHash,
+            args.nullifierHash,
+            args.externalNullifierHash,
+            args.proof
+          )
+          .accountsPartial({
+            verifyingKey: deriveVerifyingKeyKey(program.programId, 1),
+          })
+          .rpc()
+      ).to.be.rejectedWith("VerifyingKeyMismatch.");
+    }
+  );
+
+  it(
+    fmtTest("propose_verifying_key", "Rejects owner account mismatch"),
+    async () => {
+      const program = programPaidBy(next_owner);
+      await expect(
+        program.methods
+          .proposeVerifyingKey([0], new BN(0))
+          .accountsPartial({
+            verifyingKey: deriveVerifyingKeyKey(program.programId, 1),
+          })
+          .rpc()
+      ).to.be.rejectedWith(
+        "AnchorError caused by account: config. Error Code: ConstraintHasOne."
+      );
+    }
+  );
+
+  it(
+    fmtTest(
+      "activate_verifying_key",
+      "Rejects without a pending verifying key"
+    ),
+    async () => {
+      await expect(
+        program.methods
+          .activateVerifyingKey([0])
+          .accountsPartial({ verifyingKey: null })
+          .rpc()
+      ).to.be.rejectedWith("NoPendingVerifyingKey.");
+    }
+  );
+
+  it(
+    fmtTest(
+      "propose_verifying_key",
+      "Successfully proposes a registered verifying key"
+    ),
+    async () => {
+      await expect(
+        program.methods
+          .proposeVerifyingKey([0], new BN(0))
           .accountsPartial({
-            newOwner: anchor.getProvider().publicKey,
-            programData,
+            verifyingKey: deriveVerifyingKeyKey(program.programId, 1),
           })
           .rpc()
       ).to.be.fulfilled;
-      const config = await program.account.config.fetch(
-        deriveConfigKey(program.programId)
+      const verificationTypeConfig =
+        await program.account.verificationTypeConfig.fetch(
+          deriveVerificationTypeConfigKey(program.programId, 0)
+        );
+      assert(
+        verificationTypeConfig.pendingVerifyingKey?.keyId?.[0] === 1,
+        "pending verifying key id does not match"
       );
-      assert(config.pendingOwner === null, "pending owner does not match");
       assert(
-        config.owner.equals(anchor.getProvider().publicKey),
-        "owner does not match"
+        verificationTypeConfig.verifyingKeyId === null,
+        "verifying key id is set"
       );
     }
   );
 
-  // This cannot complete because `transfer_ownership` cannot complete
-  it.skip(
-    fmtTest("claim_ownership", "Successfully completes ownership transfer"),
+  it(
+    fmtTest("activate_verifying_key", "Rejects before the activation delay"),
     async () => {
-      const programData = anchor.web3.PublicKey.findProgramAddressSync(
-        [program.programId.toBuffer()],
-        new anchor.web3.PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
-      )[0];
       await expect(
         program.methods
-          .transferOwnership()
+          .activateVerifyingKey([0])
           .accountsPartial({
-            newOwner: next_owner.publicKey,
-            programData,
+            verifyingKey: deriveVerifyingKeyKey(program.programId, 1),
           })
           .rpc()
-      ).to.be.fulfilled;
-      const programNextOwner = programPaidBy(next_owner);
+      ).to.be.rejectedWith("VerifyingKeyActivationDelayNotElapsed.");
+    }
+  );
+
+  it(
+    fmtTest(
+      "execute_config_change",
+      "Successfully updates the activation delay"
+    ),
+    async () => {
+      const zero = new BN(0);
+      const tx = await changeConfig({
+        verifyingKeyActivationDelay: { verifyingKeyActivationDelay: zero },
+      });
+      const config = await program.account.config.fetch(
+        deriveConfigKey(program.programId)
+      );
+      assert(
+        config.verifyingKeyActivationDelay.eq(zero),
+        "config does not match"
+      );
+      const [event] = await getCpiEvents(program, tx);
+      assert(
+        event.name === "verifyingKeyActivationDelayUpdated",
+        "event does not match"
+      );
+      assert(
+        event.data.verifyingKeyActivationDelay.eq(zero),
+        "event does not match"
+      );
+    }
+  );
+
+  it(
+    fmtTest(
+      "activate_verifying_key",
+      "Successfully activates a registered verifying key"
+    ),
+    async () => {
+      // Proposed again, as the first proposal is subject to the old delay.
+      await program.methods
+        .proposeVerifyingKey([0], new BN(0))
+        .accountsPartial({
+          verifyingKey: deriveVerifyingKeyKey(program.programId, 1),
+        })
+        .rpc();
       await expect(
-        programNextOwner.methods
-          .claimOwnership()
+        program.methods
+          .activateVerifyingKey([0])
           .accountsPartial({
-            newOwner: next_owner.publicKey,
-            programData,
+            verifyingKey: deriveVerifyingKeyKey(program.programId, 1),
           })
           .rpc()
       ).to.be.fulfilled;
-      const config = await program.account.config.fetch(
-        deriveConfigKey(program.programId)
+      const verificationTypeConfig =
+        await program.account.verificationTypeConfig.fetch(
+          deriveVerificationTypeConfigKey(program.programId, 0)
+        );
+      assert(
+        verificationTypeConfig.verifyingKeyId?.[0] === 1,
+        "verifying key id does not match"
+      );
+      assert(
+        verificationTypeConfig.pendingVerifyingKey === null,
+        "pending verifying key is set"
+      );
+      assert(
+        verificationTypeConfig.previousVerifyingKey === null,
+        "previous verifying key is set"
       );
-      assert(config.pendingOwner === null, "pending owner does not match");
-      assert(config.owner.equals(next_owner.publicKey), "owner does not match");
     }
   );
 
   it(
     fmtTest(
       "verify_groth16_proof",
-      "Successfully verifies a valid groth16 proof"
+      "Rejects without the verifying key of the verification type"
     ),
     async () => {
-      // This is the default anvil wallet
-      const signal = "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266";
-      const signalHash = hashToField(signal);
-      // This is an example appId and action created via https://developer.worldcoin.org
-      const appId = "app_staging_7d23b838b02776cebd87b86ac3248641";
-      const action = "testing";
-      const externalNullifierHash = appIdActionToExternalNullifierHash(
-        appId,
-        action
-      );
-      const rootHash = [
-        ...Buffer.from(idkitSuccessResult.merkle_root.substring(2), "hex"),
-      ];
-      const nullifierHash = [
-        ...Buffer.from(idkitSuccessResult.nullifier_hash.substring(2), "hex"),
-      ];
-      const proof = [
-        ...Buffer.from(idkitSuccessResult.proof.substring(2), "hex"),
-      ];
+      const args = validGroth16ProofArgs();
       await expect(
         program.methods
           .verifyGroth16Proof(
-            rootHash,
+            args.rootHash,
             [0],
-            signalHash,
-            nullifierHash,
-            externalNullifierHash,
-            proof
+            args.signalHash,
+            args.nullifierHash,
+            args.externalNullifierHash,
+            args.proof
           )
           .rpc()
-      ).to.be.fulfilled;
+      ).to.be.rejectedWith("VerifyingKeyNotProvided.");
     }
   );
 
   it(
     fmtTest(
       "verify_groth16_proof",
-      "Rejects root hash without a corresponding PDA"
+      "Successfully verifies with a registered verifying key"
     ),
     async () => {
-      // This is the default anvil wallet
-      const signal = "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266";
-      const signalHash = hashToField(signal);
-      // This is an example appId and action created via https://developer.worldcoin.org
-      const appId = "app_staging_7d23b838b02776cebd87b86ac3248641";
-      const action = "testing";
-      const externalNullifierHash = appIdActionToExternalNullifierHash(
-        appId,
-        action
-      );
-      const rootHash = [
-        ...Buffer.from(
-          "00" + idkitSuccessResult.merkle_root.substring(4),
-          "hex"
-        ),
-      ];
-      const nullifierHash = [
-        ...Buffer.from(idkitSuccessResult.nullifier_hash.substring(2), "hex"),
-      ];
-      const proof = [
-        ...Buffer.from(idkitSuccessResult.proof.substring(2), "hex"),
-      ];
+      const args = validGroth16ProofArgs();
       await expect(
         program.methods
           .verifyGroth16Proof(
-            rootHash,
+            args.rootHash,
             [0],
-            signalHash,
-            nullifierHash,
-            externalNullifierHash,
-            proof
+            args.signalHash,
+            args.nullifierHash,
+            args.externalNullifierHash,
+            args.proof
           )
+          .accountsPartial({
+            verifyingKey: deriveVerifyingKeyKey(program.programId, 1),
+          })
           .rpc()
-      ).to.be.rejectedWith("AccountNotInitialized.");
+      ).to.be.fulfilled;
+    }
+  );
+
+  it(
+    fmtTest(
+      "activate_verifying_key",
+      "Successfully resets to the built-in key with a grace period"
+    ),
+    async () => {
+      const oneHour = new BN(60 * 60);
+      await program.methods
+        .proposeVerifyingKey([0], oneHour)
+        .accountsPartial({ verifyingKey: null })
+        .rpc();
+      await expect(
+        program.methods
+          .activateVerifyingKey([0])
+          .accountsPartial({ verifyingKey: null })
+          .rpc()
+      ).to.be.fulfilled;
+      const verificationTypeConfig =
+        await program.account.verificationTypeConfig.fetch(
+          deriveVerificationTypeConfigKey(program.programId, 0)
+        );
+      assert(
+        verificationTypeConfig.verifyingKeyId === null,
+        "verifying key id is set"
+      );
+      assert(
+        verificationTypeConfig.previousVerifyingKey?.keyId?.[0] === 1,
+        "previous verifying key id does not match"
+      );
     }
   );
 
   it(
     fmtTest(
       "verify_groth16_proof",
-      "Rejects root hash instruction argument mismatch"
+      "Successfully verifies with the previous key during its grace period"
     ),
     async () => {
-      // This is the default anvil wallet
-      const signal = "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266";
-      const signalHash = hashToField(signal);
-      // This is an example appId and action created via https://developer.worldcoin.org
-      const appId = "app_staging_7d23b838b02776cebd87b86ac3248641";
-      const action = "testing";
-      const externalNullifierHash = appIdActionToExternalNullifierHash(
-        appId,
-        action
+      const args = validGroth16ProofArgs();
+      for (const verifyingKey of [
+        deriveVerifyingKeyKey(program.programId, 1),
+        null,
+      ]) {
+        await expect(
+          program.methods
+            .verifyGroth16Proof(
+              args.rootHash,
+              [0],
+              args.signalHash,
+              args.nullifierHash,
+              args.externalNullifierHash,
+              args.proof
+            )
+            .accountsPartial({ verifyingKey })
+            .rpc()
+        ).to.be.fulfilled;
+      }
+    }
+  );
+
+  it(
+    fmtTest("retire_verifying_key", "Rejects owner account mismatch"),
+    async () => {
+      const program = programPaidBy(next_owner);
+      await expect(
+        program.methods.retireVerifyingKey([1]).rpc()
+      ).to.be.rejectedWith(
+        "AnchorError caused by account: config. Error Code: ConstraintHasOne."
       );
-      const badRootHash = [
-        ...Buffer.from(
-          "00" + idkitSuccessResult.merkle_root.substring(4),
-          "hex"
-        ),
-      ];
-      const nullifierHash = [
-        ...Buffer.from(idkitSuccessResult.nullifier_hash.substring(2), "hex"),
-      ];
-      const proof = [
-        ...Buffer.from(idkitSuccessResult.proof.substring(2), "hex"),
-      ];
+    }
+  );
+
+  it(
+    fmtTest("retire_verifying_key", "Successfully retires a verifying key"),
+    async () => {
+      await expect(program.methods.retireVerifyingKey([1]).rpc()).to.be
+        .fulfilled;
+      const verifyingKey = await program.account.verifyingKey.fetch(
+        deriveVerifyingKeyKey(program.programId, 1)
+      );
+      assert(verifyingKey.retired, "verifying key is not retired");
+    }
+  );
+
+  it(
+    fmtTest("propose_verifying_key", "Rejects a retired verifying key"),
+    async () => {
       await expect(
         program.methods
-          .verifyGroth16Proof(
-            badRootHash,
-            [0],
-            signalHash,
-            nullifierHash,
-            externalNullifierHash,
-            proof
-          )
+          .proposeVerifyingKey([0], new BN(0))
           .accountsPartial({
-            root: deriveRootKey(
-              program.programId,
-              Buffer.from(idkitSuccessResult.merkle_root.substring(2), "hex"),
-              0
-            ),
+            verifyingKey: deriveVerifyingKeyKey(program.programId, 1),
           })
           .rpc()
-      ).to.be.rejectedWith(
-        "AnchorError caused by account: root. Error Code: ConstraintSeeds."
+      ).to.be.rejectedWith("RetiredVerifyingKey.");
+    }
+  );
+
+  it(fmtTest("grant_role", "Rejects owner account mismatch"), async () => {
+    const program = programPaidBy(next_owner);
+    await expect(
+      program.methods.grantRole({ pauser: {} }, next_owner.publicKey).rpc()
+    ).to.be.rejectedWith(
+      "AnchorError caused by account: config. Error Code: ConstraintHasOne."
+    );
+  });
+
+  it(
+    fmtTest("pause", "Rejects without the owner or pauser as signer"),
+    async () => {
+      const program = programPaidBy(next_owner);
+      await expect(program.methods.pause(2).rpc()).to.be.rejectedWith(
+        "InvalidPauser."
+      );
+    }
+  );
+
+  it(fmtTest("grant_role", "Successfully grants the pauser role"), async () => {
+    const tx = await program.methods
+      .grantRole({ pauser: {} }, next_owner.publicKey)
+      .rpc({ commitment: "confirmed" });
+    const config = await program.account.config.fetch(
+      deriveConfigKey(program.programId)
+    );
+    assert(
+      config.pauser?.equals(next_owner.publicKey),
+      "pauser does not match"
+    );
+    const [event] = await getCpiEvents(program, tx);
+    assert(event.name === "roleGranted", "event does not match");
+    assert(
+      event.data.grantee.equals(next_owner.publicKey),
+      "event does not match"
+    );
+  });
+
+  it(fmtTest("pause", "Rejects invalid flags"), async () => {
+    await expect(program.methods.pause(8).rpc()).to.be.rejectedWith(
+      "InvalidPauseFlags."
+    );
+  });
+
+  it(
+    fmtTest("pause", "Successfully pauses proof verification as the pauser"),
+    async () => {
+      const program = programPaidBy(next_owner);
+      const tx = await program.methods
+        .pause(2)
+        .rpc({ commitment: "confirmed" });
+      const config = await program.account.config.fetch(
+        deriveConfigKey(program.programId)
+      );
+      assert(config.paused === 2, "paused does not match");
+      const [event] = await getCpiEvents(program, tx);
+      assert(event.name === "pausedUpdated", "event does not match");
+      assert(event.data.paused === 2, "event does not match");
+    }
+  );
+
+  it(
+    fmtTest(
+      "verify_groth16_proof",
+      "Rejects while proof verification is paused"
+    ),
+    async () => {
+      const args = validGroth16ProofArgs();
+      await expect(
+        program.methods
+          .verifyGroth16Proof(
+            args.rootHash,
+            [0],
+            args.signalHash,
+            args.nullifierHash,
+            args.externalNullifierHash,
+            args.proof
+          )
+          .rpc()
+      ).to.be.rejectedWith("ProofVerificationPaused.");
+    }
+  );
+
+  it(fmtTest("unpause", "Rejects owner account mismatch"), async () => {
+    const program = programPaidBy(next_owner);
+    await expect(program.methods.unpause(2).rpc()).to.be.rejectedWith(
+      "AnchorError caused by account: config. Error Code: ConstraintHasOne."
+    );
+  });
+
+  it(
+    fmtTest("unpause", "Successfully unpauses proof verification"),
+    async () => {
+      await expect(program.methods.unpause(2).rpc()).to.be.fulfilled;
+      const config = await program.account.config.fetch(
+        deriveConfigKey(program.programId)
+      );
+      assert(config.paused === 0, "paused does not match");
+    }
+  );
+
+  it(fmtTest("revoke_role", "Rejects owner account mismatch"), async () => {
+    const program = programPaidBy(next_owner);
+    await expect(
+      program.methods.revokeRole({ pauser: {} }).rpc()
+    ).to.be.rejectedWith(
+      "AnchorError caused by account: config. Error Code: ConstraintHasOne."
+    );
+  });
+
+  it(
+    fmtTest("revoke_role", "Successfully revokes the pauser role"),
+    async () => {
+      const tx = await program.methods
+        .revokeRole({ pauser: {} })
+        .rpc({ commitment: "confirmed" });
+      const config = await program.account.config.fetch(
+        deriveConfigKey(program.programId)
+      );
+      assert(config.pauser === null, "pauser is not revoked");
+      const [event] = await getCpiEvents(program, tx);
+      assert(event.name === "roleRevoked", "event does not match");
+      assert(
+        event.data.previousGrantee?.equals(next_owner.publicKey),
+        "event does not match"
       );
     }
   );
 
   it(
     fmtTest(
-      "verify_groth16_proof",
-      "Rejects verification type instruction argument mismatch"
+      "verify_groth16_proof_with_raw_inputs",
+      "Successfully verifies a valid groth16 proof"
     ),
     async () => {
-      // This is the default anvil wallet
-      const signal = "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266";
-      const signalHash = hashToField(signal);
-      // This is an example appId and action created via https://developer.worldcoin.org
-      const appId = "app_staging_7d23b838b02776cebd87b86ac3248641";
-      const action = "testing";
-      const externalNullifierHash = appIdActionToExternalNullifierHash(
-        appId,
-        action
+      const args = validGroth16ProofArgs();
+      const tx = await program.methods
+        .verifyGroth16ProofWithRawInputs(
+          args.rootHash,
+          [0],
+          "app_staging_7d23b838b02776cebd87b86ac3248641",
+          "testing",
+          Buffer.from("f39Fd6e51aad88F6F4ce6aB8827279cffFb92266", "hex"),
+          args.nullifierHash,
+          args.proof
+        )
+        .rpc({ commitment: "confirmed" });
+      const [event] = await getCpiEvents(program, tx);
+      assert(event.name === "proofVerified", "event does not match");
+      assert(
+        Buffer.from(event.data.signalHash).equals(Buffer.from(args.signalHash)),
+        "event does not match"
       );
-      const rootHash = [
-        ...Buffer.from(idkitSuccessResult.merkle_root.substring(2), "hex"),
-      ];
-      const nullifierHash = [
-        ...Buffer.from(idkitSuccessResult.nullifier_hash.substring(2), "hex"),
-      ];
-      const proof = [
-        ...Buffer.from(idkitSuccessResult.proof.substring(2), "hex"),
-      ];
+      assert(
+        Buffer.from(event.data.externalNullifierHash).equals(
+          Buffer.from(args.externalNullifierHash)
+        ),
+        "event does not match"
+      );
+    }
+  );
+
+  it(
+    fmtTest("verify_groth16_proof_with_raw_inputs", "Rejects an invalid action"),
+    async () => {
+      const args = validGroth16ProofArgs();
       await expect(
         program.methods
-          .verifyGroth16Proof(
-            rootHash,
-            [1],
-            signalHash,
-            nullifierHash,
-            externalNullifierHash,
-            proof
+          .verifyGroth16ProofWithRawInputs(
+            args.rootHash,
+            [0],
+            "app_staging_7d23b838b02776cebd87b86ac3248641",
+            "garbage",
+            Buffer.from("f39Fd6e51aad88F6F4ce6aB8827279cffFb92266", "hex"),
+            args.nullifierHash,
+            args.proof
           )
-          .accountsPartial({
-            root: deriveRootKey(program.programId, Buffer.from(rootHash), 0),
-            latestRoot: deriveLatestRootKey(program.programId, 0),
-          })
           .rpc()
-      ).to.be.rejectedWith(
-        "AnchorError caused by account: root. Error Code: ConstraintSeeds."
-      );
+      ).to.be.rejectedWith("Groth16ProofVerificationFailed.");
     }
   );
 
   it(
     fmtTest(
-      "verify_groth16_proof",
-      "Successfully verifies against an expired, but latest root"
+      "verify_and_consume_nullifier",
+      "Successfully verifies and consumes a nullifier"
     ),
     async () => {
       // This is the default anvil wallet
@@ -2119,14 +4559,9 @@ describe("solana-world-id-program", () => {
       const proof = [
         ...Buffer.from(idkitSuccessResult.proof.substring(2), "hex"),
       ];
-      // update the expiry config
-      const oneSecond = new BN(1);
-      await expect(program.methods.setRootExpiry(oneSecond).rpc()).to.be
-        .fulfilled;
-      await sleep(1000);
       await expect(
         program.methods
-          .verifyGroth16Proof(
+          .verifyAndConsumeNullifier(
             rootHash,
             [0],
             signalHash,
@@ -2136,43 +4571,34 @@ describe("solana-world-id-program", () => {
           )
           .rpc()
       ).to.be.fulfilled;
-      // put things back the way they were
-      const twentyFourHours = new BN(24 * 60 * 60);
-      await expect(program.methods.setRootExpiry(twentyFourHours).rpc()).to.be
-        .fulfilled;
+      const nullifier = await program.account.nullifier.fetch(
+        deriveNullifierKey(
+          program.programId,
+          Buffer.from(externalNullifierHash),
+          Buffer.from(nullifierHash)
+        )
+      );
+      assert(
+        Buffer.from(nullifier.nullifierHash).equals(Buffer.from(nullifierHash)),
+        "nullifierHash does not match"
+      );
+      assert(
+        Buffer.from(nullifier.externalNullifierHash).equals(
+          Buffer.from(externalNullifierHash)
+        ),
+        "externalNullifierHash does not match"
+      );
+      assert(
+        Buffer.from(nullifier.root).equals(Buffer.from(rootHash)),
+        "root does not match"
+      );
+      assert(nullifier.consumed, "nullifier is not consumed");
     }
   );
 
   it(
-    fmtTest("verify_groth16_proof", "Rejects an expired, non-latest root"),
+    fmtTest("verify_and_consume_nullifier", "Rejects a consumed nullifier"),
     async () => {
-      // Update a new root
-      const signatureSet = anchor.web3.Keypair.generate();
-      const futureResponse = QueryResponse.from(mockQueryResponse.bytes);
-      const mockEthCallQueryResponse = futureResponse.responses[0]
-        .response as EthCallQueryResponse;
-      mockEthCallQueryResponse.blockNumber += BigInt(3);
-      const futureResponseBytes = futureResponse.serialize();
-      const futureResponseSigs = new QueryProxyMock({}).sign(
-        futureResponseBytes
-      );
-      await postQuerySigs(futureResponseSigs, signatureSet);
-      await expect(
-        program.methods
-          .updateRootWithQuery(
-            Buffer.from(futureResponseBytes),
-            [...Buffer.from(rootHash, "hex")],
-            mockGuardianSetIndex
-          )
-          .accountsPartial({
-            guardianSet: deriveGuardianSetKey(
-              coreBridgeAddress,
-              mockGuardianSetIndex
-            ),
-            guardianSignatures: signatureSet.publicKey,
-          })
-          .rpc()
-      ).to.be.fulfilled;
       // This is the default anvil wallet
       const signal = "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266";
       const signalHash = hashToField(signal);
@@ -2183,7 +4609,7 @@ describe("solana-world-id-program", () => {
         appId,
         action
       );
-      const merkleRootHash = [
+      const rootHash = [
         ...Buffer.from(idkitSuccessResult.merkle_root.substring(2), "hex"),
       ];
       const nullifierHash = [
@@ -2192,15 +4618,10 @@ describe("solana-world-id-program", () => {
       const proof = [
         ...Buffer.from(idkitSuccessResult.proof.substring(2), "hex"),
       ];
-      // update the expiry config
-      const oneSecond = new BN(1);
-      await expect(program.methods.setRootExpiry(oneSecond).rpc()).to.be
-        .fulfilled;
-      await sleep(1000);
       await expect(
         program.methods
-          .verifyGroth16Proof(
-            merkleRootHash,
+          .verifyAndConsumeNullifier(
+            rootHash,
             [0],
             signalHash,
             nullifierHash,
@@ -2208,36 +4629,21 @@ describe("solana-world-id-program", () => {
             proof
           )
           .rpc()
-      ).to.be.rejectedWith("RootExpired.");
-      // put things back the way they were
-      const twentyFourHours = new BN(24 * 60 * 60);
-      await expect(program.methods.setRootExpiry(twentyFourHours).rpc()).to.be
-        .fulfilled;
+      ).to.be.rejectedWith("NullifierAlreadyConsumed.");
     }
   );
 
   it(
-    fmtTest(
-      "verify_groth16_proof",
-      "Successfully verifies when is_active would overflow"
-    ),
+    fmtTest("verify_and_consume_nullifier", "Rejects an invalid proof"),
     async () => {
-      // root expiry is only checked when it isn't the latest root
-      // the test above makes that the case
-
-      // set the root expiry config to the max u64
-      await expect(
-        program.methods.setRootExpiry(new BN("ff".repeat(64 / 8), 16)).rpc()
-      ).to.be.fulfilled;
       // This is the default anvil wallet
       const signal = "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266";
       const signalHash = hashToField(signal);
       // This is an example appId and action created via https://developer.worldcoin.org
       const appId = "app_staging_7d23b838b02776cebd87b86ac3248641";
-      const action = "testing";
-      const externalNullifierHash = appIdActionToExternalNullifierHash(
+      const badExternalNullifierHash = appIdActionToExternalNullifierHash(
         appId,
-        action
+        "garbage"
       );
       const rootHash = [
         ...Buffer.from(idkitSuccessResult.merkle_root.substring(2), "hex"),
@@ -2250,108 +4656,28 @@ describe("solana-world-id-program", () => {
       ];
       await expect(
         program.methods
-          .verifyGroth16Proof(
+          .verifyAndConsumeNullifier(
             rootHash,
             [0],
             signalHash,
             nullifierHash,
-            externalNullifierHash,
+            badExternalNullifierHash,
             proof
           )
           .rpc()
-      ).to.be.fulfilled;
-      // put things back the way they were
-      const twentyFourHours = new BN(24 * 60 * 60);
-      await expect(program.methods.setRootExpiry(twentyFourHours).rpc()).to.be
-        .fulfilled;
+      ).to.be.rejectedWith("Groth16ProofVerificationFailed.");
+      await expect(
+        program.account.nullifier.fetch(
+          deriveNullifierKey(
+            program.programId,
+            Buffer.from(badExternalNullifierHash),
+            Buffer.from(nullifierHash)
+          )
+        )
+      ).to.be.rejectedWith("Account does not exist or has no data");
     }
   );
 
-  it(fmtTest("verify_groth16_proof", "Rejects an invalid proof"), async () => {
-    // This is the default anvil wallet
-    const signal = "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266";
-    const signalHash = hashToField(signal);
-    const badSignal = "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92260";
-    const badSignalHash = hashToField(badSignal);
-    // This is an example appId and action created via https://developer.worldcoin.org
-    const appId = "app_staging_7d23b838b02776cebd87b86ac3248641";
-    const action = "testing";
-    const externalNullifierHash = appIdActionToExternalNullifierHash(
-      appId,
-      action
-    );
-    const badExternalNullifierHash = appIdActionToExternalNullifierHash(
-      appId,
-      "garbage"
-    );
-    const rootHash = [
-      ...Buffer.from(idkitSuccessResult.merkle_root.substring(2), "hex"),
-    ];
-    const nullifierHash = [
-      ...Buffer.from(idkitSuccessResult.nullifier_hash.substring(2), "hex"),
-    ];
-    const badNullifierHash = [
-      ...Buffer.from(
-        "00" + idkitSuccessResult.nullifier_hash.substring(4),
-        "hex"
-      ),
-    ];
-    const proof = [
-      ...Buffer.from(idkitSuccessResult.proof.substring(2), "hex"),
-    ];
-    const badProof = [
-      ...Buffer.from("00" + idkitSuccessResult.proof.substring(4), "hex"),
-    ];
-    await expect(
-      program.methods
-        .verifyGroth16Proof(
-          rootHash,
-          [0],
-          badSignalHash,
-          nullifierHash,
-          externalNullifierHash,
-          proof
-        )
-        .rpc()
-    ).to.be.rejectedWith("Groth16ProofVerificationFailed.");
-    await expect(
-      program.methods
-        .verifyGroth16Proof(
-          rootHash,
-          [0],
-          signalHash,
-          badNullifierHash,
-          externalNullifierHash,
-          proof
-        )
-        .rpc()
-    ).to.be.rejectedWith("Groth16ProofVerificationFailed.");
-    await expect(
-      program.methods
-        .verifyGroth16Proof(
-          rootHash,
-          [0],
-          signalHash,
-          nullifierHash,
-          badExternalNullifierHash,
-          proof
-        )
-        .rpc()
-    ).to.be.rejectedWith("Groth16ProofVerificationFailed.");
-    await expect(
-      program.methods
-        .verifyGroth16Proof(
-          rootHash,
-          [0],
-          signalHash,
-          nullifierHash,
-          externalNullifierHash,
-          badProof
-        )
-        .rpc()
-    ).to.be.rejectedWith("Groth16ProofVerificationFailed.");
-  });
-
   it(
     fmtTest("close_signatures", "Successfully closes signature accounts"),
     async () => {
@@ -2360,17 +4686,21 @@ describe("solana-world-id-program", () => {
       await expect(
         program.account.guardianSignatures.fetch(signatureSet.publicKey)
       ).to.be.fulfilled;
-      await expect(
-        program.methods
-          .closeSignatures()
-          .accounts({
-            guardianSignatures: signatureSet.publicKey,
-          })
-          .rpc()
-      ).to.be.fulfilled;
+      const tx = await program.methods
+        .closeSignatures()
+        .accounts({
+          guardianSignatures: signatureSet.publicKey,
+        })
+        .rpc({ commitment: "confirmed" });
       await expect(
         program.account.guardianSignatures.fetch(signatureSet.publicKey)
       ).to.be.rejectedWith("Account does not exist or has no data");
+      const [event] = await getCpiEvents(program, tx);
+      assert(event.name === "signaturesClosed", "event does not match");
+      assert(
+        event.data.guardianSignatures.equals(signatureSet.publicKey),
+        "event does not match"
+      );
     }
   );
 